@@ -260,7 +260,7 @@ impl Ty {
             &Ty::Vector3Array => Some(String::from("Vector3Array")),
             &Ty::ColorArray => Some(String::from("ColorArray")),
             &Ty::Int32Array => Some(String::from("Int32Array")),
-            &Ty::Float32Array => Some(String::from("Float32Array")),
+            &Ty::Float32Array => Some(String::from("RealArray")),
             &Ty::Result => Some(String::from("GodotResult")),
             &Ty::VariantType => Some(String::from("VariantType")),
             &Ty::Enum(ref name) => Some(String::from(name.clone())),
@@ -2,8 +2,10 @@ use gdnative::*;
 
 #[derive(NativeClass)]
 #[inherit(Node)]
-// register_with attribute can be used to specify custom register function for node signals and properties
-#[register_with(Self::register_signals)]
+// Signals are declared with their argument names, types and optional defaults. The argument
+// list is used by the editor for GUI and generation of GDScript handlers. The derive also
+// generates typed `emit_tick` and `emit_tick_with_data` methods.
+#[signal(tick, tick_with_data(data: i64 = 100))]
 struct SignalEmitter {
     timer: f64,
     data: i64,
@@ -11,24 +13,6 @@ struct SignalEmitter {
 
 #[methods]
 impl SignalEmitter {
-    fn register_signals(builder: &init::ClassBuilder<Self>) {
-        builder.add_signal(init::Signal {
-            name: "tick",
-            args: &[],
-        });
-
-        builder.add_signal(init::Signal {
-            name: "tick_with_data",
-            // Argument list used by the editor for GUI and generation of GDScript handlers. It can be omitted if the signal is only used from code.
-            args: &[init::SignalArgument {
-                name: "data",
-                default: Variant::from_i64(100),
-                export_info: init::ExportInfo::new(VariantType::I64),
-                usage: init::PropertyUsage::DEFAULT,
-            }],
-        });
-    }
-
     fn _init(_owner: gdnative::Node) -> Self {
         SignalEmitter {
             timer: 0.0,
@@ -37,7 +21,7 @@ impl SignalEmitter {
     }
 
     #[export]
-    fn _process(&mut self, mut owner: Node, delta: f64) {
+    fn _process(&mut self, owner: Node, delta: f64) {
         if self.timer < 1.0 {
             self.timer += delta;
             return;
@@ -46,12 +30,9 @@ impl SignalEmitter {
         self.data += 1;
         unsafe {
             if self.data % 2 == 0 {
-                owner.emit_signal(GodotString::from_str("tick"), &[]);
+                self.emit_tick(owner);
             } else {
-                owner.emit_signal(
-                    GodotString::from_str("tick_with_data"),
-                    &[Variant::from_i64(self.data)],
-                );
+                self.emit_tick_with_data(owner, self.data);
             }
         }
     }
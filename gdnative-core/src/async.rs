@@ -0,0 +1,356 @@
+//! A single-threaded executor driven by the main loop, and futures for signals and frames.
+//!
+//! This is the Rust counterpart of GDScript's `yield`. Tasks are spawned with [`spawn`] or
+//! [`spawn_for`], and can await signals with [`SignalFutureExt::signal_future`] and frames
+//! with [`next_frame`]:
+//!
+//! ```ignore
+//! use gdnative::r#async::{next_frame, spawn_for, SignalFutureExt};
+//!
+//! spawn_for(&owner, async move {
+//!     if let Ok(timeout) = unsafe { timer.signal_future("timeout") } {
+//!         unsafe { timer.start(1.0) };
+//!         timeout.await;
+//!     }
+//!     next_frame().await;
+//!     unsafe { owner.queue_free() };
+//! });
+//! ```
+//!
+//! The executor is polled on the `idle_frame` signal of the `SceneTree`. It is connected
+//! when the first task is spawned. Tasks that are woken are polled on the next idle frame,
+//! so a task resumes at most one frame after the signal it awaits was emitted.
+//!
+//! Tasks can only be spawned from the main thread, which is the thread the library was
+//! initialized on, and are always polled there.
+//!
+//! [`spawn`]: fn.spawn.html
+//! [`spawn_for`]: fn.spawn_for.html
+//! [`SignalFutureExt::signal_future`]: trait.SignalFutureExt.html#method.signal_future
+//! [`next_frame`]: fn.next_frame.html
+
+use crate::{
+    connect_closure, connect_closure_oneshot, get_api, sys, ClosureConnection, GodotError,
+    GodotObject, Object, Variant,
+};
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, ThreadId};
+
+thread_local! {
+    static EXECUTOR: RefCell<Executor> = RefCell::new(Executor::default());
+}
+
+/// The thread the library was initialized on, which is the engine's main thread.
+static MAIN_THREAD: RwLock<Option<ThreadId>> = RwLock::new(None);
+
+#[derive(Default)]
+struct Executor {
+    /// Tasks that are not finished yet. Emptied while they are polled: tasks spawned in the
+    /// meantime are added to the polled ones afterwards.
+    tasks: Vec<Task>,
+    /// Number of idle frames since the executor was connected.
+    frame: u64,
+    /// Wakers of the `NextFrame` futures, woken on the next idle frame.
+    frame_wakers: Vec<Waker>,
+    /// Connection of `tick` to the `idle_frame` signal, once the first task is spawned.
+    driver: Option<ClosureConnection>,
+}
+
+struct Task {
+    future: Pin<Box<dyn Future<Output = ()>>>,
+    /// The object the task is tied to, and its instance ID. The task is dropped once it's
+    /// freed.
+    owner: Option<(*mut sys::godot_object, i64)>,
+    woken: Arc<WakeFlag>,
+}
+
+/// Marks a task to be polled on the next idle frame.
+struct WakeFlag(AtomicBool);
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+/// Spawns a task on the executor. It's first polled on the next idle frame.
+///
+/// # Panics
+///
+/// If the API isn't bound yet, if the library wasn't initialized with `godot_gdnative_init!`,
+/// or if called from another thread than the main thread.
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    spawn_task(None, Box::pin(future));
+}
+
+/// Spawns a task tied to the lifetime of `owner`: the task is dropped, without being polled
+/// anymore, once `owner` is freed.
+///
+/// # Panics
+///
+/// If the API isn't bound yet, if the library wasn't initialized with `godot_gdnative_init!`,
+/// or if called from another thread than the main thread.
+pub fn spawn_for<O, F>(owner: &O, future: F)
+where
+    O: GodotObject,
+    F: Future<Output = ()> + 'static,
+{
+    let owner = unsafe {
+        let owner = owner.to_sys();
+        (owner, Object::from_sys(owner).get_instance_id())
+    };
+    spawn_task(Some(owner), Box::pin(future));
+}
+
+fn spawn_task(
+    owner: Option<(*mut sys::godot_object, i64)>,
+    future: Pin<Box<dyn Future<Output = ()>>>,
+) {
+    let main_thread = MAIN_THREAD
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .expect(
+            "async tasks can't be spawned before `godot_gdnative_init!` initialized the library",
+        );
+    assert!(
+        main_thread == thread::current().id(),
+        "async tasks can only be spawned on the main thread"
+    );
+
+    let task = Task {
+        future,
+        owner,
+        woken: Arc::new(WakeFlag(AtomicBool::new(true))),
+    };
+
+    let drive = EXECUTOR.with(|executor| {
+        let mut executor = executor.borrow_mut();
+        executor.tasks.push(task);
+        executor.driver.is_none()
+    });
+
+    if drive {
+        if let Some(driver) = drive_from_main_loop() {
+            EXECUTOR.with(|executor| executor.borrow_mut().driver = Some(driver));
+        }
+    }
+}
+
+/// Connects the executor to the `idle_frame` signal of the main loop. Returns `None` on
+/// failure.
+fn drive_from_main_loop() -> Option<ClosureConnection> {
+    unsafe {
+        let engine = (get_api().godot_global_get_singleton)(b"Engine\0".as_ptr() as *mut _);
+        if engine.is_null() {
+            godot_error!("gdnative-core: the Engine singleton is not available");
+            return None;
+        }
+
+        let mut engine = Object::from_sys(engine);
+        let main_loop = engine
+            .call("get_main_loop".into(), &[])
+            .try_to_object::<Object>();
+        let mut main_loop = match main_loop {
+            Some(main_loop) => main_loop,
+            None => {
+                godot_error!("gdnative-core: there is no main loop to drive async tasks");
+                return None;
+            }
+        };
+
+        match connect_closure(&mut main_loop, "idle_frame", |_| tick()) {
+            Ok(connection) => Some(connection),
+            Err(err) => {
+                godot_error!(
+                    "gdnative-core: cannot drive async tasks from the main loop: {:?}",
+                    err
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Polls the woken tasks. Called on every idle frame.
+fn tick() {
+    let (tasks, frame_wakers) = EXECUTOR.with(|executor| {
+        let mut executor = executor.borrow_mut();
+        executor.frame += 1;
+        (
+            mem::take(&mut executor.tasks),
+            mem::take(&mut executor.frame_wakers),
+        )
+    });
+
+    for waker in frame_wakers {
+        waker.wake();
+    }
+
+    let remaining = tasks
+        .into_iter()
+        .filter_map(|mut task| {
+            if let Some((owner, id)) = task.owner {
+                if !unsafe { is_same_instance(owner, id) } {
+                    return None;
+                }
+            }
+
+            if !task.woken.0.swap(false, Ordering::AcqRel) {
+                return Some(task);
+            }
+
+            let waker = Waker::from(task.woken.clone());
+            let mut context = Context::from_waker(&waker);
+            let future = task.future.as_mut();
+            match panic::catch_unwind(AssertUnwindSafe(|| future.poll(&mut context))) {
+                Ok(Poll::Pending) => Some(task),
+                Ok(Poll::Ready(())) => None,
+                Err(_) => {
+                    godot_error!("gdnative-core: async task panicked");
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    EXECUTOR.with(|executor| {
+        let mut executor = executor.borrow_mut();
+        let spawned = mem::replace(&mut executor.tasks, remaining);
+        executor.tasks.extend(spawned);
+    });
+}
+
+/// Polls the woken tasks right away, as if an idle frame had passed. Used by the integration
+/// tests, which can't wait for the main loop.
+#[doc(hidden)]
+pub fn tick_now() {
+    tick();
+}
+
+/// Returns `true` if `object` still points to the instance with the ID `id`.
+///
+/// GDNative can't look objects up by their ID, so the pointer is checked first, and the ID
+/// makes sure that it wasn't reused by another object after the original one was freed.
+unsafe fn is_same_instance(object: *mut sys::godot_object, id: i64) -> bool {
+    (get_api().godot_is_instance_valid)(object) && Object::from_sys(object).get_instance_id() == id
+}
+
+/// Records the current thread as the main thread. Should only be called from
+/// `crate::init_internal_state`.
+pub(crate) fn init() {
+    *MAIN_THREAD.write().unwrap_or_else(PoisonError::into_inner) = Some(thread::current().id());
+}
+
+/// Drops the tasks of the current thread, and disconnects the executor from the main loop.
+/// Should only be called from `crate::cleanup_internal_state`, while the API is still bound.
+pub(crate) fn cleanup() {
+    let executor = EXECUTOR.with(|executor| executor.replace(Executor::default()));
+    drop(executor);
+    *MAIN_THREAD.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Returns a future that resolves on the next idle frame.
+pub fn next_frame() -> NextFrame {
+    NextFrame { frame: None }
+}
+
+/// Future returned by [`next_frame`](fn.next_frame.html).
+#[must_use = "futures do nothing unless awaited"]
+pub struct NextFrame {
+    frame: Option<u64>,
+}
+
+impl Future for NextFrame {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+        EXECUTOR.with(|executor| {
+            let mut executor = executor.borrow_mut();
+            let target = *self.frame.get_or_insert(executor.frame + 1);
+            if executor.frame >= target {
+                Poll::Ready(())
+            } else {
+                executor.frame_wakers.push(context.waker().clone());
+                Poll::Pending
+            }
+        })
+    }
+}
+
+/// Future resolving to the arguments of the next emission of a signal. Returned by
+/// [`SignalFutureExt::signal_future`](trait.SignalFutureExt.html#method.signal_future).
+///
+/// The signal is disconnected when the future is dropped. If the object emitting the signal
+/// is freed first, the future never resolves.
+#[must_use = "futures do nothing unless awaited"]
+pub struct SignalFuture {
+    state: Rc<RefCell<SignalState>>,
+    _connection: ClosureConnection,
+}
+
+#[derive(Default)]
+struct SignalState {
+    args: Option<Vec<Variant>>,
+    waker: Option<Waker>,
+}
+
+impl Future for SignalFuture {
+    type Output = Vec<Variant>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Vec<Variant>> {
+        let mut state = self.state.borrow_mut();
+        match state.args.take() {
+            Some(args) => Poll::Ready(args),
+            None => {
+                state.waker = Some(context.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Extension trait to await the signals of any object.
+pub trait SignalFutureExt: GodotObject {
+    /// Returns a future resolving to the arguments of the next emission of `signal`.
+    ///
+    /// The signal is connected immediately, so emissions between this call and the first
+    /// poll of the future are not missed.
+    ///
+    /// # Safety
+    ///
+    /// `self` must be a valid object.
+    unsafe fn signal_future(&self, signal: &str) -> Result<SignalFuture, GodotError> {
+        let state = Rc::new(RefCell::new(SignalState::default()));
+
+        let mut source = Object::from_sys(self.to_sys());
+        let connection = {
+            let state = state.clone();
+            connect_closure_oneshot(&mut source, signal, move |args| {
+                let mut state = state.borrow_mut();
+                state.args = Some(args.to_vec());
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            })?
+        };
+
+        Ok(SignalFuture {
+            state,
+            _connection: connection,
+        })
+    }
+}
+
+impl<T: GodotObject> SignalFutureExt for T {}
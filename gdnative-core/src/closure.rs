@@ -0,0 +1,247 @@
+use crate::init::{ClassBuilder, InitHandle};
+use crate::user_data::LocalCellData;
+use crate::{
+    get_api, sys, GodotError, GodotObject, GodotString, Instance, NativeClass, NativeClassMethods,
+    Object, Reference, ToVariant, Variant, VariantArray,
+};
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Name of the method of `SignalClosure` that signals are connected to.
+const CALL_METHOD: &str = "_call_closure";
+
+/// Whether `SignalClosure` is registered. Closures can't be connected otherwise.
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+
+type Callback = Box<dyn FnMut(&[Variant])>;
+
+/// Internal class that holds a closure connected to a signal.
+///
+/// Every connection gets its own instance. The instance is kept alive by the handle, and by
+/// the connection itself, where it's bound as the last argument of the calls.
+struct SignalClosure {
+    callback: Option<Callback>,
+}
+
+impl NativeClass for SignalClosure {
+    type Base = Reference;
+    type UserData = LocalCellData<SignalClosure>;
+
+    fn class_name() -> &'static str {
+        "GDNativeSignalClosure"
+    }
+
+    fn init(_owner: Reference) -> Self {
+        SignalClosure { callback: None }
+    }
+}
+
+impl NativeClassMethods for SignalClosure {
+    fn register(builder: &ClassBuilder<Self>) {
+        builder.add_method(CALL_METHOD, call_closure);
+    }
+}
+
+unsafe extern "C" fn call_closure(
+    this: *mut sys::godot_object,
+    _method_data: *mut libc::c_void,
+    user_data: *mut libc::c_void,
+    num_args: libc::c_int,
+    args: *mut *mut sys::godot_variant,
+) -> sys::godot_variant {
+    let instance: Instance<SignalClosure> = Instance::from_raw(this, user_data);
+
+    // The last argument is the bound instance itself.
+    let num_args = (num_args as usize).saturating_sub(1);
+    let args = (0..num_args)
+        .map(|n| (*(*args.add(n) as *const Variant)).clone())
+        .collect::<Vec<_>>();
+
+    // The callback is taken out of the instance during the call, so that it can emit the
+    // signal it's connected to, or drop its own connection.
+    let callback = instance.map_mut(|closure, _| closure.callback.take());
+    let mut callback = match callback {
+        Ok(Some(callback)) => callback,
+        Ok(None) => return Variant::new().forget(),
+        Err(err) => {
+            godot_error!(
+                "gdnative-core: signal closure call failed with error: {:?}",
+                err
+            );
+            return Variant::new().forget();
+        }
+    };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| callback(&args)));
+    if result.is_ok() {
+        let _ = instance.map_mut(|closure, _| {
+            closure.callback.get_or_insert(callback);
+        });
+    } else {
+        // The closure may be in an inconsistent state, so it's dropped instead of put back.
+        godot_error!("gdnative-core: signal closure panicked, and will not be called again");
+    }
+
+    Variant::new().forget()
+}
+
+pub(crate) fn register(handle: InitHandle) {
+    handle.add_class::<SignalClosure>();
+    REGISTERED.store(true, Ordering::Release);
+}
+
+pub(crate) fn cleanup() {
+    REGISTERED.store(false, Ordering::Release);
+}
+
+/// Handle of a signal connected to a closure with
+/// [`connect_closure`](fn.connect_closure.html) or
+/// [`connect_closure_oneshot`](fn.connect_closure_oneshot.html).
+///
+/// The signal is disconnected when the handle is dropped, unless `forget` is called.
+#[must_use = "the closure is disconnected when the handle is dropped"]
+pub struct ClosureConnection {
+    source: Object,
+    signal: GodotString,
+    closure: Reference,
+    disconnect_on_drop: bool,
+}
+
+impl ClosureConnection {
+    /// Returns `true` if the closure is still connected.
+    ///
+    /// This is `false` after the signal was disconnected, a one-shot connection was called,
+    /// or the source object was freed.
+    pub fn is_connected(&self) -> bool {
+        unsafe {
+            (get_api().godot_is_instance_valid)(self.source.to_sys())
+                && self.source.is_connected(
+                    self.signal.new_ref(),
+                    Some(self.closure.to_object()),
+                    CALL_METHOD.into(),
+                )
+        }
+    }
+
+    /// Disconnects the closure now, if it's still connected.
+    pub fn disconnect(self) {
+        drop(self);
+    }
+
+    /// Drops the handle without disconnecting the closure. It stays connected until the
+    /// source object is freed or, for one-shot connections, until it's called.
+    pub fn forget(mut self) {
+        self.disconnect_on_drop = false;
+    }
+}
+
+impl Drop for ClosureConnection {
+    fn drop(&mut self) {
+        if self.disconnect_on_drop && self.is_connected() {
+            unsafe {
+                self.source.disconnect(
+                    self.signal.new_ref(),
+                    Some(self.closure.to_object()),
+                    CALL_METHOD.into(),
+                );
+            }
+        }
+    }
+}
+
+/// Connects a signal of `owner` to a closure, which is called with the arguments of the
+/// signal.
+///
+/// The closure is disconnected when the returned handle is dropped. If it panics, the panic
+/// is caught and logged, and the closure is dropped: the signal stays connected until the
+/// handle is dropped, but emitting it does nothing.
+///
+/// # Errors
+///
+/// Returns `GodotError::Unconfigured` if the class that holds the closures wasn't registered
+/// with [`register_internal_classes`](fn.register_internal_classes.html), and the error of
+/// `Object::connect` if the connection failed.
+///
+/// # Safety
+///
+/// `owner` must be a valid object. The closure is called on the thread that connected it;
+/// emissions from other threads are reported as errors.
+pub unsafe fn connect_closure<O, F>(
+    owner: &mut O,
+    signal: &str,
+    callback: F,
+) -> Result<ClosureConnection, GodotError>
+where
+    O: GodotObject,
+    F: FnMut(&[Variant]) + 'static,
+{
+    connect(owner, signal, Box::new(callback), 0)
+}
+
+/// Connects a signal of `owner` to a closure that is only called once, the next time the
+/// signal is emitted.
+///
+/// The closure is disconnected when the returned handle is dropped, if it wasn't called yet.
+///
+/// # Safety
+///
+/// See [`connect_closure`](fn.connect_closure.html).
+pub unsafe fn connect_closure_oneshot<O, F>(
+    owner: &mut O,
+    signal: &str,
+    callback: F,
+) -> Result<ClosureConnection, GodotError>
+where
+    O: GodotObject,
+    F: FnOnce(&[Variant]) + 'static,
+{
+    let mut callback = Some(callback);
+    let callback = move |args: &[Variant]| {
+        if let Some(callback) = callback.take() {
+            callback(args);
+        }
+    };
+    connect(owner, signal, Box::new(callback), Object::CONNECT_ONESHOT)
+}
+
+unsafe fn connect<O: GodotObject>(
+    owner: &mut O,
+    signal: &str,
+    callback: Callback,
+    flags: i64,
+) -> Result<ClosureConnection, GodotError> {
+    if !REGISTERED.load(Ordering::Acquire) {
+        godot_error!(
+            "gdnative-core: closures can't be connected because the internal classes aren't \
+             registered. Call `register_internal_classes` in the NativeScript init function."
+        );
+        return Err(GodotError::Unconfigured);
+    }
+
+    let closure = Instance::<SignalClosure>::new();
+    closure
+        .map_mut(|closure, _| closure.callback = Some(callback))
+        .expect("a new closure instance should not be borrowed");
+    let closure = closure.into_base();
+
+    let mut binds = VariantArray::new();
+    binds.push(&closure.to_variant());
+
+    let mut source = Object::from_sys(owner.to_sys());
+    let signal = GodotString::from_str(signal);
+    source.connect(
+        signal.new_ref(),
+        Some(closure.to_object()),
+        CALL_METHOD.into(),
+        binds,
+        flags,
+    )?;
+
+    Ok(ClosureConnection {
+        source,
+        signal,
+        closure,
+        disconnect_on_drop: true,
+    })
+}
@@ -1,10 +1,12 @@
-use crate::get_api;
 use crate::sys;
 use std::mem::transmute;
 
 /// RGBA color with 32 bits floating point components.
+///
+/// The methods are ported from the engine's `Color` and don't call into it.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -13,24 +15,274 @@ pub struct Color {
 }
 
 impl Color {
-    pub fn rgba(r: f32, g: f32, b: f32, a: f32) -> Color {
+    pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Color {
         Color { r, g, b, a }
     }
 
-    pub fn rgb(r: f32, g: f32, b: f32) -> Color {
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Color {
         Color { r, g, b, a: 1.0 }
     }
 
+    /// Creates an opaque color from its hue, saturation and value, in the range 0.0 - 1.0.
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Color {
+        Self::from_hsva(h, s, v, 1.0)
+    }
+
+    /// Creates a color from its hue, saturation, value and alpha, in the range 0.0 - 1.0.
+    ///
+    /// Hues outside of the range wrap around, so that `-0.25` is the same hue as `0.75`.
+    pub fn from_hsva(h: f32, s: f32, v: f32, a: f32) -> Color {
+        if s == 0.0 {
+            return Color::rgba(v, v, v, a);
+        }
+
+        let mut h = (h * 6.0) % 6.0;
+        if h < 0.0 {
+            h += 6.0;
+        }
+        let i = h.floor();
+        let f = h - i;
+        let p = v * (1.0 - s);
+        let q = v * (1.0 - s * f);
+        let t = v * (1.0 - s * (1.0 - f));
+
+        match i as i32 {
+            0 => Color::rgba(v, t, p, a),
+            1 => Color::rgba(q, v, p, a),
+            2 => Color::rgba(p, v, t, a),
+            3 => Color::rgba(p, q, v, a),
+            4 => Color::rgba(t, p, v, a),
+            _ => Color::rgba(v, p, q, a),
+        }
+    }
+
+    /// Parses a color from an HTML hexadecimal color string, like `#ff8000` or `ff8000`.
+    ///
+    /// The alpha component comes first if present, like in the engine: `#80ff8000` is
+    /// half-transparent orange. Shorthands with 3 or 4 digits, like `#f80` or `#8f80`, are
+    /// also accepted. Returns `None` if `html` is not a valid color string.
+    pub fn from_html(html: &str) -> Option<Color> {
+        let html = html.strip_prefix('#').unwrap_or(html);
+
+        let digits = html
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<Vec<u8>>>()?;
+        let digits = match digits.len() {
+            3 | 4 => digits.iter().flat_map(|&d| vec![d, d]).collect(),
+            6 | 8 => digits,
+            _ => return None,
+        };
+
+        let mut channels = digits
+            .chunks(2)
+            .map(|pair| component_from_u8(pair[0] << 4 | pair[1]));
+        let a = if digits.len() == 8 {
+            channels.next()?
+        } else {
+            1.0
+        };
+        let r = channels.next()?;
+        let g = channels.next()?;
+        let b = channels.next()?;
+
+        Some(Color::rgba(r, g, b, a))
+    }
+
+    /// Returns the color as an HTML hexadecimal color string without the leading `#`, like
+    /// `ff8000`. If `with_alpha` is `true`, the alpha component is prepended, like `80ff8000`.
+    pub fn to_html(&self, with_alpha: bool) -> String {
+        let mut html = String::with_capacity(8);
+        if with_alpha {
+            html.push_str(&format!("{:02x}", component_to_u8(self.a)));
+        }
+        for &component in &[self.r, self.g, self.b] {
+            html.push_str(&format!("{:02x}", component_to_u8(component)));
+        }
+        html
+    }
+
+    /// Creates a color from a 32-bit integer in RGBA format, with the red component in the
+    /// most significant byte.
+    pub fn from_rgba32(rgba: u32) -> Color {
+        let [r, g, b, a] = rgba.to_be_bytes();
+        Self::from_u8s(r, g, b, a)
+    }
+
+    /// Creates a color from a 32-bit integer in ARGB format, with the alpha component in the
+    /// most significant byte.
+    pub fn from_argb32(argb: u32) -> Color {
+        let [a, r, g, b] = argb.to_be_bytes();
+        Self::from_u8s(r, g, b, a)
+    }
+
+    /// Creates a color from a 32-bit integer in ABGR format, with the alpha component in the
+    /// most significant byte.
+    pub fn from_abgr32(abgr: u32) -> Color {
+        let [a, b, g, r] = abgr.to_be_bytes();
+        Self::from_u8s(r, g, b, a)
+    }
+
+    /// Returns the color as a 32-bit integer in RGBA format, with the red component in the
+    /// most significant byte.
+    pub fn to_rgba32(&self) -> u32 {
+        let [r, g, b, a] = self.to_u8s();
+        u32::from_be_bytes([r, g, b, a])
+    }
+
+    /// Returns the color as a 32-bit integer in ARGB format, with the alpha component in the
+    /// most significant byte.
+    pub fn to_argb32(&self) -> u32 {
+        let [r, g, b, a] = self.to_u8s();
+        u32::from_be_bytes([a, r, g, b])
+    }
+
+    /// Returns the color as a 32-bit integer in ABGR format, with the alpha component in the
+    /// most significant byte.
+    pub fn to_abgr32(&self) -> u32 {
+        let [r, g, b, a] = self.to_u8s();
+        u32::from_be_bytes([a, b, g, r])
+    }
+
+    /// Returns the named color with the given name, from the same table as the engine's
+    /// `ColorN`. Names are case-insensitive, and spaces, dashes, underscores, apostrophes
+    /// and dots are ignored, so `"Alice Blue"` is the same as `"aliceblue"`.
+    pub fn named(name: &str) -> Option<Color> {
+        let name = name
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_' | '\'' | '.'))
+            .collect::<String>()
+            .to_lowercase();
+
+        NAMED_COLORS
+            .binary_search_by(|(named, _)| (*named).cmp(name.as_str()))
+            .ok()
+            .map(|idx| NAMED_COLORS[idx].1)
+    }
+
+    /// Returns the hue of the color, in the range 0.0 - 1.0.
     pub fn h(&self) -> f32 {
-        unsafe { (get_api().godot_color_get_h)(self.sys()) }
+        let min = self.r.min(self.g).min(self.b);
+        let max = self.r.max(self.g).max(self.b);
+        let delta = max - min;
+        if delta == 0.0 {
+            return 0.0;
+        }
+
+        let h = if self.r == max {
+            (self.g - self.b) / delta
+        } else if self.g == max {
+            2.0 + (self.b - self.r) / delta
+        } else {
+            4.0 + (self.r - self.g) / delta
+        };
+
+        let h = h / 6.0;
+        if h < 0.0 {
+            h + 1.0
+        } else {
+            h
+        }
     }
 
+    /// Returns the saturation of the color, in the range 0.0 - 1.0.
     pub fn s(&self) -> f32 {
-        unsafe { (get_api().godot_color_get_s)(self.sys()) }
+        let min = self.r.min(self.g).min(self.b);
+        let max = self.r.max(self.g).max(self.b);
+        if max != 0.0 {
+            (max - min) / max
+        } else {
+            0.0
+        }
     }
 
+    /// Returns the value of the color, in the range 0.0 - 1.0.
     pub fn v(&self) -> f32 {
-        unsafe { (get_api().godot_color_get_v)(self.sys()) }
+        self.r.max(self.g).max(self.b)
+    }
+
+    /// Returns the result of drawing `over` on top of this color, using alpha blending.
+    pub fn blend(&self, over: &Color) -> Color {
+        let sa = 1.0 - over.a;
+        let a = self.a * sa + over.a;
+        if a == 0.0 {
+            return Color::rgba(0.0, 0.0, 0.0, 0.0);
+        }
+
+        let blend = |this: f32, over_c: f32| (this * self.a * sa + over_c * over.a) / a;
+        Color::rgba(
+            blend(self.r, over.r),
+            blend(self.g, over.g),
+            blend(self.b, over.b),
+            a,
+        )
+    }
+
+    /// Linearly interpolates between this color and `other` by `weight`, in the range
+    /// 0.0 - 1.0. All components, including alpha, are interpolated.
+    pub fn lerp(&self, other: &Color, weight: f32) -> Color {
+        Color::rgba(
+            self.r + weight * (other.r - self.r),
+            self.g + weight * (other.g - self.g),
+            self.b + weight * (other.b - self.b),
+            self.a + weight * (other.a - self.a),
+        )
+    }
+
+    /// Returns the most contrasting color, which has each component shifted by 0.5 and
+    /// wrapped around, so that 0.5 becomes 1.0. The alpha component is kept.
+    pub fn contrasted(&self) -> Color {
+        let contrast = |c: f32| {
+            let c = c + 0.5;
+            if c > 1.0 {
+                c - 1.0
+            } else {
+                c
+            }
+        };
+        Color::rgba(contrast(self.r), contrast(self.g), contrast(self.b), self.a)
+    }
+
+    /// Returns the inverted color, `1.0 - c` for each component. The alpha component is kept.
+    pub fn inverted(&self) -> Color {
+        Color::rgba(1.0 - self.r, 1.0 - self.g, 1.0 - self.b, self.a)
+    }
+
+    /// Returns the color made lighter by `amount`, in the range 0.0 - 1.0, which moves it
+    /// towards white. The alpha component is kept.
+    pub fn lightened(&self, amount: f32) -> Color {
+        let lighten = |c: f32| c + (1.0 - c) * amount;
+        Color::rgba(lighten(self.r), lighten(self.g), lighten(self.b), self.a)
+    }
+
+    /// Returns the color made darker by `amount`, in the range 0.0 - 1.0, which moves it
+    /// towards black. The alpha component is kept.
+    pub fn darkened(&self, amount: f32) -> Color {
+        let darken = |c: f32| c * (1.0 - amount);
+        Color::rgba(darken(self.r), darken(self.g), darken(self.b), self.a)
+    }
+
+    /// Returns the average of the red, green and blue components.
+    pub fn gray(&self) -> f32 {
+        ((self.r + self.g + self.b) as f64 / 3.0) as f32
+    }
+
+    fn from_u8s(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color::rgba(
+            component_from_u8(r),
+            component_from_u8(g),
+            component_from_u8(b),
+            component_from_u8(a),
+        )
+    }
+
+    fn to_u8s(self) -> [u8; 4] {
+        [
+            component_to_u8(self.r),
+            component_to_u8(self.g),
+            component_to_u8(self.b),
+            component_to_u8(self.a),
+        ]
     }
 
     #[doc(hidden)]
@@ -44,8 +296,316 @@ impl Color {
     }
 }
 
+fn component_from_u8(c: u8) -> f32 {
+    (c as f64 / 255.0) as f32
+}
+
+/// Converts a component to a byte, clamping it to the range 0.0 - 1.0.
+fn component_to_u8(c: f32) -> u8 {
+    (c * 255.0).round() as u8
+}
+
+/// The engine's named colors, sorted by name.
+const NAMED_COLORS: &[(&str, Color)] = &[
+    ("aliceblue", Color::rgb(0.94, 0.97, 1.00)),
+    ("antiquewhite", Color::rgb(0.98, 0.92, 0.84)),
+    ("aqua", Color::rgb(0.00, 1.00, 1.00)),
+    ("aquamarine", Color::rgb(0.50, 1.00, 0.83)),
+    ("azure", Color::rgb(0.94, 1.00, 1.00)),
+    ("beige", Color::rgb(0.96, 0.96, 0.86)),
+    ("bisque", Color::rgb(1.00, 0.89, 0.77)),
+    ("black", Color::rgb(0.00, 0.00, 0.00)),
+    ("blanchedalmond", Color::rgb(1.00, 0.92, 0.80)),
+    ("blue", Color::rgb(0.00, 0.00, 1.00)),
+    ("blueviolet", Color::rgb(0.54, 0.17, 0.89)),
+    ("brown", Color::rgb(0.65, 0.16, 0.16)),
+    ("burlywood", Color::rgb(0.87, 0.72, 0.53)),
+    ("cadetblue", Color::rgb(0.37, 0.62, 0.63)),
+    ("chartreuse", Color::rgb(0.50, 1.00, 0.00)),
+    ("chocolate", Color::rgb(0.82, 0.41, 0.12)),
+    ("coral", Color::rgb(1.00, 0.50, 0.31)),
+    ("cornflower", Color::rgb(0.39, 0.58, 0.93)),
+    ("cornsilk", Color::rgb(1.00, 0.97, 0.86)),
+    ("crimson", Color::rgb(0.86, 0.08, 0.24)),
+    ("cyan", Color::rgb(0.00, 1.00, 1.00)),
+    ("darkblue", Color::rgb(0.00, 0.00, 0.55)),
+    ("darkcyan", Color::rgb(0.00, 0.55, 0.55)),
+    ("darkgoldenrod", Color::rgb(0.72, 0.53, 0.04)),
+    ("darkgray", Color::rgb(0.66, 0.66, 0.66)),
+    ("darkgreen", Color::rgb(0.00, 0.39, 0.00)),
+    ("darkkhaki", Color::rgb(0.74, 0.72, 0.42)),
+    ("darkmagenta", Color::rgb(0.55, 0.00, 0.55)),
+    ("darkolivegreen", Color::rgb(0.33, 0.42, 0.18)),
+    ("darkorange", Color::rgb(1.00, 0.55, 0.00)),
+    ("darkorchid", Color::rgb(0.60, 0.20, 0.80)),
+    ("darkred", Color::rgb(0.55, 0.00, 0.00)),
+    ("darksalmon", Color::rgb(0.91, 0.59, 0.48)),
+    ("darkseagreen", Color::rgb(0.56, 0.74, 0.56)),
+    ("darkslateblue", Color::rgb(0.28, 0.24, 0.55)),
+    ("darkslategray", Color::rgb(0.18, 0.31, 0.31)),
+    ("darkturquoise", Color::rgb(0.00, 0.81, 0.82)),
+    ("darkviolet", Color::rgb(0.58, 0.00, 0.83)),
+    ("deeppink", Color::rgb(1.00, 0.08, 0.58)),
+    ("deepskyblue", Color::rgb(0.00, 0.75, 1.00)),
+    ("dimgray", Color::rgb(0.41, 0.41, 0.41)),
+    ("dodgerblue", Color::rgb(0.12, 0.56, 1.00)),
+    ("firebrick", Color::rgb(0.70, 0.13, 0.13)),
+    ("floralwhite", Color::rgb(1.00, 0.98, 0.94)),
+    ("forestgreen", Color::rgb(0.13, 0.55, 0.13)),
+    ("fuchsia", Color::rgb(1.00, 0.00, 1.00)),
+    ("gainsboro", Color::rgb(0.86, 0.86, 0.86)),
+    ("ghostwhite", Color::rgb(0.97, 0.97, 1.00)),
+    ("gold", Color::rgb(1.00, 0.84, 0.00)),
+    ("goldenrod", Color::rgb(0.85, 0.65, 0.13)),
+    ("gray", Color::rgb(0.75, 0.75, 0.75)),
+    ("green", Color::rgb(0.00, 1.00, 0.00)),
+    ("greenyellow", Color::rgb(0.68, 1.00, 0.18)),
+    ("honeydew", Color::rgb(0.94, 1.00, 0.94)),
+    ("hotpink", Color::rgb(1.00, 0.41, 0.71)),
+    ("indianred", Color::rgb(0.80, 0.36, 0.36)),
+    ("indigo", Color::rgb(0.29, 0.00, 0.51)),
+    ("ivory", Color::rgb(1.00, 1.00, 0.94)),
+    ("khaki", Color::rgb(0.94, 0.90, 0.55)),
+    ("lavender", Color::rgb(0.90, 0.90, 0.98)),
+    ("lavenderblush", Color::rgb(1.00, 0.94, 0.96)),
+    ("lawngreen", Color::rgb(0.49, 0.99, 0.00)),
+    ("lemonchiffon", Color::rgb(1.00, 0.98, 0.80)),
+    ("lightblue", Color::rgb(0.68, 0.85, 0.90)),
+    ("lightcoral", Color::rgb(0.94, 0.50, 0.50)),
+    ("lightcyan", Color::rgb(0.88, 1.00, 1.00)),
+    ("lightgoldenrod", Color::rgb(0.98, 0.98, 0.82)),
+    ("lightgray", Color::rgb(0.83, 0.83, 0.83)),
+    ("lightgreen", Color::rgb(0.56, 0.93, 0.56)),
+    ("lightpink", Color::rgb(1.00, 0.71, 0.76)),
+    ("lightsalmon", Color::rgb(1.00, 0.63, 0.48)),
+    ("lightseagreen", Color::rgb(0.13, 0.70, 0.67)),
+    ("lightskyblue", Color::rgb(0.53, 0.81, 0.98)),
+    ("lightslategray", Color::rgb(0.47, 0.53, 0.60)),
+    ("lightsteelblue", Color::rgb(0.69, 0.77, 0.87)),
+    ("lightyellow", Color::rgb(1.00, 1.00, 0.88)),
+    ("lime", Color::rgb(0.00, 1.00, 0.00)),
+    ("limegreen", Color::rgb(0.20, 0.80, 0.20)),
+    ("linen", Color::rgb(0.98, 0.94, 0.90)),
+    ("magenta", Color::rgb(1.00, 0.00, 1.00)),
+    ("maroon", Color::rgb(0.69, 0.19, 0.38)),
+    ("mediumaquamarine", Color::rgb(0.40, 0.80, 0.67)),
+    ("mediumblue", Color::rgb(0.00, 0.00, 0.80)),
+    ("mediumorchid", Color::rgb(0.73, 0.33, 0.83)),
+    ("mediumpurple", Color::rgb(0.58, 0.44, 0.86)),
+    ("mediumseagreen", Color::rgb(0.24, 0.70, 0.44)),
+    ("mediumslateblue", Color::rgb(0.48, 0.41, 0.93)),
+    ("mediumspringgreen", Color::rgb(0.00, 0.98, 0.60)),
+    ("mediumturquoise", Color::rgb(0.28, 0.82, 0.80)),
+    ("mediumvioletred", Color::rgb(0.78, 0.08, 0.52)),
+    ("midnightblue", Color::rgb(0.10, 0.10, 0.44)),
+    ("mintcream", Color::rgb(0.96, 1.00, 0.98)),
+    ("mistyrose", Color::rgb(1.00, 0.89, 0.88)),
+    ("moccasin", Color::rgb(1.00, 0.89, 0.71)),
+    ("navajowhite", Color::rgb(1.00, 0.87, 0.68)),
+    ("navyblue", Color::rgb(0.00, 0.00, 0.50)),
+    ("oldlace", Color::rgb(0.99, 0.96, 0.90)),
+    ("olive", Color::rgb(0.50, 0.50, 0.00)),
+    ("olivedrab", Color::rgb(0.42, 0.56, 0.14)),
+    ("orange", Color::rgb(1.00, 0.65, 0.00)),
+    ("orangered", Color::rgb(1.00, 0.27, 0.00)),
+    ("orchid", Color::rgb(0.85, 0.44, 0.84)),
+    ("palegoldenrod", Color::rgb(0.93, 0.91, 0.67)),
+    ("palegreen", Color::rgb(0.60, 0.98, 0.60)),
+    ("paleturquoise", Color::rgb(0.69, 0.93, 0.93)),
+    ("palevioletred", Color::rgb(0.86, 0.44, 0.58)),
+    ("papayawhip", Color::rgb(1.00, 0.94, 0.84)),
+    ("peachpuff", Color::rgb(1.00, 0.85, 0.73)),
+    ("peru", Color::rgb(0.80, 0.52, 0.25)),
+    ("pink", Color::rgb(1.00, 0.75, 0.80)),
+    ("plum", Color::rgb(0.87, 0.63, 0.87)),
+    ("powderblue", Color::rgb(0.69, 0.88, 0.90)),
+    ("purple", Color::rgb(0.63, 0.13, 0.94)),
+    ("rebeccapurple", Color::rgb(0.40, 0.20, 0.60)),
+    ("red", Color::rgb(1.00, 0.00, 0.00)),
+    ("rosybrown", Color::rgb(0.74, 0.56, 0.56)),
+    ("royalblue", Color::rgb(0.25, 0.41, 0.88)),
+    ("saddlebrown", Color::rgb(0.55, 0.27, 0.07)),
+    ("salmon", Color::rgb(0.98, 0.50, 0.45)),
+    ("sandybrown", Color::rgb(0.96, 0.64, 0.38)),
+    ("seagreen", Color::rgb(0.18, 0.55, 0.34)),
+    ("seashell", Color::rgb(1.00, 0.96, 0.93)),
+    ("sienna", Color::rgb(0.63, 0.32, 0.18)),
+    ("silver", Color::rgb(0.75, 0.75, 0.75)),
+    ("skyblue", Color::rgb(0.53, 0.81, 0.92)),
+    ("slateblue", Color::rgb(0.42, 0.35, 0.80)),
+    ("slategray", Color::rgb(0.44, 0.50, 0.56)),
+    ("snow", Color::rgb(1.00, 0.98, 0.98)),
+    ("springgreen", Color::rgb(0.00, 1.00, 0.50)),
+    ("steelblue", Color::rgb(0.27, 0.51, 0.71)),
+    ("tan", Color::rgb(0.82, 0.71, 0.55)),
+    ("teal", Color::rgb(0.00, 0.50, 0.50)),
+    ("thistle", Color::rgb(0.85, 0.75, 0.85)),
+    ("tomato", Color::rgb(1.00, 0.39, 0.28)),
+    ("transparent", Color::rgba(1.00, 1.00, 1.00, 0.00)),
+    ("turquoise", Color::rgb(0.25, 0.88, 0.82)),
+    ("violet", Color::rgb(0.93, 0.51, 0.93)),
+    ("webgray", Color::rgb(0.50, 0.50, 0.50)),
+    ("webgreen", Color::rgb(0.00, 0.50, 0.00)),
+    ("webmaroon", Color::rgb(0.50, 0.00, 0.00)),
+    ("webpurple", Color::rgb(0.50, 0.00, 0.50)),
+    ("wheat", Color::rgb(0.96, 0.87, 0.70)),
+    ("white", Color::rgb(1.00, 1.00, 1.00)),
+    ("whitesmoke", Color::rgb(0.96, 0.96, 0.96)),
+    ("yellow", Color::rgb(1.00, 1.00, 0.00)),
+    ("yellowgreen", Color::rgb(0.60, 0.80, 0.20)),
+];
+
 #[test]
 fn color_repr() {
     use std::mem::size_of;
     assert_eq!(size_of::<Color>(), size_of::<sys::godot_color>());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_color_approx(expected: Color, actual: Color) {
+        let close = |a: f32, b: f32| (a - b).abs() < 0.0001;
+        assert!(
+            close(expected.r, actual.r)
+                && close(expected.g, actual.g)
+                && close(expected.b, actual.b)
+                && close(expected.a, actual.a),
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn hsv_round_trips() {
+        let orange = Color::rgb(1.0, 0.5, 0.0);
+        assert!((orange.h() - 30.0 / 360.0).abs() < 0.0001);
+        assert_eq!(1.0, orange.s());
+        assert_eq!(1.0, orange.v());
+        assert_color_approx(orange, Color::from_hsv(orange.h(), orange.s(), orange.v()));
+
+        for &color in &[
+            Color::rgba(0.2, 0.4, 0.6, 0.5),
+            Color::rgb(0.9, 0.1, 0.3),
+            Color::rgb(0.3, 0.8, 0.1),
+            Color::rgb(0.5, 0.5, 0.5),
+        ] {
+            let hsv = Color::from_hsva(color.h(), color.s(), color.v(), color.a);
+            assert_color_approx(color, hsv);
+        }
+
+        assert_eq!(Color::rgb(0.5, 0.5, 0.5), Color::from_hsv(0.7, 0.0, 0.5));
+        assert_color_approx(
+            Color::rgb(1.0, 0.0, 1.0),
+            Color::from_hsv(-1.0 / 6.0, 1.0, 1.0),
+        );
+        assert_color_approx(
+            Color::from_hsv(0.75, 0.5, 0.8),
+            Color::from_hsv(-0.25, 0.5, 0.8),
+        );
+        assert_color_approx(
+            Color::from_hsv(0.25, 0.5, 0.8),
+            Color::from_hsv(-1.75, 0.5, 0.8),
+        );
+        assert_eq!(0.0, Color::rgb(0.0, 0.0, 0.0).s());
+    }
+
+    #[test]
+    fn html_round_trips() {
+        let color = Color::from_html("#ff8000").unwrap();
+        assert_color_approx(Color::rgb(1.0, 128.0 / 255.0, 0.0), color);
+        assert_eq!("ff8000", color.to_html(false));
+        assert_eq!("ffff8000", color.to_html(true));
+
+        let color = Color::from_html("80ff8000").unwrap();
+        assert_color_approx(Color::rgba(1.0, 128.0 / 255.0, 0.0, 128.0 / 255.0), color);
+        assert_eq!("80ff8000", color.to_html(true));
+
+        assert_eq!(Color::from_html("#ff8800"), Color::from_html("#f80"));
+        assert_eq!(Color::from_html("#88ff8800"), Color::from_html("8F80"));
+    }
+
+    #[test]
+    fn invalid_html_is_rejected() {
+        for &html in &[
+            "",
+            "#",
+            "ff",
+            "#ff800",
+            "#ff80000",
+            "#gg8000",
+            "#ff8000ff00",
+            "äää",
+        ] {
+            assert_eq!(None, Color::from_html(html), "{:?}", html);
+        }
+    }
+
+    #[test]
+    fn packed_integers_round_trip() {
+        let color = Color::rgba(1.0, 0.0, 128.0 / 255.0, 64.0 / 255.0);
+        assert_eq!(0xff00_8040, color.to_rgba32());
+        assert_eq!(0x40ff_0080, color.to_argb32());
+        assert_eq!(0x4080_00ff, color.to_abgr32());
+
+        assert_eq!(color, Color::from_rgba32(0xff00_8040));
+        assert_eq!(color, Color::from_argb32(0x40ff_0080));
+        assert_eq!(color, Color::from_abgr32(0x4080_00ff));
+
+        assert_eq!(0xffff_ffff, Color::rgba(2.0, 1.5, 1.0, 1.0).to_rgba32());
+        assert_eq!(0x0000_00ff, Color::rgba(-1.0, 0.0, 0.0, 1.0).to_rgba32());
+    }
+
+    #[test]
+    fn blend_is_sane() {
+        let red = Color::rgb(1.0, 0.0, 0.0);
+        let half_blue = Color::rgba(0.0, 0.0, 1.0, 0.5);
+        assert_eq!(Color::rgb(0.5, 0.0, 0.5), red.blend(&half_blue));
+        assert_eq!(
+            Color::rgba(0.0, 0.0, 0.0, 0.0),
+            Color::rgba(1.0, 1.0, 1.0, 0.0).blend(&Color::rgba(1.0, 1.0, 1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn adjustments_are_sane() {
+        let color = Color::rgba(0.2, 0.4, 0.8, 0.5);
+
+        assert_color_approx(
+            Color::rgba(0.6, 0.7, 0.9, 0.5),
+            color.lerp(&Color::rgba(1.0, 1.0, 1.0, 0.5), 0.5),
+        );
+        assert_color_approx(Color::rgba(0.7, 0.9, 0.3, 0.5), color.contrasted());
+        assert_color_approx(
+            Color::rgba(1.0, 0.5, 0.6, 1.0),
+            Color::rgb(0.5, 0.0, 0.1).contrasted(),
+        );
+        assert_color_approx(Color::rgba(0.8, 0.6, 0.2, 0.5), color.inverted());
+        assert_color_approx(Color::rgba(0.6, 0.7, 0.9, 0.5), color.lightened(0.5));
+        assert_color_approx(Color::rgba(0.1, 0.2, 0.4, 0.5), color.darkened(0.5));
+        assert!((color.gray() - 1.4 / 3.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn named_colors_are_sorted() {
+        assert!(NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn named_colors_are_found() {
+        assert_eq!(
+            Some(Color::rgb(0.94, 0.97, 1.00)),
+            Color::named("aliceblue")
+        );
+        assert_eq!(Color::named("aliceblue"), Color::named("Alice Blue"));
+        assert_eq!(Color::named("navyblue"), Color::named("NAVY_BLUE"));
+        assert_eq!(Color::named("webgray"), Color::named("web-gray"));
+        assert_eq!(
+            Some(Color::rgba(1.0, 1.0, 1.0, 0.0)),
+            Color::named("transparent")
+        );
+        assert_eq!(None, Color::named("notacolor"));
+    }
+}
@@ -5,6 +5,8 @@ use crate::GodotString;
 use crate::Variant;
 use crate::VariantArray;
 use std::fmt;
+use std::iter::{Extend, FromIterator};
+use std::ptr;
 
 /// A reference-counted `Dictionary` of `Variant` key-value pairs.
 pub struct Dictionary(pub(crate) sys::godot_dictionary);
@@ -93,6 +95,25 @@ impl Dictionary {
         unsafe { Variant::cast_ref((get_api().godot_dictionary_next)(&self.0, &key.0)) }
     }
 
+    /// Returns an iterator over copies of the key-value pairs, in insertion order.
+    pub fn iter(&self) -> DictionaryIter<'_> {
+        DictionaryIter {
+            dict: self,
+            last_key: None,
+            finished: false,
+        }
+    }
+
+    /// Gets the entry for the given key for in-place manipulation.
+    pub fn entry(&mut self, key: &Variant) -> Entry<'_> {
+        let key = key.clone();
+        if self.contains(&key) {
+            Entry::Occupied(OccupiedEntry { dict: self, key })
+        } else {
+            Entry::Vacant(VacantEntry { dict: self, key })
+        }
+    }
+
     /// Return a hashed i32 value representing the dictionary's contents.
     pub fn hash(&self) -> i32 {
         unsafe { (get_api().godot_dictionary_hash)(&self.0) }
@@ -127,6 +148,168 @@ impl fmt::Debug for Dictionary {
     }
 }
 
+impl FromIterator<(Variant, Variant)> for Dictionary {
+    fn from_iter<I: IntoIterator<Item = (Variant, Variant)>>(iter: I) -> Self {
+        let mut dict = Dictionary::new();
+        dict.extend(iter);
+        dict
+    }
+}
+
+impl Extend<(Variant, Variant)> for Dictionary {
+    fn extend<I: IntoIterator<Item = (Variant, Variant)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.set(&key, &value);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Dictionary {
+    type Item = (Variant, Variant);
+    type IntoIter = DictionaryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over copies of the key-value pairs of a `Dictionary`.
+pub struct DictionaryIter<'a> {
+    dict: &'a Dictionary,
+    last_key: Option<Variant>,
+    finished: bool,
+}
+
+impl<'a> Iterator for DictionaryIter<'a> {
+    type Item = (Variant, Variant);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let last_key = match &self.last_key {
+            Some(key) => &key.0 as *const _,
+            None => ptr::null(),
+        };
+
+        let next_key = unsafe { (get_api().godot_dictionary_next)(&self.dict.0, last_key) };
+        if next_key.is_null() {
+            self.finished = true;
+            self.last_key = None;
+            return None;
+        }
+
+        let key = Variant::cast_ref(next_key).clone();
+        let value = self.dict.get(&key);
+        self.last_key = Some(key.clone());
+        Some((key, value))
+    }
+}
+
+/// A view into a single key of a `Dictionary`, which may either be vacant or occupied.
+///
+/// Returned by `Dictionary::entry`.
+pub enum Entry<'a> {
+    Occupied(OccupiedEntry<'a>),
+    Vacant(VacantEntry<'a>),
+}
+
+impl<'a> Entry<'a> {
+    /// Returns the key of this entry.
+    pub fn key(&self) -> &Variant {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant, and returns a mutable reference to the value.
+    pub fn or_insert(self, default: &Variant) -> &'a mut Variant {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Inserts the result of `default` if the entry is vacant, and returns a mutable reference
+    /// to the value.
+    pub fn or_insert_with<F: FnOnce() -> Variant>(self, default: F) -> &'a mut Variant {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(&default()),
+        }
+    }
+
+    /// Calls `f` with the value if the entry is occupied.
+    pub fn and_modify<F: FnOnce(&mut Variant)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+/// An occupied entry of a `Dictionary`.
+pub struct OccupiedEntry<'a> {
+    dict: &'a mut Dictionary,
+    key: Variant,
+}
+
+impl<'a> OccupiedEntry<'a> {
+    /// Returns the key of this entry.
+    pub fn key(&self) -> &Variant {
+        &self.key
+    }
+
+    /// Returns a reference to the value.
+    pub fn get(&self) -> &Variant {
+        self.dict.get_ref(&self.key)
+    }
+
+    /// Returns a mutable reference to the value.
+    pub fn get_mut(&mut self) -> &mut Variant {
+        self.dict.get_mut_ref(&self.key)
+    }
+
+    /// Converts the entry into a mutable reference to the value with the lifetime of the
+    /// `Dictionary`.
+    pub fn into_mut(self) -> &'a mut Variant {
+        self.dict.get_mut_ref(&self.key)
+    }
+
+    /// Replaces the value, returning the old one.
+    pub fn insert(&mut self, val: &Variant) -> Variant {
+        std::mem::replace(self.get_mut(), val.clone())
+    }
+
+    /// Removes the entry from the `Dictionary`, returning the value.
+    pub fn remove(self) -> Variant {
+        let val = self.dict.get(&self.key);
+        self.dict.erase(&self.key);
+        val
+    }
+}
+
+/// A vacant entry of a `Dictionary`.
+pub struct VacantEntry<'a> {
+    dict: &'a mut Dictionary,
+    key: Variant,
+}
+
+impl<'a> VacantEntry<'a> {
+    /// Returns the key of this entry.
+    pub fn key(&self) -> &Variant {
+        &self.key
+    }
+
+    /// Sets the value of the entry, and returns a mutable reference to it.
+    pub fn insert(self, val: &Variant) -> &'a mut Variant {
+        self.dict.set(&self.key, val);
+        self.dict.get_mut_ref(&self.key)
+    }
+}
+
 godot_test!(test_dictionary {
     use crate::VariantType;
     let foo = Variant::from_str("foo");
@@ -171,6 +354,36 @@ godot_test!(test_dictionary {
     }
 });
 
+godot_test!(test_dictionary_iter_entry {
+    let mut dict: Dictionary = (0..3)
+        .map(|i| (Variant::from_i64(i), Variant::from_i64(i * 10)))
+        .collect();
+    dict.extend(vec![(Variant::from_str("foo"), Variant::from_str("bar"))]);
+    assert_eq!(4, dict.len());
+
+    let pairs = dict
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(4, pairs.len());
+    assert!(pairs.contains(&("1".to_string(), "10".to_string())));
+    assert!(pairs.contains(&("foo".to_string(), "bar".to_string())));
+    assert_eq!(0, Dictionary::new().iter().count());
+
+    *dict.entry(&Variant::from_str("count")).or_insert(&Variant::from_i64(0)) =
+        Variant::from_i64(5);
+    dict.entry(&Variant::from_str("count"))
+        .and_modify(|v| *v = Variant::from_i64(v.to_i64() + 1))
+        .or_insert(&Variant::from_i64(0));
+    assert_eq!(6, dict.get(&Variant::from_str("count")).to_i64());
+
+    match dict.entry(&Variant::from_i64(2)) {
+        Entry::Occupied(entry) => assert_eq!(20, entry.remove().to_i64()),
+        Entry::Vacant(_) => panic!("entry should be occupied"),
+    }
+    assert!(!dict.contains(&Variant::from_i64(2)));
+});
+
 // TODO: clear dictionaries without affecting clones
 //godot_test!(test_dictionary_clone_clear {
 //    let foo = Variant::from_str("foo");
@@ -1,8 +1,12 @@
-use crate::Vector3;
+use crate::{real, Plane, Vector3};
 
 /// Axis-aligned bounding box.
+///
+/// The queries are ported from the engine's `AABB` and don't call into it. Like in the engine,
+/// they assume that the size is not negative.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Aabb {
     pub position: Vector3,
     pub size: Vector3,
@@ -18,4 +22,345 @@ impl Aabb {
     pub fn from_sys(c: sys::godot_aabb) -> Self {
         unsafe { std::mem::transmute::<sys::godot_aabb, Self>(c) }
     }
+
+    /// Returns the end of the box, which is `position + size`.
+    #[inline]
+    pub fn end(&self) -> Vector3 {
+        self.position + self.size
+    }
+
+    /// Returns `true` if the box contains `point`. Points on the faces are contained.
+    pub fn has_point(&self, point: Vector3) -> bool {
+        let (begin, end) = (self.position, self.end());
+        point.x >= begin.x
+            && point.y >= begin.y
+            && point.z >= begin.z
+            && point.x <= end.x
+            && point.y <= end.y
+            && point.z <= end.z
+    }
+
+    /// Returns `true` if the box overlaps with `other`. Boxes that only touch don't overlap.
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        let (begin, end) = (self.position, self.end());
+        let (other_begin, other_end) = (other.position, other.end());
+        begin.x < other_end.x
+            && begin.y < other_end.y
+            && begin.z < other_end.z
+            && end.x > other_begin.x
+            && end.y > other_begin.y
+            && end.z > other_begin.z
+    }
+
+    /// Returns the overlapping part of the box and `other`, or `None` if they are disjoint.
+    /// Boxes that only touch intersect in a flat box.
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        let (begin, end) = (self.position, self.end());
+        let (other_begin, other_end) = (other.position, other.end());
+
+        if begin.x > other_end.x
+            || end.x < other_begin.x
+            || begin.y > other_end.y
+            || end.y < other_begin.y
+            || begin.z > other_end.z
+            || end.z < other_begin.z
+        {
+            return None;
+        }
+
+        let min = begin.max(other_begin);
+        let max = end.min(other_end);
+        Some(Aabb {
+            position: min,
+            size: max - min,
+        })
+    }
+
+    /// Returns the smallest box containing both the box and `other`.
+    pub fn merge(&self, other: &Aabb) -> Aabb {
+        let min = self.position.min(other.position);
+        let max = self.end().max(other.end());
+        Aabb {
+            position: min,
+            size: max - min,
+        }
+    }
+
+    /// Returns the box expanded to include `point`.
+    pub fn expand(&self, point: Vector3) -> Aabb {
+        let min = self.position.min(point);
+        let max = self.end().max(point);
+        Aabb {
+            position: min,
+            size: max - min,
+        }
+    }
+
+    /// Returns the box grown by `by` on every side.
+    pub fn grow(&self, by: real) -> Aabb {
+        let by = Vector3::new(by, by, by);
+        Aabb {
+            position: self.position - by,
+            size: self.size + by * 2.0,
+        }
+    }
+
+    /// Returns the position of one of the 8 vertices of the box. Each of the 3 lowest bits of
+    /// `idx` selects the end of the box instead of its position on one axis: bit 2 on the X
+    /// axis, bit 1 on the Y axis, and bit 0 on the Z axis.
+    ///
+    /// # Panics
+    ///
+    /// If `idx` is greater than 7.
+    pub fn get_endpoint(&self, idx: usize) -> Vector3 {
+        assert!(idx < 8, "endpoint index out of bounds: {}", idx);
+
+        let (begin, end) = (self.position, self.end());
+        Vector3::new(
+            if idx & 4 == 0 { begin.x } else { end.x },
+            if idx & 2 == 0 { begin.y } else { end.y },
+            if idx & 1 == 0 { begin.z } else { end.z },
+        )
+    }
+
+    /// Returns the support point of the box for `dir`.
+    ///
+    /// Like in the engine, this is the vertex that is the furthest away in the *opposite*
+    /// direction of `dir`. Negate `dir` to get the vertex furthest along it.
+    pub fn get_support(&self, dir: Vector3) -> Vector3 {
+        let half_extents = self.size * 0.5;
+        let center = self.position + half_extents;
+        let pick = |d: real, half: real| if d > 0.0 { -half } else { half };
+        Vector3::new(
+            pick(dir.x, half_extents.x),
+            pick(dir.y, half_extents.y),
+            pick(dir.z, half_extents.z),
+        ) + center
+    }
+
+    /// Returns `true` if `plane` splits the box, i.e. there are vertices on both sides of it.
+    pub fn intersects_plane(&self, plane: &Plane) -> bool {
+        let mut over = false;
+        let mut under = false;
+        for idx in 0..8 {
+            if plane.distance_to(self.get_endpoint(idx)) > 0.0 {
+                over = true;
+            } else {
+                under = true;
+            }
+        }
+        over && under
+    }
+
+    /// Returns the point where the segment from `from` to `to` enters the box, or `None` if
+    /// it doesn't intersect the box. If `from` is inside the box, `from` is returned.
+    pub fn intersects_segment(&self, from: Vector3, to: Vector3) -> Option<Vector3> {
+        let (seg_from, seg_to) = (from.to_array(), to.to_array());
+        let (box_begin, box_end) = (self.position.to_array(), self.end().to_array());
+
+        let mut min = 0.0;
+        let mut max = 1.0;
+        for axis in 0..3 {
+            let (seg_from, seg_to) = (seg_from[axis], seg_to[axis]);
+            let (box_begin, box_end) = (box_begin[axis], box_end[axis]);
+            let length = seg_to - seg_from;
+
+            let (cmin, cmax) = if seg_from < seg_to {
+                if seg_from > box_end || seg_to < box_begin {
+                    return None;
+                }
+                (
+                    if seg_from < box_begin {
+                        (box_begin - seg_from) / length
+                    } else {
+                        0.0
+                    },
+                    if seg_to > box_end {
+                        (box_end - seg_from) / length
+                    } else {
+                        1.0
+                    },
+                )
+            } else {
+                if seg_to > box_end || seg_from < box_begin {
+                    return None;
+                }
+                (
+                    if seg_from > box_end {
+                        (box_end - seg_from) / length
+                    } else {
+                        0.0
+                    },
+                    if seg_to < box_begin {
+                        (box_begin - seg_from) / length
+                    } else {
+                        1.0
+                    },
+                )
+            };
+
+            if cmin > min {
+                min = cmin;
+            }
+            if cmax < max {
+                max = cmax;
+            }
+            if max < min {
+                return None;
+            }
+        }
+
+        Some(from + (to - from) * min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_aabb() -> Aabb {
+        Aabb {
+            position: Vector3::new(1.0, 2.0, 3.0),
+            size: Vector3::new(2.0, 4.0, 6.0),
+        }
+    }
+
+    #[test]
+    fn has_point_is_sane() {
+        let aabb = test_aabb();
+        assert!(aabb.has_point(Vector3::new(2.0, 3.0, 4.0)));
+        assert!(aabb.has_point(Vector3::new(1.0, 2.0, 3.0)));
+        assert!(aabb.has_point(Vector3::new(3.0, 6.0, 9.0)));
+        assert!(!aabb.has_point(Vector3::new(0.0, 3.0, 4.0)));
+        assert!(!aabb.has_point(Vector3::new(2.0, 3.0, 9.5)));
+    }
+
+    #[test]
+    fn merge_and_intersection_are_sane() {
+        let a = test_aabb();
+        let b = Aabb {
+            position: Vector3::new(2.0, 0.0, 5.0),
+            size: Vector3::new(4.0, 4.0, 1.0),
+        };
+
+        assert_eq!(
+            Aabb {
+                position: Vector3::new(1.0, 0.0, 3.0),
+                size: Vector3::new(5.0, 6.0, 6.0),
+            },
+            a.merge(&b)
+        );
+
+        assert!(a.intersects(&b));
+        assert_eq!(
+            Some(Aabb {
+                position: Vector3::new(2.0, 2.0, 5.0),
+                size: Vector3::new(1.0, 2.0, 1.0),
+            }),
+            a.intersection(&b)
+        );
+
+        let far = Aabb {
+            position: Vector3::new(10.0, 10.0, 10.0),
+            size: Vector3::new(1.0, 1.0, 1.0),
+        };
+        assert!(!a.intersects(&far));
+        assert_eq!(None, a.intersection(&far));
+
+        let touching = Aabb {
+            position: Vector3::new(3.0, 2.0, 3.0),
+            size: Vector3::new(1.0, 1.0, 1.0),
+        };
+        assert!(!a.intersects(&touching));
+        assert_eq!(
+            Vector3::new(0.0, 1.0, 1.0),
+            a.intersection(&touching).unwrap().size
+        );
+    }
+
+    #[test]
+    fn grow_and_expand_are_sane() {
+        let aabb = test_aabb();
+        assert_eq!(
+            Aabb {
+                position: Vector3::new(0.0, 1.0, 2.0),
+                size: Vector3::new(4.0, 6.0, 8.0),
+            },
+            aabb.grow(1.0)
+        );
+
+        assert_eq!(aabb, aabb.expand(Vector3::new(2.0, 3.0, 4.0)));
+        assert_eq!(
+            Aabb {
+                position: Vector3::new(-1.0, 2.0, 3.0),
+                size: Vector3::new(4.0, 4.0, 7.0),
+            },
+            aabb.expand(Vector3::new(-1.0, 4.0, 10.0))
+        );
+    }
+
+    #[test]
+    fn endpoints_and_support_are_sane() {
+        let aabb = test_aabb();
+        assert_eq!(Vector3::new(1.0, 2.0, 3.0), aabb.get_endpoint(0));
+        assert_eq!(Vector3::new(1.0, 2.0, 9.0), aabb.get_endpoint(1));
+        assert_eq!(Vector3::new(1.0, 6.0, 3.0), aabb.get_endpoint(2));
+        assert_eq!(Vector3::new(3.0, 2.0, 3.0), aabb.get_endpoint(4));
+        assert_eq!(Vector3::new(3.0, 6.0, 9.0), aabb.get_endpoint(7));
+
+        assert_eq!(
+            Vector3::new(1.0, 6.0, 9.0),
+            aabb.get_support(Vector3::new(1.0, -1.0, 0.0))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_endpoint_panics_out_of_bounds() {
+        test_aabb().get_endpoint(8);
+    }
+
+    #[test]
+    fn intersects_plane_is_sane() {
+        let aabb = test_aabb();
+        let splitting = Plane {
+            normal: Vector3::new(0.0, 1.0, 0.0),
+            d: 4.0,
+        };
+        let above = Plane {
+            normal: Vector3::new(0.0, 1.0, 0.0),
+            d: 7.0,
+        };
+        assert!(aabb.intersects_plane(&splitting));
+        assert!(!aabb.intersects_plane(&above));
+    }
+
+    #[test]
+    fn intersects_segment_is_sane() {
+        let aabb = test_aabb();
+
+        assert_eq!(
+            Some(Vector3::new(1.0, 4.0, 6.0)),
+            aabb.intersects_segment(Vector3::new(-1.0, 4.0, 6.0), Vector3::new(5.0, 4.0, 6.0))
+        );
+        assert_eq!(
+            Some(Vector3::new(3.0, 4.0, 6.0)),
+            aabb.intersects_segment(Vector3::new(5.0, 4.0, 6.0), Vector3::new(-1.0, 4.0, 6.0))
+        );
+
+        let inside = Vector3::new(2.0, 4.0, 6.0);
+        assert_eq!(
+            Some(inside),
+            aabb.intersects_segment(inside, Vector3::new(20.0, 4.0, 6.0))
+        );
+
+        assert_eq!(
+            None,
+            aabb.intersects_segment(Vector3::new(-1.0, 4.0, 6.0), Vector3::new(0.0, 4.0, 6.0))
+        );
+        assert_eq!(
+            None,
+            aabb.intersects_segment(Vector3::new(-1.0, 0.0, 6.0), Vector3::new(5.0, 0.0, 6.0))
+        );
+    }
 }
@@ -1,9 +1,17 @@
-use crate::Vector3;
+use crate::{real, Quat, Vector3};
 use euclid::{default, Transform3D, UnknownUnit, Vector3D};
+use std::ops::Mul;
+
+use super::CMP_EPSILON;
 
 /// A 3x3 matrix.
+///
+/// The math is a port of the engine's `Basis`, and is done in Rust without calling into the
+/// engine. Intermediate values are computed with the same precision as in the engine's
+/// single-precision build, so that results match the engine where possible.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Basis {
     pub elements: [Vector3; 3],
 }
@@ -40,12 +48,12 @@ impl Basis {
     }
 
     /// Creates a `Basis` from the rotation and scaling of the provided transform.
-    pub fn from_transform(transform: &default::Transform3D<f32>) -> Basis {
+    pub fn from_transform(transform: &default::Transform3D<real>) -> Basis {
         Self::from_typed_transform::<UnknownUnit, UnknownUnit>(transform)
     }
 
     /// Creates a `Basis` from the rotation and scaling of the provided transform, in `Dst` space.
-    pub fn from_typed_transform<Src, Dst>(transform: &Transform3D<f32, Src, Dst>) -> Basis {
+    pub fn from_typed_transform<Src, Dst>(transform: &Transform3D<real, Src, Dst>) -> Basis {
         Basis {
             elements: [
                 transform
@@ -62,17 +70,17 @@ impl Basis {
     }
 
     /// Transposed dot product with the x axis of the matrix.
-    pub fn tdotx(&self, v: Vector3) -> f32 {
+    pub fn tdotx(&self, v: Vector3) -> real {
         self.elements[0].x * v.x + self.elements[1].x * v.y + self.elements[2].x * v.z
     }
 
     /// Transposed dot product with the y axis of the matrix.
-    pub fn tdoty(&self, v: Vector3) -> f32 {
+    pub fn tdoty(&self, v: Vector3) -> real {
         self.elements[0].y * v.x + self.elements[1].y * v.y + self.elements[2].y * v.z
     }
 
     /// Transposed dot product with the z axis of the matrix.
-    pub fn tdotz(&self, v: Vector3) -> f32 {
+    pub fn tdotz(&self, v: Vector3) -> real {
         self.elements[0].z * v.x + self.elements[1].z * v.y + self.elements[2].z * v.z
     }
 
@@ -111,6 +119,362 @@ impl Basis {
         self.elements[1].z = v.y;
         self.elements[2].z = v.z;
     }
+
+    /// Creates a rotation matrix around `axis` by `phi` radians. `axis` must be normalized.
+    pub fn from_axis_angle(axis: Vector3, phi: real) -> Basis {
+        let axis_sq = Vector3::new(axis.x * axis.x, axis.y * axis.y, axis.z * axis.z);
+        let cosine = phi.cos();
+        let sine = phi.sin();
+        let t = 1.0 - cosine;
+
+        let diagonal = |sq: real| (sq as f64 + cosine as f64 * (1.0 - sq as f64)) as real;
+
+        let xyzt = axis.x * axis.y * t;
+        let zyxs = axis.z * sine;
+        let (e01, e10) = (xyzt - zyxs, xyzt + zyxs);
+
+        let xyzt = axis.x * axis.z * t;
+        let zyxs = axis.y * sine;
+        let (e02, e20) = (xyzt + zyxs, xyzt - zyxs);
+
+        let xyzt = axis.y * axis.z * t;
+        let zyxs = axis.x * sine;
+        let (e12, e21) = (xyzt - zyxs, xyzt + zyxs);
+
+        Basis {
+            elements: [
+                Vector3::new(diagonal(axis_sq.x), e01, e02),
+                Vector3::new(e10, diagonal(axis_sq.y), e12),
+                Vector3::new(e20, e21, diagonal(axis_sq.z)),
+            ],
+        }
+    }
+
+    /// Creates a rotation matrix from Euler angles in radians, in the YXZ convention used by
+    /// the engine: the rotation around Z is applied first, then X, then Y.
+    pub fn from_euler(euler: Vector3) -> Basis {
+        let (s, c) = euler.x.sin_cos();
+        let xmat = Basis::from_rows(
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, c, -s),
+            Vector3::new(0.0, s, c),
+        );
+
+        let (s, c) = euler.y.sin_cos();
+        let ymat = Basis::from_rows(
+            Vector3::new(c, 0.0, s),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(-s, 0.0, c),
+        );
+
+        let (s, c) = euler.z.sin_cos();
+        let zmat = Basis::from_rows(
+            Vector3::new(c, -s, 0.0),
+            Vector3::new(s, c, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        );
+
+        ymat * xmat * zmat
+    }
+
+    /// Creates a rotation matrix from a quaternion. The quaternion doesn't need to be
+    /// normalized, but must not be zero.
+    pub fn from_quat(quat: Quat) -> Basis {
+        let d = quat.i * quat.i + quat.j * quat.j + quat.k * quat.k + quat.r * quat.r;
+        let s = 2.0 / d;
+        let (xs, ys, zs) = (quat.i * s, quat.j * s, quat.k * s);
+        let (wx, wy, wz) = (quat.r * xs, quat.r * ys, quat.r * zs);
+        let (xx, xy, xz) = (quat.i * xs, quat.i * ys, quat.i * zs);
+        let (yy, yz, zz) = (quat.j * ys, quat.j * zs, quat.k * zs);
+
+        Basis::from_rows(
+            Vector3::new(1.0 - (yy + zz), xy - wz, xz + wy),
+            Vector3::new(xy + wz, 1.0 - (xx + zz), yz - wx),
+            Vector3::new(xz - wy, yz + wx, 1.0 - (xx + yy)),
+        )
+    }
+
+    fn from_rows(x: Vector3, y: Vector3, z: Vector3) -> Basis {
+        Basis {
+            elements: [x, y, z],
+        }
+    }
+
+    /// Returns the determinant of the matrix.
+    pub fn determinant(&self) -> real {
+        let e = &self.elements;
+        e[0].x * (e[1].y * e[2].z - e[2].y * e[1].z) - e[1].x * (e[0].y * e[2].z - e[2].y * e[0].z)
+            + e[2].x * (e[0].y * e[1].z - e[1].y * e[0].z)
+    }
+
+    /// Returns the transposed matrix.
+    pub fn transposed(&self) -> Basis {
+        Basis::from_rows(self.x(), self.y(), self.z())
+    }
+
+    /// Returns the inverse of the matrix.
+    ///
+    /// If the matrix isn't invertible, the result contains infinite or NaN elements.
+    pub fn inverse(&self) -> Basis {
+        let e = &self.elements;
+        let cofac = |r1: usize, c1: usize, r2: usize, c2: usize| {
+            let (a, b) = (e[r1].to_array(), e[r2].to_array());
+            a[c1] * b[c2] - a[c2] * b[c1]
+        };
+
+        let co = [cofac(1, 1, 2, 2), cofac(1, 2, 2, 0), cofac(1, 0, 2, 1)];
+        let det = e[0].x * co[0] + e[0].y * co[1] + e[0].z * co[2];
+        let s = 1.0 / det;
+
+        Basis::from_rows(
+            Vector3::new(co[0] * s, cofac(0, 2, 2, 1) * s, cofac(0, 1, 1, 2) * s),
+            Vector3::new(co[1] * s, cofac(0, 0, 2, 2) * s, cofac(0, 2, 1, 0) * s),
+            Vector3::new(co[2] * s, cofac(0, 1, 2, 0) * s, cofac(0, 0, 1, 1) * s),
+        )
+    }
+
+    /// Returns the matrix with its axes made orthogonal and normalized, using the
+    /// Gram-Schmidt process.
+    pub fn orthonormalized(&self) -> Basis {
+        let x = normalized(self.x());
+        let y = normalized(self.y() - x * x.dot(self.y()));
+        let z = normalized(self.z() - x * x.dot(self.z()) - y * y.dot(self.z()));
+
+        let mut basis = *self;
+        basis.set_x(x);
+        basis.set_y(y);
+        basis.set_z(z);
+        basis
+    }
+
+    /// Returns the matrix rotated around `axis` by `phi` radians. `axis` must be normalized.
+    pub fn rotated(&self, axis: Vector3, phi: real) -> Basis {
+        Basis::from_axis_angle(axis, phi) * *self
+    }
+
+    /// Returns the matrix with each axis scaled by the corresponding component of `scale`.
+    pub fn scaled(&self, scale: Vector3) -> Basis {
+        Basis::from_rows(
+            self.elements[0] * scale.x,
+            self.elements[1] * scale.y,
+            self.elements[2] * scale.z,
+        )
+    }
+
+    /// Returns the length of each axis. All components are negative if the determinant is
+    /// negative.
+    pub fn get_scale(&self) -> Vector3 {
+        let det_sign = if self.determinant() < 0.0 { -1.0 } else { 1.0 };
+        Vector3::new(self.x().length(), self.y().length(), self.z().length()) * det_sign
+    }
+
+    /// Returns the Euler angles in radians, in the YXZ convention used by the engine. See
+    /// `from_euler`.
+    ///
+    /// The matrix should be orthonormalized, although scaled matrices are accepted, like in
+    /// the engine.
+    pub fn get_euler(&self) -> Vector3 {
+        let e = &self.elements;
+        let m12 = e[1].z;
+
+        if (m12 as f64) < 1.0 - CMP_EPSILON {
+            if (m12 as f64) > -(1.0 - CMP_EPSILON) {
+                // Pure X rotations are returned in their simplest form.
+                if e[1].x == 0.0 && e[0].y == 0.0 && e[0].z == 0.0 && e[2].x == 0.0 && e[0].x == 1.0
+                {
+                    Vector3::new((-m12).atan2(e[1].y), 0.0, 0.0)
+                } else {
+                    Vector3::new((-m12).asin(), e[0].z.atan2(e[2].z), e[1].x.atan2(e[1].y))
+                }
+            } else {
+                Vector3::new(
+                    (std::f64::consts::PI * 0.5) as real,
+                    e[0].y.atan2(e[0].x),
+                    0.0,
+                )
+            }
+        } else {
+            Vector3::new(
+                (-std::f64::consts::PI * 0.5) as real,
+                -e[0].y.atan2(e[0].x),
+                0.0,
+            )
+        }
+    }
+
+    /// Returns the rotation of the matrix as a quaternion. The matrix should be
+    /// orthonormalized.
+    pub fn get_quat(&self) -> Quat {
+        let e = [
+            self.elements[0].to_array(),
+            self.elements[1].to_array(),
+            self.elements[2].to_array(),
+        ];
+        let trace = e[0][0] + e[1][1] + e[2][2];
+        let mut temp = [0.0; 4];
+
+        if trace > 0.0 {
+            let s = (trace as f64 + 1.0).sqrt() as real;
+            temp[3] = s * 0.5;
+            let s = 0.5 / s;
+
+            temp[0] = (e[2][1] - e[1][2]) * s;
+            temp[1] = (e[0][2] - e[2][0]) * s;
+            temp[2] = (e[1][0] - e[0][1]) * s;
+        } else {
+            let i = if e[0][0] < e[1][1] {
+                if e[1][1] < e[2][2] {
+                    2
+                } else {
+                    1
+                }
+            } else if e[0][0] < e[2][2] {
+                2
+            } else {
+                0
+            };
+            let j = (i + 1) % 3;
+            let k = (i + 2) % 3;
+
+            let s = ((e[i][i] - e[j][j] - e[k][k]) as f64 + 1.0).sqrt() as real;
+            temp[i] = s * 0.5;
+            let s = 0.5 / s;
+
+            temp[3] = (e[k][j] - e[j][k]) * s;
+            temp[j] = (e[j][i] + e[i][j]) * s;
+            temp[k] = (e[k][i] + e[i][k]) * s;
+        }
+
+        Quat::quaternion(temp[0], temp[1], temp[2], temp[3])
+    }
+
+    /// Returns the rotation of the matrix as a quaternion, like `get_quat`, after
+    /// orthonormalizing it and removing any reflection. This complements `get_scale`.
+    pub fn get_rotation_quat(&self) -> Quat {
+        let m = self.orthonormalized();
+        if m.determinant() < 0.0 {
+            m.scaled(Vector3::new(-1.0, -1.0, -1.0)).get_quat()
+        } else {
+            m.get_quat()
+        }
+    }
+
+    /// Spherically interpolates between the rotations of this matrix and `other`, and linearly
+    /// interpolates the length of each row, by `t` in the range 0.0 - 1.0.
+    pub fn slerp(&self, other: &Basis, t: real) -> Basis {
+        let from = self.get_quat();
+        let to = other.get_quat();
+        let basis = Basis::from_quat(slerp(from, to, t));
+
+        let lerp = |a: real, b: real| a + (b - a) * t;
+        Basis::from_rows(
+            basis.elements[0] * lerp(self.elements[0].length(), other.elements[0].length()),
+            basis.elements[1] * lerp(self.elements[1].length(), other.elements[1].length()),
+            basis.elements[2] * lerp(self.elements[2].length(), other.elements[2].length()),
+        )
+    }
+
+    /// Transforms `v` by the matrix.
+    pub fn xform(&self, v: Vector3) -> Vector3 {
+        Vector3::new(
+            self.elements[0].dot(v),
+            self.elements[1].dot(v),
+            self.elements[2].dot(v),
+        )
+    }
+
+    /// Transforms `v` by the transposed matrix, which is the inverse transformation if the
+    /// matrix is orthonormal.
+    pub fn xform_inv(&self, v: Vector3) -> Vector3 {
+        Vector3::new(self.tdotx(v), self.tdoty(v), self.tdotz(v))
+    }
+
+    /// Returns `true` if all elements of the matrices are approximately equal, like the
+    /// engine's `Math::is_equal_approx`.
+    pub fn is_equal_approx(&self, other: &Basis) -> bool {
+        let approx = |a: real, b: real| {
+            let tolerance = ((CMP_EPSILON * a.abs() as f64) as real).max(CMP_EPSILON as real);
+            a == b || (a - b).abs() < tolerance
+        };
+        self.elements
+            .iter()
+            .zip(other.elements.iter())
+            .all(|(a, b)| approx(a.x, b.x) && approx(a.y, b.y) && approx(a.z, b.z))
+    }
+}
+
+impl Mul<Basis> for Basis {
+    type Output = Basis;
+
+    /// Composes two matrices, so that `rhs` is applied first.
+    fn mul(self, rhs: Basis) -> Basis {
+        Basis::from_rows(
+            Vector3::new(
+                rhs.tdotx(self.elements[0]),
+                rhs.tdoty(self.elements[0]),
+                rhs.tdotz(self.elements[0]),
+            ),
+            Vector3::new(
+                rhs.tdotx(self.elements[1]),
+                rhs.tdoty(self.elements[1]),
+                rhs.tdotz(self.elements[1]),
+            ),
+            Vector3::new(
+                rhs.tdotx(self.elements[2]),
+                rhs.tdoty(self.elements[2]),
+                rhs.tdotz(self.elements[2]),
+            ),
+        )
+    }
+}
+
+impl Mul<Vector3> for Basis {
+    type Output = Vector3;
+
+    /// Transforms a vector, like `xform`.
+    fn mul(self, rhs: Vector3) -> Vector3 {
+        self.xform(rhs)
+    }
+}
+
+/// Normalizes a vector like the engine, which returns a zero vector for a zero length.
+pub(crate) fn normalized(v: Vector3) -> Vector3 {
+    let length_squared = v.square_length();
+    if length_squared == 0.0 {
+        Vector3::zero()
+    } else {
+        v / length_squared.sqrt()
+    }
+}
+
+/// Spherically interpolates between two quaternions like the engine's `Quat::slerp`.
+pub(crate) fn slerp(from: Quat, to: Quat, t: real) -> Quat {
+    let mut cosom = from.i * to.i + from.j * to.j + from.k * to.k + from.r * to.r;
+    let to = if cosom < 0.0 {
+        cosom = -cosom;
+        Quat::quaternion(-to.i, -to.j, -to.k, -to.r)
+    } else {
+        to
+    };
+
+    let (scale0, scale1) = if 1.0 - cosom as f64 > CMP_EPSILON {
+        let omega = cosom.acos();
+        let sinom = omega.sin();
+        (
+            (((1.0 - t as f64) * omega as f64).sin() / sinom as f64) as real,
+            (t * omega).sin() / sinom,
+        )
+    } else {
+        // The quaternions are very close, so linear interpolation is enough.
+        (1.0 - t, t)
+    };
+
+    Quat::quaternion(
+        scale0 * from.i + scale1 * to.i,
+        scale0 * from.j + scale1 * to.j,
+        scale0 * from.k + scale1 * to.k,
+        scale0 * from.r + scale1 * to.r,
+    )
 }
 
 #[cfg(test)]
@@ -129,9 +493,9 @@ mod tests {
 
         let vector = Vector3::new(4.0, 5.0, 6.0);
 
-        assert!((basis.tdotx(vector) - 32.0).abs() < std::f32::EPSILON);
-        assert!((basis.tdoty(vector) - 47.0).abs() < std::f32::EPSILON);
-        assert!((basis.tdotz(vector) - 62.0).abs() < std::f32::EPSILON);
+        assert!((basis.tdotx(vector) - 32.0).abs() < real::EPSILON);
+        assert!((basis.tdoty(vector) - 47.0).abs() < real::EPSILON);
+        assert!((basis.tdotz(vector) - 62.0).abs() < real::EPSILON);
     }
 
     #[test]
@@ -164,4 +528,144 @@ mod tests {
         assert!(basis.elements[1] == Vector3::new(4.0, 5.0, 6.0));
         assert!(basis.elements[2] == Vector3::new(7.0, 8.0, 9.0));
     }
+
+    fn assert_approx(expected: Vector3, actual: Vector3) {
+        assert!(
+            (expected - actual).length() < 1e-5,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn determinant_and_transpose_are_sane() {
+        let basis = Basis::from_rows(
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(0.0, 1.0, 4.0),
+            Vector3::new(5.0, 6.0, 0.0),
+        );
+
+        assert!((basis.determinant() - 1.0).abs() < real::EPSILON);
+        assert!(basis.transposed().x() == Vector3::new(1.0, 2.0, 3.0));
+        assert!(basis.transposed().transposed() == basis);
+        assert!((basis * basis.inverse()).is_equal_approx(&Basis::identity()));
+        assert!(
+            basis.inverse().elements
+                == [
+                    Vector3::new(-24.0, 18.0, 5.0),
+                    Vector3::new(20.0, -15.0, -4.0),
+                    Vector3::new(-5.0, 4.0, 1.0),
+                ]
+        );
+
+        let diagonal = Basis::from_diagonal(Vector3::new(2.0, 4.0, 8.0));
+        assert!(diagonal.inverse() == Basis::from_diagonal(Vector3::new(0.5, 0.25, 0.125)));
+    }
+
+    #[test]
+    fn rotation_is_sane() {
+        let half_pi = crate::real_consts::FRAC_PI_2;
+        let rotation = Basis::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), half_pi);
+
+        assert_approx(
+            Vector3::new(0.0, 0.0, -1.0),
+            rotation.xform(Vector3::new(1.0, 0.0, 0.0)),
+        );
+        assert_approx(
+            Vector3::new(0.0, 0.0, -1.0),
+            rotation * Vector3::new(1.0, 0.0, 0.0),
+        );
+        assert_approx(
+            Vector3::new(1.0, 0.0, 0.0),
+            rotation.xform_inv(Vector3::new(0.0, 0.0, -1.0)),
+        );
+        assert!((rotation.determinant() - 1.0).abs() < 1e-6);
+
+        let twice = rotation.rotated(Vector3::new(0.0, 1.0, 0.0), half_pi);
+        assert_approx(
+            Vector3::new(-1.0, 0.0, 0.0),
+            twice.xform(Vector3::new(1.0, 0.0, 0.0)),
+        );
+    }
+
+    #[test]
+    fn euler_is_sane() {
+        let euler = Vector3::new(0.3, -0.7, 1.1);
+        let basis = Basis::from_euler(euler);
+        assert_approx(euler, basis.get_euler());
+
+        // YXZ convention: Z is applied first, then X, then Y.
+        let y = Basis::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), euler.y);
+        let x = Basis::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), euler.x);
+        let z = Basis::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), euler.z);
+        assert!((y * x * z).is_equal_approx(&basis));
+
+        let pure_x = Basis::from_euler(Vector3::new(0.5, 0.0, 0.0)).get_euler();
+        assert!((pure_x.x - 0.5).abs() < 1e-6);
+        assert!(pure_x.y == 0.0 && pure_x.z == 0.0);
+
+        let locked = Basis::from_euler(Vector3::new(crate::real_consts::FRAC_PI_2, 0.4, 0.0));
+        let locked_euler = locked.get_euler();
+        assert!(locked_euler.x == crate::real_consts::FRAC_PI_2);
+        assert!(locked_euler.z == 0.0);
+        assert!(Basis::from_euler(locked_euler).is_equal_approx(&locked));
+    }
+
+    #[test]
+    fn quat_is_sane() {
+        assert!(Basis::identity().get_quat() == Quat::quaternion(0.0, 0.0, 0.0, 1.0));
+        assert!(Basis::from_quat(Quat::quaternion(0.0, 0.0, 0.0, 1.0)) == Basis::identity());
+
+        let basis = Basis::from_euler(Vector3::new(0.3, -0.7, 1.1));
+        let quat = basis.get_quat();
+        assert!(Basis::from_quat(quat).is_equal_approx(&basis));
+
+        // Rotations of more than 180 degrees take the branch where the trace is negative.
+        let around_x = Basis::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 3.0);
+        assert!(Basis::from_quat(around_x.get_quat()).is_equal_approx(&around_x));
+    }
+
+    #[test]
+    fn orthonormalize_and_scale_are_sane() {
+        let rotation = Basis::from_euler(Vector3::new(0.3, -0.7, 1.1));
+        let scaled = rotation.scaled(Vector3::new(2.0, 3.0, 4.0));
+        assert!(!scaled.is_equal_approx(&rotation));
+
+        let skewed = Basis::from_rows(
+            Vector3::new(2.0, 1.0, 0.0),
+            Vector3::new(0.0, 3.0, 1.0),
+            Vector3::new(0.0, 0.0, 4.0),
+        );
+        let orthonormal = skewed.orthonormalized();
+        assert!((orthonormal * orthonormal.transposed()).is_equal_approx(&Basis::identity()));
+        assert_approx(Vector3::new(1.0, 0.0, 0.0), orthonormal.x());
+
+        let scale = Basis::identity()
+            .scaled(Vector3::new(2.0, 3.0, 4.0))
+            .get_scale();
+        assert!(scale == Vector3::new(2.0, 3.0, 4.0));
+        let mirrored = Basis::identity()
+            .scaled(Vector3::new(-2.0, 3.0, 4.0))
+            .get_scale();
+        assert!(mirrored == Vector3::new(-2.0, -3.0, -4.0));
+    }
+
+    #[test]
+    fn slerp_is_sane() {
+        let axis = Vector3::new(0.0, 1.0, 0.0);
+        let from = Basis::identity();
+        let to = Basis::from_axis_angle(axis, 1.0);
+
+        assert!(from.slerp(&to, 0.0).is_equal_approx(&from));
+        assert!(from.slerp(&to, 1.0).is_equal_approx(&to));
+        assert!(from
+            .slerp(&to, 0.5)
+            .is_equal_approx(&Basis::from_axis_angle(axis, 0.5)));
+
+        let scaled = Basis::from_diagonal(Vector3::new(3.0, 3.0, 3.0));
+        assert!(from
+            .slerp(&scaled, 0.5)
+            .is_equal_approx(&Basis::from_diagonal(Vector3::new(2.0, 2.0, 2.0))));
+    }
 }
@@ -3,20 +3,90 @@
 mod aabb;
 mod basis;
 mod plane;
+mod rect2;
 mod transform;
+mod transform2d;
 
-pub type Vector3 = euclid::default::Vector3D<f32>;
-pub type Vector2 = euclid::default::Vector2D<f32>;
-pub type Transform2D = euclid::default::Transform2D<f32>;
-pub type Quat = euclid::default::Rotation3D<f32>;
-pub type Rect2 = euclid::default::Rect<f32>;
-pub type Angle = euclid::Angle<f32>;
-pub type Point3 = euclid::default::Point3D<f32>;
-pub type Point2 = euclid::default::Point2D<f32>;
-pub type Rotation2D = euclid::default::Rotation2D<f32>;
-pub type Rotation3D = euclid::default::Rotation3D<f32>;
+pub(crate) use self::basis::normalized;
+
+/// Floating-point type of the components of geometric types, the engine's `real_t`.
+///
+/// This is `f32`, or `f64` if the `double-precision` feature is enabled, which is required
+/// for engine builds compiled with `real_t=double`. `Color` always uses `f32`.
+#[cfg(not(feature = "double-precision"))]
+#[allow(non_camel_case_types)]
+pub type real = f32;
+
+/// Floating-point type of the components of geometric types, the engine's `real_t`.
+///
+/// This is `f32`, or `f64` if the `double-precision` feature is enabled, which is required
+/// for engine builds compiled with `real_t=double`. `Color` always uses `f32`.
+#[cfg(feature = "double-precision")]
+#[allow(non_camel_case_types)]
+pub type real = f64;
+
+/// Mathematical constants of the `real` type.
+#[cfg(not(feature = "double-precision"))]
+pub use std::f32::consts as real_consts;
+
+/// Mathematical constants of the `real` type.
+#[cfg(feature = "double-precision")]
+pub use std::f64::consts as real_consts;
+
+/// Epsilon used by the engine for approximate comparisons, `CMP_EPSILON`.
+pub(crate) const CMP_EPSILON: f64 = 0.00001;
+
+/// Epsilon used by the engine to check whether vectors are normalized, `UNIT_EPSILON`.
+pub(crate) const UNIT_EPSILON: f64 = 0.001;
+
+/// Returns the remainder of `x / y` with the sign of `y`, like the engine's `Math::fposmod`.
+pub(crate) fn fposmod(x: real, y: real) -> real {
+    let value = x % y;
+    if (value < 0.0 && y > 0.0) || (value > 0.0 && y < 0.0) {
+        value + y
+    } else {
+        // Turns -0.0 into 0.0, like the engine.
+        value + 0.0
+    }
+}
+
+/// Returns -1.0 for negative values and 1.0 otherwise, like the engine's `SGN`.
+pub(crate) fn sgn(x: real) -> real {
+    if x < 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+/// Converts a `real` to `f32`, which only rounds it with the `double-precision` feature.
+#[inline]
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn real_to_f32(x: real) -> f32 {
+    x as f32
+}
+
+pub type Vector3 = euclid::default::Vector3D<real>;
+pub type Vector2 = euclid::default::Vector2D<real>;
+pub type Transform2D = euclid::default::Transform2D<real>;
+pub type Quat = euclid::default::Rotation3D<real>;
+pub type Rect2 = euclid::default::Rect<real>;
+pub type Angle = euclid::Angle<real>;
+pub type Point3 = euclid::default::Point3D<real>;
+pub type Point2 = euclid::default::Point2D<real>;
+pub type Rotation2D = euclid::default::Rotation2D<real>;
+pub type Rotation3D = euclid::default::Rotation3D<real>;
 
 pub use self::aabb::Aabb;
 pub use self::basis::Basis;
 pub use self::plane::Plane;
+pub use self::rect2::{Margin, Rect2Godot};
 pub use self::transform::Transform;
+pub use self::transform2d::Transform2DGodot;
+
+#[cfg(feature = "gd_test")]
+#[doc(hidden)]
+pub use self::transform::test_transform_variants;
+#[cfg(feature = "gd_test")]
+#[doc(hidden)]
+pub use self::transform2d::test_transform2d_variants;
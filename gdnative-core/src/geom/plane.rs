@@ -1,11 +1,18 @@
-use crate::Vector3;
+use crate::{real, Vector3};
+
+use super::basis::normalized;
+use super::CMP_EPSILON;
 
 /// Plane in hessian form.
+///
+/// The queries are ported from the engine's `Plane` and don't call into it. They assume that
+/// the normal is normalized.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Plane {
     pub normal: Vector3,
-    pub d: f32,
+    pub d: real,
 }
 
 impl Plane {
@@ -18,4 +25,179 @@ impl Plane {
     pub fn from_sys(c: sys::godot_plane) -> Self {
         unsafe { std::mem::transmute::<sys::godot_plane, Self>(c) }
     }
+
+    /// Creates the plane going through the three points. The normal points towards the side
+    /// from which the points appear in clockwise order, like in the engine.
+    pub fn from_points(a: Vector3, b: Vector3, c: Vector3) -> Plane {
+        let normal = normalized((a - c).cross(a - b));
+        Plane {
+            normal,
+            d: normal.dot(a),
+        }
+    }
+
+    /// Returns the point of the plane that is closest to the origin.
+    #[inline]
+    pub fn center(&self) -> Vector3 {
+        self.normal * self.d
+    }
+
+    /// Returns the signed distance from the plane to `point`. It is positive if the point is
+    /// above the plane.
+    #[inline]
+    pub fn distance_to(&self, point: Vector3) -> real {
+        self.normal.dot(point) - self.d
+    }
+
+    /// Returns `true` if `point` is above the plane, i.e. on the side the normal points to.
+    #[inline]
+    pub fn is_point_over(&self, point: Vector3) -> bool {
+        self.normal.dot(point) > self.d
+    }
+
+    /// Returns the orthogonal projection of `point` onto the plane.
+    #[inline]
+    pub fn project(&self, point: Vector3) -> Vector3 {
+        point - self.normal * self.distance_to(point)
+    }
+
+    /// Returns the point where the plane, `b` and `c` intersect, or `None` if two of them are
+    /// parallel.
+    pub fn intersect_3(&self, b: &Plane, c: &Plane) -> Option<Vector3> {
+        let (normal0, normal1, normal2) = (self.normal, b.normal, c.normal);
+
+        let denom = normal0.cross(normal1).dot(normal2);
+        if is_zero_approx(denom) {
+            return None;
+        }
+
+        Some(
+            (normal1.cross(normal2) * self.d
+                + normal2.cross(normal0) * b.d
+                + normal0.cross(normal1) * c.d)
+                / denom,
+        )
+    }
+
+    /// Returns the point where the ray starting at `from` in direction `dir` hits the plane,
+    /// or `None` if it doesn't.
+    pub fn intersects_ray(&self, from: Vector3, dir: Vector3) -> Option<Vector3> {
+        let den = self.normal.dot(dir);
+        if is_zero_approx(den) {
+            return None;
+        }
+
+        let dist = (self.normal.dot(from) - self.d) / den;
+        if dist as f64 > CMP_EPSILON {
+            // The plane is behind the start of the ray.
+            return None;
+        }
+
+        Some(from + dir * -dist)
+    }
+
+    /// Returns the point where the segment from `begin` to `end` crosses the plane, or `None`
+    /// if it doesn't.
+    pub fn intersects_segment(&self, begin: Vector3, end: Vector3) -> Option<Vector3> {
+        let segment = begin - end;
+        let den = self.normal.dot(segment);
+        if is_zero_approx(den) {
+            return None;
+        }
+
+        let dist = (self.normal.dot(begin) - self.d) / den;
+        if (dist as f64) < -CMP_EPSILON || dist as f64 > 1.0 + CMP_EPSILON {
+            return None;
+        }
+
+        Some(begin + segment * -dist)
+    }
+}
+
+fn is_zero_approx(s: real) -> bool {
+    (s.abs() as f64) < CMP_EPSILON
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_plane() -> Plane {
+        Plane {
+            normal: Vector3::new(0.0, 1.0, 0.0),
+            d: 2.0,
+        }
+    }
+
+    #[test]
+    fn from_points_is_sane() {
+        let plane = Plane::from_points(
+            Vector3::new(0.0, 2.0, 0.0),
+            Vector3::new(1.0, 2.0, 0.0),
+            Vector3::new(0.0, 2.0, 1.0),
+        );
+        assert_eq!(test_plane(), plane);
+        assert_eq!(Vector3::new(0.0, 2.0, 0.0), plane.center());
+    }
+
+    #[test]
+    fn distance_and_projection_are_sane() {
+        let plane = test_plane();
+        assert_eq!(3.0, plane.distance_to(Vector3::new(1.0, 5.0, 1.0)));
+        assert_eq!(-2.0, plane.distance_to(Vector3::new(1.0, 0.0, 1.0)));
+        assert!(plane.is_point_over(Vector3::new(0.0, 3.0, 0.0)));
+        assert!(!plane.is_point_over(Vector3::new(0.0, 2.0, 0.0)));
+        assert_eq!(
+            Vector3::new(1.0, 2.0, -4.0),
+            plane.project(Vector3::new(1.0, 7.0, -4.0))
+        );
+    }
+
+    #[test]
+    fn intersect_3_is_sane() {
+        let x = Plane {
+            normal: Vector3::new(1.0, 0.0, 0.0),
+            d: 1.0,
+        };
+        let z = Plane {
+            normal: Vector3::new(0.0, 0.0, 1.0),
+            d: 3.0,
+        };
+        assert_eq!(
+            Some(Vector3::new(1.0, 2.0, 3.0)),
+            test_plane().intersect_3(&x, &z)
+        );
+        assert_eq!(None, test_plane().intersect_3(&test_plane(), &z));
+    }
+
+    #[test]
+    fn intersects_ray_is_sane() {
+        let plane = test_plane();
+        let down = Vector3::new(0.0, -1.0, 0.0);
+        assert_eq!(
+            Some(Vector3::new(1.0, 2.0, 1.0)),
+            plane.intersects_ray(Vector3::new(1.0, 5.0, 1.0), down)
+        );
+        assert_eq!(
+            None,
+            plane.intersects_ray(Vector3::new(1.0, 0.0, 1.0), down)
+        );
+        assert_eq!(
+            None,
+            plane.intersects_ray(Vector3::new(1.0, 5.0, 1.0), Vector3::new(1.0, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn intersects_segment_is_sane() {
+        let plane = test_plane();
+        assert_eq!(
+            Some(Vector3::new(1.0, 2.0, 1.0)),
+            plane.intersects_segment(Vector3::new(1.0, 6.0, 1.0), Vector3::new(1.0, -2.0, 1.0))
+        );
+        assert_eq!(
+            None,
+            plane.intersects_segment(Vector3::new(1.0, 5.0, 1.0), Vector3::new(1.0, 3.0, 1.0))
+        );
+    }
 }
@@ -0,0 +1,145 @@
+use crate::{real, Point2, Rect2, Vector2};
+use euclid::default::Size2D;
+
+/// A side of a rectangle, in the order of the engine's `Margin` constants.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Margin {
+    Left = 0,
+    Top = 1,
+    Right = 2,
+    Bottom = 3,
+}
+
+/// Helper methods for `Rect2`.
+///
+/// Trait used to provide additional methods that are equivalent to Godot's methods, ported
+/// from the engine. See the official
+/// [`Godot documentation`](https://docs.godotengine.org/en/3.2/classes/class_rect2.html).
+///
+/// Unlike `Aabb::has_point`, `has_point` doesn't contain points on the right and bottom
+/// edges, like in the engine.
+pub trait Rect2Godot {
+    /// Returns `true` if the rectangle contains `point`.
+    fn has_point(&self, point: Vector2) -> bool;
+    /// Returns the overlapping part of the rectangle and `other`, or an empty rectangle at the
+    /// origin if they don't overlap.
+    fn clip(&self, other: &Self) -> Self;
+    /// Returns the smallest rectangle containing both the rectangle and `other`.
+    fn merge(&self, other: &Self) -> Self;
+    /// Returns the rectangle expanded to include `point`.
+    fn expand(&self, point: Vector2) -> Self;
+    /// Returns the rectangle grown by `by` on every side.
+    fn grow(&self, by: real) -> Self;
+    /// Returns the rectangle grown by `by` on the side given by `margin`.
+    fn grow_margin(&self, margin: Margin, by: real) -> Self;
+    /// Returns the rectangle grown by the given amount on each side.
+    fn grow_individual(&self, left: real, top: real, right: real, bottom: real) -> Self;
+}
+
+impl Rect2Godot for Rect2 {
+    fn has_point(&self, point: Vector2) -> bool {
+        point.x >= self.origin.x
+            && point.y >= self.origin.y
+            && point.x < self.max_x()
+            && point.y < self.max_y()
+    }
+
+    fn clip(&self, other: &Rect2) -> Rect2 {
+        let overlaps = self.origin.x < other.max_x()
+            && self.origin.y < other.max_y()
+            && self.max_x() > other.origin.x
+            && self.max_y() > other.origin.y;
+        if !overlaps {
+            return Rect2::zero();
+        }
+
+        let begin = self.origin.max(other.origin);
+        let end = self.max().min(other.max());
+        Rect2::new(begin, (end - begin).to_size())
+    }
+
+    fn merge(&self, other: &Rect2) -> Rect2 {
+        let begin = self.origin.min(other.origin);
+        let end = self.max().max(other.max());
+        Rect2::new(begin, (end - begin).to_size())
+    }
+
+    fn expand(&self, point: Vector2) -> Rect2 {
+        let begin = self.origin.min(point.to_point());
+        let end = self.max().max(point.to_point());
+        Rect2::new(begin, (end - begin).to_size())
+    }
+
+    #[inline]
+    fn grow(&self, by: real) -> Rect2 {
+        self.grow_individual(by, by, by, by)
+    }
+
+    fn grow_margin(&self, margin: Margin, by: real) -> Rect2 {
+        let amount = |side| if margin == side { by } else { 0.0 };
+        self.grow_individual(
+            amount(Margin::Left),
+            amount(Margin::Top),
+            amount(Margin::Right),
+            amount(Margin::Bottom),
+        )
+    }
+
+    fn grow_individual(&self, left: real, top: real, right: real, bottom: real) -> Rect2 {
+        Rect2::new(
+            Point2::new(self.origin.x - left, self.origin.y - top),
+            Size2D::new(
+                self.size.width + left + right,
+                self.size.height + top + bottom,
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: real, y: real, width: real, height: real) -> Rect2 {
+        euclid::rect(x, y, width, height)
+    }
+
+    #[test]
+    fn has_point_is_sane() {
+        let r = rect(1.0, 2.0, 3.0, 4.0);
+        assert!(r.has_point(Vector2::new(1.0, 2.0)));
+        assert!(r.has_point(Vector2::new(3.5, 5.5)));
+        assert!(!r.has_point(Vector2::new(4.0, 3.0)));
+        assert!(!r.has_point(Vector2::new(2.0, 6.0)));
+        assert!(!r.has_point(Vector2::new(0.5, 3.0)));
+    }
+
+    #[test]
+    fn clip_and_merge_are_sane() {
+        let a = rect(0.0, 0.0, 4.0, 4.0);
+        let b = rect(2.0, -1.0, 4.0, 2.0);
+        assert_eq!(rect(2.0, 0.0, 2.0, 1.0), a.clip(&b));
+        assert_eq!(rect(0.0, -1.0, 6.0, 5.0), a.merge(&b));
+
+        let touching = rect(4.0, 0.0, 1.0, 1.0);
+        assert_eq!(Rect2::zero(), a.clip(&touching));
+        assert_eq!(rect(0.0, 0.0, 5.0, 4.0), a.merge(&touching));
+    }
+
+    #[test]
+    fn grow_and_expand_are_sane() {
+        let r = rect(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(rect(0.0, 1.0, 5.0, 6.0), r.grow(1.0));
+        assert_eq!(rect(-1.0, 2.0, 5.0, 4.0), r.grow_margin(Margin::Left, 2.0));
+        assert_eq!(rect(1.0, 0.0, 3.0, 6.0), r.grow_margin(Margin::Top, 2.0));
+        assert_eq!(rect(1.0, 2.0, 5.0, 4.0), r.grow_margin(Margin::Right, 2.0));
+        assert_eq!(rect(1.0, 2.0, 3.0, 6.0), r.grow_margin(Margin::Bottom, 2.0));
+        assert_eq!(
+            rect(0.0, 1.0, 6.0, 8.0),
+            r.grow_individual(1.0, 1.0, 2.0, 3.0)
+        );
+
+        assert_eq!(r, r.expand(Vector2::new(2.0, 3.0)));
+        assert_eq!(rect(-1.0, 2.0, 5.0, 5.0), r.expand(Vector2::new(-1.0, 7.0)));
+    }
+}
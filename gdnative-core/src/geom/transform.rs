@@ -1,9 +1,14 @@
-use crate::{Basis, Vector3};
+use crate::geom::basis::{self, normalized};
+use crate::{real, Aabb, Basis, Plane, Quat, Vector3};
 use euclid::{default, Point3D, Transform3D, UnknownUnit};
+use std::ops::Mul;
 
 /// 3D Transformation (3x4 matrix) Using basis + origin representation.
+///
+/// Like `Basis`, the methods are ported from the engine and don't call into it.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transform {
     /// The basis is a matrix containing 3 Vector3 as its columns: X axis, Y axis, and Z axis.
     /// These vectors can be interpreted as the basis vectors of local coordinate system
@@ -34,12 +39,12 @@ impl Transform {
     }
 
     /// Creates a `Basis` from the rotation and scaling of the provided transform.
-    pub fn from_transform(transform: &default::Transform3D<f32>) -> Transform {
+    pub fn from_transform(transform: &default::Transform3D<real>) -> Transform {
         Self::from_typed_transform::<UnknownUnit, UnknownUnit>(transform)
     }
 
     /// Creates a `Basis` from the rotation and scaling of the provided transform, in `Dst` space.
-    pub fn from_typed_transform<Src, Dst>(transform: &Transform3D<f32, Src, Dst>) -> Transform {
+    pub fn from_typed_transform<Src, Dst>(transform: &Transform3D<real, Src, Dst>) -> Transform {
         Transform {
             basis: Basis::from_typed_transform(transform),
             origin: transform
@@ -49,4 +54,456 @@ impl Transform {
                 .to_untyped(),
         }
     }
+
+    /// Returns the inverse of the transform, which may contain rotation, scaling and
+    /// translation.
+    pub fn affine_inverse(&self) -> Transform {
+        let basis = self.basis.inverse();
+        Transform {
+            basis,
+            origin: basis.xform(-self.origin),
+        }
+    }
+
+    /// Returns the transform rotated so that its -Z axis points towards `target`, and its
+    /// Y axis is as close as possible to `up`. The origin is kept, and any scaling is lost.
+    ///
+    /// `target` must differ from the origin, and `up` must not be parallel to the direction
+    /// from the origin to `target`.
+    pub fn looking_at(&self, target: Vector3, up: Vector3) -> Transform {
+        let v_z = normalized(self.origin - target);
+        let v_x = up.cross(v_z);
+        let v_y = v_z.cross(v_x);
+
+        let mut basis = self.basis;
+        basis.set_x(normalized(v_x));
+        basis.set_y(normalized(v_y));
+        basis.set_z(v_z);
+
+        Transform {
+            basis,
+            origin: self.origin,
+        }
+    }
+
+    /// Interpolates between this transform and `other` by `weight` in the range 0.0 - 1.0.
+    /// The rotation is interpolated spherically, and the scale and origin linearly.
+    pub fn interpolate_with(&self, other: &Transform, weight: real) -> Transform {
+        let src_scale = self.basis.get_scale();
+        let src_rot = self.basis.get_rotation_quat();
+        let dst_scale = other.basis.get_scale();
+        let dst_rot = other.basis.get_rotation_quat();
+
+        let rotation = normalized_quat(basis::slerp(src_rot, dst_rot, weight));
+        let scale = lerp(src_scale, dst_scale, weight);
+
+        Transform {
+            basis: Basis::from_quat(rotation) * Basis::from_diagonal(scale),
+            origin: lerp(self.origin, other.origin, weight),
+        }
+    }
+
+    /// Returns the transform with an orthonormalized basis. See `Basis::orthonormalized`.
+    pub fn orthonormalized(&self) -> Transform {
+        Transform {
+            basis: self.basis.orthonormalized(),
+            origin: self.origin,
+        }
+    }
+
+    /// Returns the transform rotated around `axis` by `phi` radians, in global space. `axis`
+    /// must be normalized.
+    pub fn rotated(&self, axis: Vector3, phi: real) -> Transform {
+        let rotation = Transform {
+            basis: Basis::from_axis_angle(axis, phi),
+            origin: Vector3::zero(),
+        };
+        rotation * *self
+    }
+
+    /// Returns the transform scaled by `scale`, in global space. The origin is scaled as well.
+    pub fn scaled(&self, scale: Vector3) -> Transform {
+        Transform {
+            basis: self.basis.scaled(scale),
+            origin: Vector3::new(
+                self.origin.x * scale.x,
+                self.origin.y * scale.y,
+                self.origin.z * scale.z,
+            ),
+        }
+    }
+
+    /// Returns the transform translated by `offset`, relative to its basis.
+    pub fn translated(&self, offset: Vector3) -> Transform {
+        Transform {
+            basis: self.basis,
+            origin: self.origin + self.basis.xform(offset),
+        }
+    }
+
+    /// Transforms a point.
+    pub fn xform(&self, v: Vector3) -> Vector3 {
+        let e = &self.basis.elements;
+        Vector3::new(
+            e[0].dot(v) + self.origin.x,
+            e[1].dot(v) + self.origin.y,
+            e[2].dot(v) + self.origin.z,
+        )
+    }
+
+    /// Transforms a point by the inverse of the transform, assuming that its basis is
+    /// orthonormal. Use `affine_inverse().xform(v)` otherwise.
+    pub fn xform_inv(&self, v: Vector3) -> Vector3 {
+        self.basis.xform_inv(v - self.origin)
+    }
+
+    /// Transforms a plane.
+    pub fn xform_plane(&self, plane: Plane) -> Plane {
+        xform_plane_with(plane, |v| self.xform(v))
+    }
+
+    /// Transforms a plane by the inverse of the transform, assuming that its basis is
+    /// orthonormal.
+    pub fn xform_inv_plane(&self, plane: Plane) -> Plane {
+        xform_plane_with(plane, |v| self.xform_inv(v))
+    }
+
+    /// Returns the smallest axis-aligned box containing the transformed box.
+    pub fn xform_aabb(&self, aabb: Aabb) -> Aabb {
+        let min = aabb.position.to_array();
+        let max = (aabb.position + aabb.size).to_array();
+        let origin = self.origin.to_array();
+
+        let mut tmin = origin;
+        let mut tmax = origin;
+        for i in 0..3 {
+            let row = self.basis.elements[i].to_array();
+            for j in 0..3 {
+                let e = row[j] * min[j];
+                let f = row[j] * max[j];
+                if e < f {
+                    tmin[i] += e;
+                    tmax[i] += f;
+                } else {
+                    tmin[i] += f;
+                    tmax[i] += e;
+                }
+            }
+        }
+
+        let (tmin, tmax) = (Vector3::from(tmin), Vector3::from(tmax));
+        Aabb {
+            position: tmin,
+            size: tmax - tmin,
+        }
+    }
+
+    /// Returns the smallest axis-aligned box containing the box transformed by the inverse
+    /// of the transform, assuming that its basis is orthonormal.
+    pub fn xform_inv_aabb(&self, aabb: Aabb) -> Aabb {
+        let (p, s) = (aabb.position, aabb.size);
+        let vertices = [
+            Vector3::new(p.x + s.x, p.y + s.y, p.z + s.z),
+            Vector3::new(p.x + s.x, p.y + s.y, p.z),
+            Vector3::new(p.x + s.x, p.y, p.z + s.z),
+            Vector3::new(p.x + s.x, p.y, p.z),
+            Vector3::new(p.x, p.y + s.y, p.z + s.z),
+            Vector3::new(p.x, p.y + s.y, p.z),
+            Vector3::new(p.x, p.y, p.z + s.z),
+            Vector3::new(p.x, p.y, p.z),
+        ];
+
+        let mut aabb = Aabb {
+            position: self.xform_inv(vertices[0]),
+            size: Vector3::zero(),
+        };
+        for &vertex in &vertices[1..] {
+            aabb = aabb.expand(self.xform_inv(vertex));
+        }
+        aabb
+    }
+}
+
+impl Mul<Transform> for Transform {
+    type Output = Transform;
+
+    /// Composes two transforms, so that `rhs` is applied first.
+    fn mul(self, rhs: Transform) -> Transform {
+        Transform {
+            basis: self.basis * rhs.basis,
+            origin: self.xform(rhs.origin),
+        }
+    }
+}
+
+impl Mul<Vector3> for Transform {
+    type Output = Vector3;
+
+    /// Transforms a point, like `xform`.
+    fn mul(self, rhs: Vector3) -> Vector3 {
+        self.xform(rhs)
+    }
+}
+
+/// Normalizes a quaternion like the engine's `Quat::normalized`.
+fn normalized_quat(q: Quat) -> Quat {
+    let length = (q.i * q.i + q.j * q.j + q.k * q.k + q.r * q.r).sqrt();
+    let s = (1.0 / length as f64) as real;
+    Quat::quaternion(q.i * s, q.j * s, q.k * s, q.r * s)
+}
+
+fn lerp(from: Vector3, to: Vector3, weight: real) -> Vector3 {
+    Vector3::new(
+        from.x + weight * (to.x - from.x),
+        from.y + weight * (to.y - from.y),
+        from.z + weight * (to.z - from.z),
+    )
+}
+
+fn xform_plane_with(plane: Plane, xform: impl Fn(Vector3) -> Vector3) -> Plane {
+    let point = plane.normal * plane.d;
+    let point_dir = point + plane.normal;
+    let point = xform(point);
+    let point_dir = xform(point_dir);
+
+    let normal = normalized(point_dir - point);
+    Plane {
+        normal,
+        d: normal.dot(point),
+    }
+}
+
+godot_test!(
+    test_transform_variants {
+        use crate::{FromVariant, ToVariant};
+
+        fn test(x: Vector3, y: Vector3, z: Vector3, origin: Vector3, point: Vector3) {
+            let api = crate::get_api();
+
+            let mut basis = Basis::identity();
+            basis.set_x(x);
+            basis.set_y(y);
+            basis.set_z(z);
+            let transform = Transform { basis, origin };
+
+            let from_engine = unsafe {
+                let mut dest = sys::godot_transform::default();
+                (api.godot_transform_new_with_axis_origin)(
+                    &mut dest,
+                    &x as *const _ as *const sys::godot_vector3,
+                    &y as *const _ as *const sys::godot_vector3,
+                    &z as *const _ as *const sys::godot_vector3,
+                    &origin as *const _ as *const sys::godot_vector3,
+                );
+                Transform::from_sys(dest)
+            };
+            assert_eq!(transform, from_engine);
+
+            unsafe {
+                let engine_origin = (api.godot_transform_get_origin)(transform.sys());
+                assert_eq!(origin, std::mem::transmute::<sys::godot_vector3, Vector3>(engine_origin));
+
+                let p = &point as *const _ as *const sys::godot_vector3;
+                let xformed = (api.godot_transform_xform_vector3)(transform.sys(), p);
+                assert_eq!(transform.xform(point), std::mem::transmute::<sys::godot_vector3, Vector3>(xformed));
+                let xformed = (api.godot_transform_xform_inv_vector3)(transform.sys(), p);
+                assert_eq!(transform.xform_inv(point), std::mem::transmute::<sys::godot_vector3, Vector3>(xformed));
+            }
+
+            let variant = transform.to_variant();
+            assert_eq!(Some(transform), variant.try_to_transform());
+            assert_eq!(transform, Transform::from_variant(&variant).unwrap());
+        }
+
+        test(
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 2.0, 3.0),
+        );
+        test(
+            Vector3::new(0.0, 2.0, 0.0),
+            Vector3::new(-3.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, 0.5),
+            Vector3::new(4.0, 5.0, 6.0),
+            Vector3::new(-1.0, 0.5, 2.0),
+        );
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec_approx(expected: Vector3, actual: Vector3) {
+        assert!(
+            (expected - actual).length() < 0.0001,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    fn assert_transform_approx(expected: &Transform, actual: &Transform) {
+        assert!(
+            expected.basis.is_equal_approx(&actual.basis),
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+        assert_vec_approx(expected.origin, actual.origin);
+    }
+
+    fn test_transform() -> Transform {
+        Transform {
+            basis: Basis::from_euler(Vector3::new(0.3, -0.7, 1.1))
+                .scaled(Vector3::new(2.0, 0.5, 1.5)),
+            origin: Vector3::new(1.0, -2.0, 3.0),
+        }
+    }
+
+    #[test]
+    fn it_has_the_engine_layout() {
+        let mut basis = Basis::identity();
+        basis.set_x(Vector3::new(1.0, 2.0, 3.0));
+        basis.set_y(Vector3::new(4.0, 5.0, 6.0));
+        basis.set_z(Vector3::new(7.0, 8.0, 9.0));
+        let transform = Transform {
+            basis,
+            origin: Vector3::new(10.0, 11.0, 12.0),
+        };
+
+        // The engine stores the rows of the basis, followed by the origin.
+        let reals: [real; 12] = unsafe { std::mem::transmute(transform) };
+        assert_eq!(
+            [1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0, 10.0, 11.0, 12.0],
+            reals
+        );
+        assert_eq!(
+            transform,
+            Transform::from_sys(unsafe {
+                std::mem::transmute::<[real; 12], sys::godot_transform>(reals)
+            })
+        );
+    }
+
+    #[test]
+    fn affine_inverse_is_sane() {
+        let transform = test_transform();
+        let identity = Transform::translate(Vector3::zero());
+
+        assert_transform_approx(&identity, &(transform * transform.affine_inverse()));
+        assert_transform_approx(&identity, &(transform.affine_inverse() * transform));
+
+        let point = Vector3::new(0.5, 4.0, -2.0);
+        assert_vec_approx(
+            point,
+            transform.affine_inverse().xform(transform.xform(point)),
+        );
+    }
+
+    #[test]
+    fn xform_inv_inverts_orthonormal_transforms() {
+        let transform = test_transform().orthonormalized();
+        let point = Vector3::new(0.5, 4.0, -2.0);
+        assert_vec_approx(point, transform.xform_inv(transform.xform(point)));
+        assert_vec_approx(
+            transform.affine_inverse().xform(point),
+            transform.xform_inv(point),
+        );
+    }
+
+    #[test]
+    fn looking_at_is_sane() {
+        let transform = Transform::translate(Vector3::new(1.0, 1.0, 1.0))
+            .looking_at(Vector3::new(1.0, 1.0, -4.0), Vector3::new(0.0, 1.0, 0.0));
+        assert_transform_approx(
+            &Transform::translate(Vector3::new(1.0, 1.0, 1.0)),
+            &transform,
+        );
+
+        let transform = Transform::translate(Vector3::zero())
+            .looking_at(Vector3::new(3.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        assert_vec_approx(Vector3::new(-1.0, 0.0, 0.0), transform.basis.z());
+        assert_vec_approx(Vector3::new(0.0, 1.0, 0.0), transform.basis.y());
+        assert_vec_approx(Vector3::new(0.0, 0.0, 1.0), transform.basis.x());
+    }
+
+    #[test]
+    fn interpolate_with_is_sane() {
+        let from = Transform::translate(Vector3::new(0.0, 0.0, 0.0));
+        let to = Transform {
+            basis: Basis::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 1.0)
+                .scaled(Vector3::new(3.0, 3.0, 3.0)),
+            origin: Vector3::new(2.0, 4.0, 6.0),
+        };
+
+        assert_transform_approx(&from, &from.interpolate_with(&to, 0.0));
+        assert_transform_approx(&to, &from.interpolate_with(&to, 1.0));
+
+        let expected = Transform {
+            basis: Basis::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 0.5)
+                .scaled(Vector3::new(2.0, 2.0, 2.0)),
+            origin: Vector3::new(1.0, 2.0, 3.0),
+        };
+        assert_transform_approx(&expected, &from.interpolate_with(&to, 0.5));
+    }
+
+    #[test]
+    fn rotated_scaled_translated_are_sane() {
+        let transform = Transform::translate(Vector3::new(1.0, 0.0, 0.0));
+
+        let rotated = transform.rotated(Vector3::new(0.0, 0.0, 1.0), crate::real_consts::FRAC_PI_2);
+        assert_vec_approx(Vector3::new(0.0, 1.0, 0.0), rotated.origin);
+        assert_vec_approx(Vector3::new(0.0, 1.0, 0.0), rotated.basis.x());
+
+        let scaled = transform.scaled(Vector3::new(2.0, 3.0, 4.0));
+        assert_vec_approx(Vector3::new(2.0, 0.0, 0.0), scaled.origin);
+        assert_vec_approx(Vector3::new(2.0, 3.0, 4.0), scaled.basis.get_scale());
+
+        let translated = rotated.translated(Vector3::new(1.0, 0.0, 0.0));
+        assert_vec_approx(Vector3::new(0.0, 2.0, 0.0), translated.origin);
+    }
+
+    #[test]
+    fn xform_plane_is_sane() {
+        let transform = Transform::translate(Vector3::new(0.0, 2.0, 0.0))
+            .rotated(Vector3::new(1.0, 0.0, 0.0), crate::real_consts::FRAC_PI_2);
+        let plane = Plane {
+            normal: Vector3::new(0.0, 1.0, 0.0),
+            d: 1.0,
+        };
+
+        let xformed = transform.xform_plane(plane);
+        assert_vec_approx(Vector3::new(0.0, 0.0, 1.0), xformed.normal);
+        assert!((xformed.d - 3.0).abs() < 0.0001);
+
+        let back = transform.xform_inv_plane(xformed);
+        assert_vec_approx(plane.normal, back.normal);
+        assert!((plane.d - back.d).abs() < 0.0001);
+    }
+
+    #[test]
+    fn xform_aabb_is_sane() {
+        let transform = Transform {
+            basis: Basis::from_axis_angle(
+                Vector3::new(0.0, 0.0, 1.0),
+                crate::real_consts::FRAC_PI_2,
+            ),
+            origin: Vector3::new(10.0, 0.0, 0.0),
+        };
+        let aabb = Aabb {
+            position: Vector3::new(1.0, 2.0, 3.0),
+            size: Vector3::new(2.0, 4.0, 6.0),
+        };
+
+        let xformed = transform.xform_aabb(aabb);
+        assert_vec_approx(Vector3::new(4.0, 1.0, 3.0), xformed.position);
+        assert_vec_approx(Vector3::new(4.0, 2.0, 6.0), xformed.size);
+
+        let back = transform.xform_inv_aabb(xformed);
+        assert_vec_approx(aabb.position, back.position);
+        assert_vec_approx(aabb.size, back.size);
+    }
 }
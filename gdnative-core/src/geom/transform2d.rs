@@ -0,0 +1,515 @@
+use crate::{real, Rect2, Rect2Godot, Transform2D, Vector2, Vector2Godot};
+use euclid::default::Size2D;
+
+/// Helper methods for `Transform2D`.
+///
+/// Trait used to provide additional methods that are equivalent to Godot's methods, ported
+/// from the engine. See the official
+/// [`Godot documentation`](https://docs.godotengine.org/en/3.2/classes/class_transform2d.html).
+///
+/// `Transform2D` is an alias for euclid's `Transform2D`, which has the same memory layout as
+/// the engine's type: `m11`, `m12` is the X axis, `m21`, `m22` the Y axis, and `m31`, `m32`
+/// the origin. However, euclid transforms row vectors, so `a.post_transform(&b)` applies `a`
+/// first, like `b * a` in GDScript, and euclid's `pre_rotate`/`post_rotate` don't match
+/// `rotated`. Prefer the methods of this trait when porting code from GDScript.
+pub trait Transform2DGodot {
+    /// Creates a transform from its X axis, Y axis and origin, which are the columns of the
+    /// matrix.
+    fn from_axis_origin(x: Vector2, y: Vector2, origin: Vector2) -> Self;
+    /// Creates a transform from a rotation in radians and an origin.
+    fn from_rotation_origin(rotation: real, origin: Vector2) -> Self;
+    /// Returns the X axis of the transform.
+    fn x_axis(&self) -> Vector2;
+    /// Returns the Y axis of the transform.
+    fn y_axis(&self) -> Vector2;
+    /// Returns the origin of the transform.
+    fn origin(&self) -> Vector2;
+    /// Sets the X axis of the transform.
+    fn set_x_axis(&mut self, x: Vector2);
+    /// Sets the Y axis of the transform.
+    fn set_y_axis(&mut self, y: Vector2);
+    /// Sets the origin of the transform.
+    fn set_origin(&mut self, origin: Vector2);
+    /// Returns the rotation of the transform in radians. If the determinant of the basis is
+    /// negative, the reflection is attributed to the Y axis, like in `get_scale`.
+    fn get_rotation(&self) -> real;
+    /// Returns the length of each axis. The Y component is negative if the determinant of
+    /// the basis is negative.
+    fn get_scale(&self) -> Vector2;
+    /// Returns the inverse of the transform, which may contain rotation, scaling and
+    /// translation.
+    fn affine_inverse(&self) -> Self;
+    /// Returns the transform with its axes made orthogonal and normalized.
+    fn orthonormalized(&self) -> Self;
+    /// Returns the transform rotated by `phi` radians, in global space.
+    fn rotated(&self, phi: real) -> Self;
+    /// Returns the transform scaled by `scale`, in global space. The origin is scaled as well.
+    fn scaled(&self, scale: Vector2) -> Self;
+    /// Returns the transform translated by `offset`, relative to its axes.
+    fn translated(&self, offset: Vector2) -> Self;
+    /// Interpolates between this transform and `other` by `weight` in the range 0.0 - 1.0.
+    /// The rotation is interpolated spherically, and the scale and origin linearly.
+    fn interpolate_with(&self, other: &Self, weight: real) -> Self;
+    /// Transforms a point.
+    fn xform(&self, v: Vector2) -> Vector2;
+    /// Transforms a point by the inverse of the transform, assuming that its basis is
+    /// orthonormal. Use `affine_inverse().xform(v)` otherwise.
+    fn xform_inv(&self, v: Vector2) -> Vector2;
+    /// Transforms a vector, without applying the translation.
+    fn basis_xform(&self, v: Vector2) -> Vector2;
+    /// Transforms a vector by the inverse of the basis, assuming that it is orthonormal.
+    fn basis_xform_inv(&self, v: Vector2) -> Vector2;
+    /// Returns the smallest rectangle containing the transformed rectangle.
+    fn xform_rect(&self, rect: Rect2) -> Rect2;
+    /// Returns the smallest rectangle containing the rectangle transformed by the inverse of
+    /// the transform, assuming that its basis is orthonormal.
+    fn xform_inv_rect(&self, rect: Rect2) -> Rect2;
+}
+
+impl Transform2DGodot for Transform2D {
+    #[inline]
+    fn from_axis_origin(x: Vector2, y: Vector2, origin: Vector2) -> Self {
+        Transform2D::row_major(x.x, x.y, y.x, y.y, origin.x, origin.y)
+    }
+
+    #[inline]
+    fn from_rotation_origin(rotation: real, origin: Vector2) -> Self {
+        let (sr, cr) = rotation.sin_cos();
+        Transform2D::row_major(cr, sr, -sr, cr, origin.x, origin.y)
+    }
+
+    #[inline]
+    fn x_axis(&self) -> Vector2 {
+        Vector2::new(self.m11, self.m12)
+    }
+
+    #[inline]
+    fn y_axis(&self) -> Vector2 {
+        Vector2::new(self.m21, self.m22)
+    }
+
+    #[inline]
+    fn origin(&self) -> Vector2 {
+        Vector2::new(self.m31, self.m32)
+    }
+
+    #[inline]
+    fn set_x_axis(&mut self, x: Vector2) {
+        self.m11 = x.x;
+        self.m12 = x.y;
+    }
+
+    #[inline]
+    fn set_y_axis(&mut self, y: Vector2) {
+        self.m21 = y.x;
+        self.m22 = y.y;
+    }
+
+    #[inline]
+    fn set_origin(&mut self, origin: Vector2) {
+        self.m31 = origin.x;
+        self.m32 = origin.y;
+    }
+
+    fn get_rotation(&self) -> real {
+        let mut m = self.orthonormalized();
+        if basis_determinant(self) < 0.0 {
+            // Like the engine, absorb a flip along Y into the scale.
+            m = scale_basis(&m, Vector2::new(1.0, -1.0));
+        }
+        m.m12.atan2(m.m11)
+    }
+
+    #[inline]
+    fn get_scale(&self) -> Vector2 {
+        let det_sign = if basis_determinant(self) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        Vector2::new(self.x_axis().length(), det_sign * self.y_axis().length())
+    }
+
+    fn affine_inverse(&self) -> Self {
+        let idet = 1.0 / basis_determinant(self);
+        let mut inverse = Transform2D::row_major(
+            self.m22 * idet,
+            self.m12 * -idet,
+            self.m21 * -idet,
+            self.m11 * idet,
+            0.0,
+            0.0,
+        );
+        let origin = inverse.basis_xform(-self.origin());
+        inverse.set_origin(origin);
+        inverse
+    }
+
+    fn orthonormalized(&self) -> Self {
+        let x = self.x_axis().normalized();
+        let y = (self.y_axis() - x * x.dot(self.y_axis())).normalized();
+        Transform2D::from_axis_origin(x, y, self.origin())
+    }
+
+    #[inline]
+    fn rotated(&self, phi: real) -> Self {
+        mul(
+            &Transform2D::from_rotation_origin(phi, Vector2::zero()),
+            self,
+        )
+    }
+
+    #[inline]
+    fn scaled(&self, scale: Vector2) -> Self {
+        let mut scaled = scale_basis(self, scale);
+        scaled.set_origin(Vector2::new(self.m31 * scale.x, self.m32 * scale.y));
+        scaled
+    }
+
+    #[inline]
+    fn translated(&self, offset: Vector2) -> Self {
+        let mut translated = *self;
+        translated.set_origin(self.origin() + self.basis_xform(offset));
+        translated
+    }
+
+    fn interpolate_with(&self, other: &Self, weight: real) -> Self {
+        let (r1, r2) = (self.get_rotation(), other.get_rotation());
+
+        let v1 = Vector2::new(r1.cos(), r1.sin());
+        let v2 = Vector2::new(r2.cos(), r2.sin());
+        let dot = v1.dot(v2);
+        let dot = dot.clamp(-1.0, 1.0);
+
+        let v = if dot as f64 > 0.9995 {
+            // Linear interpolation avoids precision issues for close rotations.
+            v1.linear_interpolate(v2, weight).normalized()
+        } else {
+            let angle = weight * dot.acos();
+            let v3 = (v2 - v1 * dot).normalized();
+            v1 * angle.cos() + v3 * angle.sin()
+        };
+
+        let origin = self.origin().linear_interpolate(other.origin(), weight);
+        let scale = self
+            .get_scale()
+            .linear_interpolate(other.get_scale(), weight);
+        let result = Transform2D::from_rotation_origin(v.y.atan2(v.x), origin);
+        scale_basis(&result, scale)
+    }
+
+    #[inline]
+    fn xform(&self, v: Vector2) -> Vector2 {
+        self.basis_xform(v) + self.origin()
+    }
+
+    #[inline]
+    fn xform_inv(&self, v: Vector2) -> Vector2 {
+        self.basis_xform_inv(v - self.origin())
+    }
+
+    #[inline]
+    fn basis_xform(&self, v: Vector2) -> Vector2 {
+        Vector2::new(
+            self.m11 * v.x + self.m21 * v.y,
+            self.m12 * v.x + self.m22 * v.y,
+        )
+    }
+
+    #[inline]
+    fn basis_xform_inv(&self, v: Vector2) -> Vector2 {
+        Vector2::new(self.x_axis().dot(v), self.y_axis().dot(v))
+    }
+
+    fn xform_rect(&self, rect: Rect2) -> Rect2 {
+        let x = self.x_axis() * rect.size.width;
+        let y = self.y_axis() * rect.size.height;
+        let pos = self.xform(rect.origin.to_vector());
+
+        let rect = Rect2::new(pos.to_point(), Size2D::zero());
+        rect.expand(pos + x).expand(pos + y).expand(pos + x + y)
+    }
+
+    fn xform_inv_rect(&self, rect: Rect2) -> Rect2 {
+        let (p, s) = (rect.origin, rect.size);
+        let ends = [
+            self.xform_inv(Vector2::new(p.x, p.y + s.height)),
+            self.xform_inv(Vector2::new(p.x + s.width, p.y + s.height)),
+            self.xform_inv(Vector2::new(p.x + s.width, p.y)),
+        ];
+
+        let start = self.xform_inv(p.to_vector());
+        ends.iter().fold(
+            Rect2::new(start.to_point(), Size2D::zero()),
+            |rect, &end| rect.expand(end),
+        )
+    }
+}
+
+fn basis_determinant(t: &Transform2D) -> real {
+    t.m11 * t.m22 - t.m12 * t.m21
+}
+
+/// Scales the axes of a transform in global space, like the engine's `scale_basis`.
+fn scale_basis(t: &Transform2D, scale: Vector2) -> Transform2D {
+    Transform2D::row_major(
+        t.m11 * scale.x,
+        t.m12 * scale.y,
+        t.m21 * scale.x,
+        t.m22 * scale.y,
+        t.m31,
+        t.m32,
+    )
+}
+
+/// Composes two transforms like the engine's `operator*`, so that `b` is applied first.
+fn mul(a: &Transform2D, b: &Transform2D) -> Transform2D {
+    let origin = a.xform(b.origin());
+    let x = a.basis_xform(b.x_axis());
+    let y = a.basis_xform(b.y_axis());
+    Transform2D::from_axis_origin(x, y, origin)
+}
+
+godot_test!(
+    test_transform2d_variants {
+        use crate::{FromVariant, ToVariant};
+
+        fn test(x: Vector2, y: Vector2, origin: Vector2, point: Vector2) {
+            let api = crate::get_api();
+            let transform = Transform2D::from_axis_origin(x, y, origin);
+
+            let from_engine = unsafe {
+                let mut dest = sys::godot_transform2d::default();
+                (api.godot_transform2d_new_axis_origin)(
+                    &mut dest,
+                    &x as *const _ as *const sys::godot_vector2,
+                    &y as *const _ as *const sys::godot_vector2,
+                    &origin as *const _ as *const sys::godot_vector2,
+                );
+                std::mem::transmute::<sys::godot_transform2d, Transform2D>(dest)
+            };
+            assert_eq!(transform, from_engine);
+
+            unsafe {
+                let this = &transform as *const _ as *const sys::godot_transform2d;
+                let engine_origin = (api.godot_transform2d_get_origin)(this);
+                assert_eq!(origin, std::mem::transmute::<sys::godot_vector2, Vector2>(engine_origin));
+                assert_eq!(transform.get_rotation(), (api.godot_transform2d_get_rotation)(this));
+
+                let p = &point as *const _ as *const sys::godot_vector2;
+                let xformed = (api.godot_transform2d_xform_vector2)(this, p);
+                assert_eq!(transform.xform(point), std::mem::transmute::<sys::godot_vector2, Vector2>(xformed));
+                let xformed = (api.godot_transform2d_xform_inv_vector2)(this, p);
+                assert_eq!(transform.xform_inv(point), std::mem::transmute::<sys::godot_vector2, Vector2>(xformed));
+            }
+
+            let variant = transform.to_variant();
+            assert_eq!(Some(transform), variant.try_to_transform2d());
+            assert_eq!(transform, Transform2D::from_variant(&variant).unwrap());
+        }
+
+        test(
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, 1.0),
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 2.0),
+        );
+        test(
+            Vector2::new(0.0, 2.0),
+            Vector2::new(-3.0, 0.0),
+            Vector2::new(4.0, 5.0),
+            Vector2::new(-1.0, 0.5),
+        );
+        test(
+            Vector2::new(0.0, 2.0),
+            Vector2::new(3.0, 0.0),
+            Vector2::new(-1.0, 1.0),
+            Vector2::new(2.0, 0.5),
+        );
+
+        let api = crate::get_api();
+        let origin = Vector2::new(1.0, 2.0);
+        let from_engine = unsafe {
+            let mut dest = sys::godot_transform2d::default();
+            (api.godot_transform2d_new)(
+                &mut dest,
+                0.5,
+                &origin as *const _ as *const sys::godot_vector2,
+            );
+            std::mem::transmute::<sys::godot_transform2d, Transform2D>(dest)
+        };
+        assert_eq!(Transform2D::from_rotation_origin(0.5, origin), from_engine);
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec_approx(expected: Vector2, actual: Vector2) {
+        assert!(
+            (expected - actual).length() < 0.0001,
+            "expected {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    fn assert_transform_approx(expected: &Transform2D, actual: &Transform2D) {
+        assert_vec_approx(expected.x_axis(), actual.x_axis());
+        assert_vec_approx(expected.y_axis(), actual.y_axis());
+        assert_vec_approx(expected.origin(), actual.origin());
+    }
+
+    fn test_transform() -> Transform2D {
+        Transform2D::from_rotation_origin(0.7, Vector2::new(1.0, -2.0))
+            .scaled(Vector2::new(2.0, 0.5))
+    }
+
+    #[test]
+    fn it_has_the_engine_layout() {
+        let mut transform = Transform2D::from_axis_origin(
+            Vector2::new(1.0, 2.0),
+            Vector2::new(3.0, 4.0),
+            Vector2::new(5.0, 6.0),
+        );
+
+        // The engine stores the X axis, the Y axis and the origin.
+        let reals: [real; 6] = unsafe { std::mem::transmute(transform) };
+        assert_eq!([1.0, 2.0, 3.0, 4.0, 5.0, 6.0], reals);
+        let from_sys: sys::godot_transform2d = unsafe { std::mem::transmute(reals) };
+        assert_eq!(transform, unsafe {
+            std::mem::transmute::<sys::godot_transform2d, Transform2D>(from_sys)
+        });
+
+        transform.set_x_axis(Vector2::new(-1.0, -2.0));
+        transform.set_y_axis(Vector2::new(-3.0, -4.0));
+        transform.set_origin(Vector2::new(-5.0, -6.0));
+        let reals: [real; 6] = unsafe { std::mem::transmute(transform) };
+        assert_eq!([-1.0, -2.0, -3.0, -4.0, -5.0, -6.0], reals);
+    }
+
+    #[test]
+    fn rotation_and_scale_are_sane() {
+        let transform = Transform2D::from_rotation_origin(0.5, Vector2::zero());
+        assert!((transform.get_rotation() - 0.5).abs() < 0.0001);
+        assert_vec_approx(Vector2::new(1.0, 1.0), transform.get_scale());
+
+        let flipped = Transform2D::from_axis_origin(
+            Vector2::new(2.0, 0.0),
+            Vector2::new(0.0, -3.0),
+            Vector2::zero(),
+        );
+        assert_vec_approx(Vector2::new(2.0, -3.0), flipped.get_scale());
+        assert!(flipped.get_rotation().abs() < 0.0001);
+    }
+
+    #[test]
+    fn rotation_of_flipped_transforms_matches_the_engine() {
+        use std::f64::consts::FRAC_PI_2;
+
+        // Mirrored along the diagonal: the engine reports a rotation of -PI/2 with a scale of
+        // (2, -3), not PI/2, which is the angle of the X axis.
+        let flipped = Transform2D::from_axis_origin(
+            Vector2::new(0.0, 2.0),
+            Vector2::new(3.0, 0.0),
+            Vector2::zero(),
+        );
+        assert!((flipped.get_rotation() as f64 + FRAC_PI_2).abs() < 0.0001);
+        assert_vec_approx(Vector2::new(2.0, -3.0), flipped.get_scale());
+
+        // With a uniform scale, the rotation and scale recompose into the same transform.
+        let flipped = Transform2D::from_axis_origin(
+            Vector2::new(0.0, 2.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(1.0, 1.0),
+        );
+        let moved = Transform2D::from_axis_origin(
+            Vector2::new(0.0, 2.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(3.0, 1.0),
+        );
+        let halfway = Transform2D::from_axis_origin(
+            Vector2::new(0.0, 2.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(2.0, 1.0),
+        );
+        assert_transform_approx(&flipped, &flipped.interpolate_with(&flipped, 0.5));
+        assert_transform_approx(&halfway, &flipped.interpolate_with(&moved, 0.5));
+    }
+
+    #[test]
+    fn affine_inverse_is_sane() {
+        let transform = test_transform();
+        let identity = Transform2D::identity();
+
+        assert_transform_approx(&identity, &mul(&transform, &transform.affine_inverse()));
+        assert_transform_approx(&identity, &mul(&transform.affine_inverse(), &transform));
+
+        let point = Vector2::new(0.5, 4.0);
+        assert_vec_approx(
+            point,
+            transform.affine_inverse().xform(transform.xform(point)),
+        );
+    }
+
+    #[test]
+    fn xform_inv_inverts_orthonormal_transforms() {
+        let transform = test_transform().orthonormalized();
+        let point = Vector2::new(0.5, 4.0);
+        assert_vec_approx(point, transform.xform_inv(transform.xform(point)));
+        assert_vec_approx(
+            transform.affine_inverse().xform(point),
+            transform.xform_inv(point),
+        );
+    }
+
+    #[test]
+    fn rotated_scaled_translated_are_sane() {
+        let transform = Transform2D::from_rotation_origin(0.0, Vector2::new(1.0, 0.0));
+
+        let rotated = transform.rotated(crate::real_consts::FRAC_PI_2);
+        assert_vec_approx(Vector2::new(0.0, 1.0), rotated.origin());
+        assert_vec_approx(Vector2::new(0.0, 1.0), rotated.x_axis());
+
+        let scaled = transform.scaled(Vector2::new(2.0, 3.0));
+        assert_vec_approx(Vector2::new(2.0, 0.0), scaled.origin());
+        assert_vec_approx(Vector2::new(2.0, 3.0), scaled.get_scale());
+
+        let translated = rotated.translated(Vector2::new(1.0, 0.0));
+        assert_vec_approx(Vector2::new(0.0, 2.0), translated.origin());
+    }
+
+    #[test]
+    fn interpolate_with_is_sane() {
+        let from = Transform2D::identity();
+        let mut to =
+            Transform2D::from_rotation_origin(1.0, Vector2::zero()).scaled(Vector2::new(3.0, 3.0));
+        to.set_origin(Vector2::new(2.0, 4.0));
+
+        assert_transform_approx(&from, &from.interpolate_with(&to, 0.0));
+        assert_transform_approx(&to, &from.interpolate_with(&to, 1.0));
+
+        let mut expected =
+            Transform2D::from_rotation_origin(0.5, Vector2::zero()).scaled(Vector2::new(2.0, 2.0));
+        expected.set_origin(Vector2::new(1.0, 2.0));
+        assert_transform_approx(&expected, &from.interpolate_with(&to, 0.5));
+    }
+
+    #[test]
+    fn xform_rect_is_sane() {
+        let transform = Transform2D::from_rotation_origin(
+            crate::real_consts::FRAC_PI_2,
+            Vector2::new(10.0, 0.0),
+        );
+        let rect = euclid::rect(1.0, 2.0, 2.0, 4.0);
+
+        let xformed = transform.xform_rect(rect);
+        assert_vec_approx(Vector2::new(4.0, 1.0), xformed.origin.to_vector());
+        assert_vec_approx(Vector2::new(4.0, 2.0), xformed.size.to_vector());
+
+        let back = transform.xform_inv_rect(xformed);
+        assert_vec_approx(rect.origin.to_vector(), back.origin.to_vector());
+        assert_vec_approx(rect.size.to_vector(), back.size.to_vector());
+    }
+}
@@ -216,12 +216,41 @@ pub type ScriptConstructorFn =
 pub type ScriptDestructorFn =
     unsafe extern "C" fn(*mut sys::godot_object, *mut libc::c_void, *mut libc::c_void) -> ();
 
+/// RPC mode of an exported method, or rset mode of a property.
+///
+/// See the high-level multiplayer documentation of the engine for the meaning of each mode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum RpcMode {
     Disabled,
     Remote,
-    Sync,
-    Mater,
-    Slave,
+    Master,
+    Puppet,
+    RemoteSync,
+    MasterSync,
+    PuppetSync,
+}
+
+#[allow(non_upper_case_globals)]
+impl RpcMode {
+    #[deprecated(note = "renamed to `RpcMode::RemoteSync` in the engine's 3.2 API")]
+    pub const Sync: RpcMode = RpcMode::RemoteSync;
+    #[deprecated(note = "use `RpcMode::Master` instead")]
+    pub const Mater: RpcMode = RpcMode::Master;
+    #[deprecated(note = "renamed to `RpcMode::Puppet` in the engine's 3.2 API")]
+    pub const Slave: RpcMode = RpcMode::Puppet;
+
+    #[doc(hidden)]
+    pub fn to_sys(self) -> sys::godot_method_rpc_mode {
+        match self {
+            RpcMode::Disabled => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_DISABLED,
+            RpcMode::Remote => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_REMOTE,
+            RpcMode::Master => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_MASTER,
+            RpcMode::Puppet => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_PUPPET,
+            RpcMode::RemoteSync => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_REMOTESYNC,
+            RpcMode::MasterSync => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_MASTERSYNC,
+            RpcMode::PuppetSync => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_PUPPETSYNC,
+        }
+    }
 }
 
 pub struct ScriptMethodAttributes {
@@ -256,7 +285,7 @@ impl<C: NativeClass> ClassBuilder<C> {
     pub fn add_method_advanced(&self, method: ScriptMethod) {
         let method_name = CString::new(method.name).unwrap();
         let attr = sys::godot_method_attributes {
-            rpc_type: sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_DISABLED,
+            rpc_type: method.attributes.rpc_mode.to_sys(),
         };
 
         let method_desc = sys::godot_instance_method {
@@ -277,12 +306,15 @@ impl<C: NativeClass> ClassBuilder<C> {
     }
 
     pub fn add_method(&self, name: &str, method: ScriptMethodFn) {
+        self.add_method_with_rpc_mode(name, method, RpcMode::Disabled);
+    }
+
+    /// Adds a method that can be called remotely with `rpc`, according to `rpc_mode`.
+    pub fn add_method_with_rpc_mode(&self, name: &str, method: ScriptMethodFn, rpc_mode: RpcMode) {
         self.add_method_advanced(ScriptMethod {
             name: name,
             method_ptr: Some(method),
-            attributes: ScriptMethodAttributes {
-                rpc_mode: RpcMode::Disabled,
-            },
+            attributes: ScriptMethodAttributes { rpc_mode },
             method_data: ptr::null_mut(),
             free_func: None,
         });
@@ -347,6 +379,93 @@ impl<C: NativeClass> ClassBuilder<C> {
             );
         }
     }
+
+    /// Sets the names and types of the arguments of a method, which are shown by the editor.
+    /// The method must already be registered.
+    pub fn set_method_argument_information(&self, method: &str, args: &[MethodArgument]) {
+        let method_name = CString::new(method).unwrap();
+        let owned = args
+            .iter()
+            .map(|arg| {
+                let arg_name = GodotString::from_str(arg.name);
+                let hint_string = arg.export_info.hint_string.clone();
+                (arg, arg_name, hint_string)
+            })
+            .collect::<Vec<_>>();
+        let args = owned
+            .iter()
+            .map(|(arg, arg_name, hint_string)| sys::godot_method_arg {
+                name: arg_name.to_sys(),
+                type_: arg.export_info.variant_type as sys::godot_variant_type,
+                hint: arg.export_info.hint_kind,
+                hint_string: hint_string.to_sys(),
+            })
+            .collect::<Vec<_>>();
+
+        unsafe {
+            (get_api().godot_nativescript_set_method_argument_information)(
+                self.init_handle,
+                self.class_name.as_ptr(),
+                method_name.as_ptr(),
+                args.len() as i32,
+                args.as_ptr(),
+            );
+        }
+    }
+
+    /// Sets the documentation of the class, which is shown in the help of the editor.
+    pub fn set_class_documentation(&self, documentation: &str) {
+        let documentation = GodotString::from_str(documentation);
+        unsafe {
+            (get_api().godot_nativescript_set_class_documentation)(
+                self.init_handle,
+                self.class_name.as_ptr(),
+                documentation.to_sys(),
+            );
+        }
+    }
+
+    /// Sets the documentation of a method. The method must already be registered.
+    pub fn set_method_documentation(&self, method: &str, documentation: &str) {
+        let method_name = CString::new(method).unwrap();
+        let documentation = GodotString::from_str(documentation);
+        unsafe {
+            (get_api().godot_nativescript_set_method_documentation)(
+                self.init_handle,
+                self.class_name.as_ptr(),
+                method_name.as_ptr(),
+                documentation.to_sys(),
+            );
+        }
+    }
+
+    /// Sets the documentation of a property. The property must already be registered.
+    pub fn set_property_documentation(&self, path: &str, documentation: &str) {
+        let path = CString::new(path).unwrap();
+        let documentation = GodotString::from_str(documentation);
+        unsafe {
+            (get_api().godot_nativescript_set_property_documentation)(
+                self.init_handle,
+                self.class_name.as_ptr(),
+                path.as_ptr(),
+                documentation.to_sys(),
+            );
+        }
+    }
+
+    /// Sets the documentation of a signal. The signal must already be registered.
+    pub fn set_signal_documentation(&self, signal: &str, documentation: &str) {
+        let signal_name = CString::new(signal).unwrap();
+        let documentation = GodotString::from_str(documentation);
+        unsafe {
+            (get_api().godot_nativescript_set_signal_documentation)(
+                self.init_handle,
+                self.class_name.as_ptr(),
+                signal_name.as_ptr(),
+                documentation.to_sys(),
+            );
+        }
+    }
 }
 
 pub struct Signal<'l> {
@@ -360,3 +479,41 @@ pub struct SignalArgument<'l> {
     pub export_info: ExportInfo,
     pub usage: PropertyUsage,
 }
+
+pub struct MethodArgument<'l> {
+    pub name: &'l str,
+    pub export_info: ExportInfo,
+}
+
+/// Export info of the type of a method argument, used by the `methods` macro.
+///
+/// Argument types that implement `Export` use their own export info, through
+/// `TypedArgument`. Other types are reported as untyped, through `UntypedArgument`:
+///
+/// ```ignore
+/// (&ArgumentType::<T>(PhantomData)).argument_export_info()
+/// ```
+#[doc(hidden)]
+pub struct ArgumentType<T>(pub PhantomData<T>);
+
+#[doc(hidden)]
+pub trait TypedArgument {
+    fn argument_export_info(&self) -> ExportInfo;
+}
+
+impl<T: Export> TypedArgument for ArgumentType<T> {
+    fn argument_export_info(&self) -> ExportInfo {
+        T::export_info(None)
+    }
+}
+
+#[doc(hidden)]
+pub trait UntypedArgument {
+    fn argument_export_info(&self) -> ExportInfo;
+}
+
+impl<T> UntypedArgument for &ArgumentType<T> {
+    fn argument_export_info(&self) -> ExportInfo {
+        ExportInfo::new(VariantType::Nil)
+    }
+}
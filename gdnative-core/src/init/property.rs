@@ -9,7 +9,7 @@ use crate::NativeClass;
 use crate::ToVariant;
 use crate::VariantType;
 
-use super::ClassBuilder;
+use super::{ClassBuilder, RpcMode};
 
 mod accessor;
 pub mod hint;
@@ -68,6 +68,7 @@ pub struct PropertyBuilder<'a, C, T: Export, S = InvalidSetter<'a>, G = InvalidG
     default: Option<T>,
     hint: Option<T::Hint>,
     usage: Usage,
+    rset_mode: RpcMode,
     class_builder: &'a ClassBuilder<C>,
 }
 
@@ -85,6 +86,7 @@ where
             default: None,
             hint: None,
             usage: Usage::DEFAULT,
+            rset_mode: RpcMode::Disabled,
             class_builder,
         }
     }
@@ -107,7 +109,7 @@ where
         let default = self.default.to_variant();
 
         let mut attr = sys::godot_property_attributes {
-            rset_type: sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_DISABLED, // TODO:
+            rset_type: self.rset_mode.to_sys(),
             type_: variant_type as sys::godot_int,
             hint: hint_kind,
             hint_string: hint_string.to_sys(),
@@ -148,6 +150,7 @@ where
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            rset_mode: self.rset_mode,
             class_builder: self.class_builder,
         }
     }
@@ -168,6 +171,7 @@ where
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            rset_mode: self.rset_mode,
             class_builder: self.class_builder,
         }
     }
@@ -188,6 +192,7 @@ where
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            rset_mode: self.rset_mode,
             class_builder: self.class_builder,
         }
     }
@@ -208,6 +213,7 @@ where
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            rset_mode: self.rset_mode,
             class_builder: self.class_builder,
         }
     }
@@ -228,6 +234,7 @@ where
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            rset_mode: self.rset_mode,
             class_builder: self.class_builder,
         }
     }
@@ -248,6 +255,7 @@ where
             default: self.default,
             hint: self.hint,
             usage: self.usage,
+            rset_mode: self.rset_mode,
             class_builder: self.class_builder,
         }
     }
@@ -270,6 +278,13 @@ where
         self.usage = usage;
         self
     }
+
+    /// Sets the rset mode, which controls whether the property can be set remotely with
+    /// `rset`. Defaults to `RpcMode::Disabled`.
+    pub fn with_rset_mode(mut self, rset_mode: RpcMode) -> Self {
+        self.rset_mode = rset_mode;
+        self
+    }
 }
 
 bitflags! {
@@ -395,7 +410,7 @@ mod impl_export {
     impl_export_for_core_type_without_hint!(VariantArray);
     impl_export_for_core_type_without_hint!(ByteArray);
     impl_export_for_core_type_without_hint!(Int32Array);
-    impl_export_for_core_type_without_hint!(Float32Array);
+    impl_export_for_core_type_without_hint!(RealArray: Float32Array);
     impl_export_for_core_type_without_hint!(StringArray);
     impl_export_for_core_type_without_hint!(Vector2Array);
     impl_export_for_core_type_without_hint!(Vector3Array);
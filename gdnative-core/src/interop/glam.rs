@@ -0,0 +1,212 @@
+use super::*;
+use crate::{Color, Quat};
+
+use ::glam::Vec4;
+#[cfg(not(feature = "double-precision"))]
+use ::glam::{Affine2, Affine3A as Affine3, Mat3, Quat as GlamQuat, Vec2, Vec3};
+#[cfg(feature = "double-precision")]
+use ::glam::{
+    DAffine2 as Affine2, DAffine3 as Affine3, DMat3 as Mat3, DQuat as GlamQuat, DVec2 as Vec2,
+    DVec3 as Vec3,
+};
+
+/// Conversions between core types and their `glam` counterparts.
+///
+/// | Core type     | `glam` type                      |
+/// |---------------|----------------------------------|
+/// | `Vector2`     | `Vec2` (`DVec2`)                 |
+/// | `Vector3`     | `Vec3` (`DVec3`)                 |
+/// | `Quat`        | `Quat` (`DQuat`)                 |
+/// | `Basis`       | `Mat3` (`DMat3`)                 |
+/// | `Transform`   | `Affine3A` (`DAffine3`)          |
+/// | `Transform2D` | `Affine2` (`DAffine2`)           |
+/// | `Color`       | `Vec4`, as `(r, g, b, a)`        |
+///
+/// The types in parentheses are used with the `double-precision` feature.
+pub trait GlamConv: Sized {
+    /// The corresponding `glam` type.
+    type Glam;
+
+    /// Converts this value to its `glam` counterpart.
+    fn to_glam(self) -> Self::Glam;
+
+    /// Converts a `glam` value to its core counterpart.
+    fn from_glam(value: Self::Glam) -> Self;
+}
+
+impl GlamConv for Vector2 {
+    type Glam = Vec2;
+
+    fn to_glam(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    fn from_glam(value: Vec2) -> Self {
+        Vector2::new(value.x, value.y)
+    }
+}
+
+impl GlamConv for Vector3 {
+    type Glam = Vec3;
+
+    fn to_glam(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    fn from_glam(value: Vec3) -> Self {
+        Vector3::new(value.x, value.y, value.z)
+    }
+}
+
+impl GlamConv for Quat {
+    type Glam = GlamQuat;
+
+    fn to_glam(self) -> GlamQuat {
+        GlamQuat::from_xyzw(self.i, self.j, self.k, self.r)
+    }
+
+    fn from_glam(value: GlamQuat) -> Self {
+        let [x, y, z, w]: [real; 4] = value.into();
+        Quat::quaternion(x, y, z, w)
+    }
+}
+
+impl GlamConv for Basis {
+    type Glam = Mat3;
+
+    fn to_glam(self) -> Mat3 {
+        Mat3::from_cols_array_2d(&basis_to_cols(&self))
+    }
+
+    fn from_glam(value: Mat3) -> Self {
+        basis_from_cols(value.to_cols_array_2d())
+    }
+}
+
+impl GlamConv for Transform {
+    type Glam = Affine3;
+
+    fn to_glam(self) -> Affine3 {
+        Affine3::from_cols_array_2d(&transform_to_cols(&self))
+    }
+
+    fn from_glam(value: Affine3) -> Self {
+        transform_from_cols(value.to_cols_array_2d())
+    }
+}
+
+impl GlamConv for Transform2D {
+    type Glam = Affine2;
+
+    fn to_glam(self) -> Affine2 {
+        Affine2::from_cols_array_2d(&transform2d_to_cols(&self))
+    }
+
+    fn from_glam(value: Affine2) -> Self {
+        transform2d_from_cols(value.to_cols_array_2d())
+    }
+}
+
+impl GlamConv for Color {
+    type Glam = Vec4;
+
+    fn to_glam(self) -> Vec4 {
+        Vec4::new(self.r, self.g, self.b, self.a)
+    }
+
+    fn from_glam(value: Vec4) -> Self {
+        Color::rgba(value.x, value.y, value.z, value.w)
+    }
+}
+
+macro_rules! impl_from_glam {
+    ($($Core:ident <=> $Glam:ident,)*) => {
+        $(
+            impl From<$Glam> for $Core {
+                fn from(value: $Glam) -> Self {
+                    $Core::from_glam(value)
+                }
+            }
+
+            impl From<$Core> for $Glam {
+                fn from(value: $Core) -> Self {
+                    value.to_glam()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_glam! {
+    Basis <=> Mat3,
+    Transform <=> Affine3,
+    Color <=> Vec4,
+}
+
+impl_variant_for_foreign!(GlamConv::to_glam / from_glam {
+    Vec2 => Vector2,
+    Vec3 => Vector3,
+    GlamQuat => Quat,
+    Mat3 => Basis,
+    Affine3 => Transform,
+    Affine2 => Transform2D,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometry_round_trips() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(Vec3::new(1.0, 2.0, 3.0), v.to_glam());
+        assert_eq!(v, Vector3::from_glam(v.to_glam()));
+        assert_eq!(Vec2::new(4.0, 5.0), Vector2::new(4.0, 5.0).to_glam());
+
+        let quat = Quat::quaternion(0.5, -0.5, 0.5, 0.5);
+        assert_eq!(GlamQuat::from_xyzw(0.5, -0.5, 0.5, 0.5), quat.to_glam());
+        assert_eq!(quat, Quat::from_glam(quat.to_glam()));
+    }
+
+    #[test]
+    fn matrices_use_the_axes_as_columns() {
+        let basis = Basis::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 0.5);
+        let mat = Mat3::from(basis);
+        assert_eq!(basis.x().to_glam(), mat.x_axis);
+        assert_eq!(basis.z().to_glam(), mat.z_axis);
+        assert_eq!(basis, Basis::from(mat));
+
+        let v = Vector3::new(1.0, -2.0, 3.0);
+        assert!(basis
+            .xform(v)
+            .to_glam()
+            .abs_diff_eq(mat * v.to_glam(), 0.0001));
+
+        let transform = Transform {
+            basis,
+            origin: Vector3::new(7.0, 8.0, 9.0),
+        };
+        let affine = transform.to_glam();
+        assert!(transform
+            .xform(v)
+            .to_glam()
+            .abs_diff_eq(affine.transform_point3(v.to_glam()), 0.0001));
+        assert_eq!(transform, Transform::from(affine));
+
+        let transform2d = Transform2D::from_rotation_origin(0.5, Vector2::new(-1.0, 2.0));
+        let affine = transform2d.to_glam();
+        let p = Vector2::new(3.0, 4.0);
+        assert!(transform2d
+            .xform(p)
+            .to_glam()
+            .abs_diff_eq(affine.transform_point2(p.to_glam()), 0.0001));
+        assert_eq!(transform2d, Transform2D::from_glam(affine));
+    }
+
+    #[test]
+    fn colors_are_rgba_vectors() {
+        let color = Color::rgba(0.1, 0.2, 0.3, 0.4);
+        assert_eq!(Vec4::new(0.1, 0.2, 0.3, 0.4), Vec4::from(color));
+        assert_eq!(color, Color::from(Vec4::from(color)));
+    }
+}
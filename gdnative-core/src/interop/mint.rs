@@ -0,0 +1,204 @@
+use super::*;
+use crate::{Color, Quat};
+
+use ::mint::{
+    ColumnMatrix2x3, ColumnMatrix3, ColumnMatrix3x4, Quaternion, Vector2 as MintVector2,
+    Vector3 as MintVector3, Vector4 as MintVector4,
+};
+
+/// Conversions between core types and their `mint` counterparts.
+///
+/// | Core type     | `mint` type                       |
+/// |---------------|-----------------------------------|
+/// | `Vector2`     | `Vector2<real>`                   |
+/// | `Vector3`     | `Vector3<real>`                   |
+/// | `Quat`        | `Quaternion<real>`                |
+/// | `Basis`       | `ColumnMatrix3<real>`             |
+/// | `Transform`   | `ColumnMatrix3x4<real>`           |
+/// | `Transform2D` | `ColumnMatrix2x3<real>`           |
+/// | `Color`       | `Vector4<f32>`, as `(r, g, b, a)` |
+pub trait MintConv: Sized {
+    /// The corresponding `mint` type.
+    type Mint;
+
+    /// Converts this value to its `mint` counterpart.
+    fn to_mint(self) -> Self::Mint;
+
+    /// Converts a `mint` value to its core counterpart.
+    fn from_mint(value: Self::Mint) -> Self;
+}
+
+impl MintConv for Vector2 {
+    type Mint = MintVector2<real>;
+
+    fn to_mint(self) -> MintVector2<real> {
+        MintVector2::from(self.to_array())
+    }
+
+    fn from_mint(value: MintVector2<real>) -> Self {
+        Vector2::new(value.x, value.y)
+    }
+}
+
+impl MintConv for Vector3 {
+    type Mint = MintVector3<real>;
+
+    fn to_mint(self) -> MintVector3<real> {
+        MintVector3::from(self.to_array())
+    }
+
+    fn from_mint(value: MintVector3<real>) -> Self {
+        Vector3::new(value.x, value.y, value.z)
+    }
+}
+
+impl MintConv for Quat {
+    type Mint = Quaternion<real>;
+
+    fn to_mint(self) -> Quaternion<real> {
+        Quaternion {
+            v: MintVector3::from([self.i, self.j, self.k]),
+            s: self.r,
+        }
+    }
+
+    fn from_mint(value: Quaternion<real>) -> Self {
+        Quat::quaternion(value.v.x, value.v.y, value.v.z, value.s)
+    }
+}
+
+impl MintConv for Basis {
+    type Mint = ColumnMatrix3<real>;
+
+    fn to_mint(self) -> ColumnMatrix3<real> {
+        ColumnMatrix3::from(basis_to_cols(&self))
+    }
+
+    fn from_mint(value: ColumnMatrix3<real>) -> Self {
+        basis_from_cols(value.into())
+    }
+}
+
+impl MintConv for Transform {
+    type Mint = ColumnMatrix3x4<real>;
+
+    fn to_mint(self) -> ColumnMatrix3x4<real> {
+        ColumnMatrix3x4::from(transform_to_cols(&self))
+    }
+
+    fn from_mint(value: ColumnMatrix3x4<real>) -> Self {
+        transform_from_cols(value.into())
+    }
+}
+
+impl MintConv for Transform2D {
+    type Mint = ColumnMatrix2x3<real>;
+
+    fn to_mint(self) -> ColumnMatrix2x3<real> {
+        ColumnMatrix2x3::from(transform2d_to_cols(&self))
+    }
+
+    fn from_mint(value: ColumnMatrix2x3<real>) -> Self {
+        transform2d_from_cols(value.into())
+    }
+}
+
+impl MintConv for Color {
+    type Mint = MintVector4<f32>;
+
+    fn to_mint(self) -> MintVector4<f32> {
+        MintVector4::from([self.r, self.g, self.b, self.a])
+    }
+
+    fn from_mint(value: MintVector4<f32>) -> Self {
+        Color::rgba(value.x, value.y, value.z, value.w)
+    }
+}
+
+macro_rules! impl_from_mint {
+    ($($Core:ident <=> $Mint:ty,)*) => {
+        $(
+            impl From<$Mint> for $Core {
+                fn from(value: $Mint) -> Self {
+                    $Core::from_mint(value)
+                }
+            }
+
+            impl From<$Core> for $Mint {
+                fn from(value: $Core) -> Self {
+                    value.to_mint()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_mint! {
+    Basis <=> ColumnMatrix3<real>,
+    Transform <=> ColumnMatrix3x4<real>,
+    Color <=> MintVector4<f32>,
+}
+
+impl_variant_for_foreign!(MintConv::to_mint / from_mint {
+    MintVector2<real> => Vector2,
+    MintVector3<real> => Vector3,
+    Quaternion<real> => Quat,
+    ColumnMatrix3<real> => Basis,
+    ColumnMatrix3x4<real> => Transform,
+    ColumnMatrix2x3<real> => Transform2D,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometry_round_trips() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(MintVector3::from([1.0, 2.0, 3.0]), v.to_mint());
+        assert_eq!(v, Vector3::from_mint(v.to_mint()));
+        assert_eq!(
+            MintVector2::from([4.0, 5.0]),
+            Vector2::new(4.0, 5.0).to_mint()
+        );
+
+        let quat = Quat::quaternion(0.5, -0.5, 0.5, 0.5);
+        let mint_quat = quat.to_mint();
+        assert_eq!(MintVector3::from([0.5, -0.5, 0.5]), mint_quat.v);
+        assert_eq!(0.5, mint_quat.s);
+        assert_eq!(quat, Quat::from_mint(mint_quat));
+    }
+
+    #[test]
+    fn matrices_use_the_axes_as_columns() {
+        let basis = Basis::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 0.5);
+        let mat = ColumnMatrix3::from(basis);
+        assert_eq!(basis.x().to_mint(), mat.x);
+        assert_eq!(basis.z().to_mint(), mat.z);
+        assert_eq!(basis, Basis::from(mat));
+
+        let transform = Transform {
+            basis,
+            origin: Vector3::new(7.0, 8.0, 9.0),
+        };
+        let mat = ColumnMatrix3x4::from(transform);
+        assert_eq!(transform.origin.to_mint(), mat.w);
+        assert_eq!(transform, Transform::from(mat));
+
+        let transform2d = Transform2D::from_rotation_origin(0.5, Vector2::new(-1.0, 2.0));
+        let mat = transform2d.to_mint();
+        assert_eq!(transform2d.x_axis().to_mint(), mat.x);
+        assert_eq!(transform2d.origin().to_mint(), mat.z);
+        assert_eq!(transform2d, Transform2D::from_mint(mat));
+    }
+
+    #[test]
+    fn colors_are_rgba_vectors() {
+        let color = Color::rgba(0.1, 0.2, 0.3, 0.4);
+        assert_eq!(
+            MintVector4::from([0.1, 0.2, 0.3, 0.4]),
+            MintVector4::from(color)
+        );
+        assert_eq!(color, Color::from(MintVector4::from(color)));
+    }
+}
@@ -0,0 +1,111 @@
+//! Conversions between the core types and the math types of other crates.
+//!
+//! Each supported crate is behind a feature of the same name:
+//!
+//! - `glam`: `GlamConv`, for `glam`'s vectors, quaternions, matrices and affine transforms.
+//! - `nalgebra`: `NalgebraConv`, for `nalgebra`'s vectors, quaternions, matrices and affine
+//!   transforms.
+//! - `mint`: `MintConv`, for `mint`'s vectors, quaternions and column matrices. This also
+//!   enables `euclid`'s own `mint` conversions for `Vector2`, `Vector3` and `Transform2D`.
+//!
+//! `Vector2`, `Vector3`, `Quat` and `Transform2D` are aliases of `euclid` types, so coherence
+//! rules don't allow them to implement `From` for the types of another crate. They are
+//! converted with the methods of the conversion traits instead. `Basis`, `Transform` and
+//! `Color` implement `From` in both directions as well.
+//!
+//! The foreign counterparts of the geometry types also implement `ToVariant`, `FromVariant`
+//! and `Export`, so they can be used directly as arguments of exported methods and as
+//! properties. This isn't the case for the 4D vectors used for `Color`, which could as well
+//! hold a quaternion or a plane.
+//!
+//! With the `double-precision` feature, the counterparts of the geometry types use `f64`.
+//! Colors are always converted from and to 4D vectors of `f32`.
+
+use crate::{real, Basis, Transform, Transform2D, Transform2DGodot, Vector2, Vector3};
+
+/// Implements `ToVariant`, `FromVariant` and `Export` for foreign types, through the core
+/// type they are converted from and to with a conversion trait.
+macro_rules! impl_variant_for_foreign {
+    ($Conv:ident :: $to:ident / $from:ident { $($Foreign:ty => $Core:ident,)* }) => {
+        $(
+            impl crate::ToVariant for $Foreign {
+                fn to_variant(&self) -> crate::Variant {
+                    crate::ToVariant::to_variant(&<$Core as $Conv>::$from(*self))
+                }
+            }
+
+            impl crate::FromVariant for $Foreign {
+                fn from_variant(
+                    variant: &crate::Variant,
+                ) -> Result<Self, crate::FromVariantError> {
+                    <$Core as crate::FromVariant>::from_variant(variant).map($Conv::$to)
+                }
+            }
+
+            impl crate::init::Export for $Foreign {
+                type Hint = ();
+                fn export_info(_hint: Option<Self::Hint>) -> crate::init::ExportInfo {
+                    crate::init::ExportInfo::new(crate::VariantType::$Core)
+                }
+            }
+        )*
+    };
+}
+
+// The submodules are declared after the macro, so that they can use it.
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "mint")]
+mod mint;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+
+#[cfg(feature = "glam")]
+pub use self::glam::GlamConv;
+#[cfg(feature = "mint")]
+pub use self::mint::MintConv;
+#[cfg(feature = "nalgebra")]
+pub use self::nalgebra::NalgebraConv;
+
+/// Returns the columns of a basis, i.e. its X, Y and Z axes.
+fn basis_to_cols(basis: &Basis) -> [[real; 3]; 3] {
+    [
+        basis.x().to_array(),
+        basis.y().to_array(),
+        basis.z().to_array(),
+    ]
+}
+
+fn basis_from_cols([x, y, z]: [[real; 3]; 3]) -> Basis {
+    let mut basis = Basis::identity();
+    basis.set_x(Vector3::from(x));
+    basis.set_y(Vector3::from(y));
+    basis.set_z(Vector3::from(z));
+    basis
+}
+
+/// Returns the columns of a 3x4 matrix: the axes of the basis, then the origin.
+fn transform_to_cols(transform: &Transform) -> [[real; 3]; 4] {
+    let [x, y, z] = basis_to_cols(&transform.basis);
+    [x, y, z, transform.origin.to_array()]
+}
+
+fn transform_from_cols([x, y, z, origin]: [[real; 3]; 4]) -> Transform {
+    Transform {
+        basis: basis_from_cols([x, y, z]),
+        origin: Vector3::from(origin),
+    }
+}
+
+/// Returns the columns of a 2x3 matrix: the X and Y axes, then the origin.
+fn transform2d_to_cols(transform: &Transform2D) -> [[real; 2]; 3] {
+    [
+        transform.x_axis().to_array(),
+        transform.y_axis().to_array(),
+        transform.origin().to_array(),
+    ]
+}
+
+fn transform2d_from_cols([x, y, origin]: [[real; 2]; 3]) -> Transform2D {
+    Transform2D::from_axis_origin(Vector2::from(x), Vector2::from(y), Vector2::from(origin))
+}
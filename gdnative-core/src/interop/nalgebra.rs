@@ -0,0 +1,244 @@
+use super::*;
+use crate::{Color, Quat};
+
+use ::nalgebra::{
+    Affine2, Affine3, Matrix3, Matrix4, Quaternion, UnitQuaternion, Vector2 as NaVector2,
+    Vector3 as NaVector3, Vector4 as NaVector4,
+};
+
+/// Conversions between core types and their `nalgebra` counterparts.
+///
+/// | Core type     | `nalgebra` type                   |
+/// |---------------|-----------------------------------|
+/// | `Vector2`     | `Vector2<real>`                   |
+/// | `Vector3`     | `Vector3<real>`                   |
+/// | `Quat`        | `Quaternion<real>`                |
+/// | `Basis`       | `Matrix3<real>`                   |
+/// | `Transform`   | `Affine3<real>`                   |
+/// | `Transform2D` | `Affine2<real>`                   |
+/// | `Color`       | `Vector4<f32>`, as `(r, g, b, a)` |
+///
+/// `UnitQuaternion<real>` also implements `ToVariant`, `FromVariant` and `Export`. It is
+/// normalized when converted from a variant.
+pub trait NalgebraConv: Sized {
+    /// The corresponding `nalgebra` type.
+    type Nalgebra;
+
+    /// Converts this value to its `nalgebra` counterpart.
+    fn to_nalgebra(self) -> Self::Nalgebra;
+
+    /// Converts a `nalgebra` value to its core counterpart.
+    fn from_nalgebra(value: Self::Nalgebra) -> Self;
+}
+
+impl NalgebraConv for Vector2 {
+    type Nalgebra = NaVector2<real>;
+
+    fn to_nalgebra(self) -> NaVector2<real> {
+        NaVector2::new(self.x, self.y)
+    }
+
+    fn from_nalgebra(value: NaVector2<real>) -> Self {
+        Vector2::new(value.x, value.y)
+    }
+}
+
+impl NalgebraConv for Vector3 {
+    type Nalgebra = NaVector3<real>;
+
+    fn to_nalgebra(self) -> NaVector3<real> {
+        NaVector3::new(self.x, self.y, self.z)
+    }
+
+    fn from_nalgebra(value: NaVector3<real>) -> Self {
+        Vector3::new(value.x, value.y, value.z)
+    }
+}
+
+impl NalgebraConv for Quat {
+    type Nalgebra = Quaternion<real>;
+
+    fn to_nalgebra(self) -> Quaternion<real> {
+        Quaternion::new(self.r, self.i, self.j, self.k)
+    }
+
+    fn from_nalgebra(value: Quaternion<real>) -> Self {
+        Quat::quaternion(value.i, value.j, value.k, value.w)
+    }
+}
+
+impl NalgebraConv for Basis {
+    type Nalgebra = Matrix3<real>;
+
+    fn to_nalgebra(self) -> Matrix3<real> {
+        Matrix3::from(basis_to_cols(&self))
+    }
+
+    fn from_nalgebra(value: Matrix3<real>) -> Self {
+        basis_from_cols(*value.as_ref())
+    }
+}
+
+impl NalgebraConv for Transform {
+    type Nalgebra = Affine3<real>;
+
+    fn to_nalgebra(self) -> Affine3<real> {
+        let [x, y, z, origin] = transform_to_cols(&self);
+        Affine3::from_matrix_unchecked(Matrix4::from([
+            [x[0], x[1], x[2], 0.0],
+            [y[0], y[1], y[2], 0.0],
+            [z[0], z[1], z[2], 0.0],
+            [origin[0], origin[1], origin[2], 1.0],
+        ]))
+    }
+
+    fn from_nalgebra(value: Affine3<real>) -> Self {
+        let cols: [[real; 4]; 4] = *value.matrix().as_ref();
+        let [x, y, z, origin] = cols;
+        transform_from_cols([
+            [x[0], x[1], x[2]],
+            [y[0], y[1], y[2]],
+            [z[0], z[1], z[2]],
+            [origin[0], origin[1], origin[2]],
+        ])
+    }
+}
+
+impl NalgebraConv for Transform2D {
+    type Nalgebra = Affine2<real>;
+
+    fn to_nalgebra(self) -> Affine2<real> {
+        let [x, y, origin] = transform2d_to_cols(&self);
+        Affine2::from_matrix_unchecked(Matrix3::from([
+            [x[0], x[1], 0.0],
+            [y[0], y[1], 0.0],
+            [origin[0], origin[1], 1.0],
+        ]))
+    }
+
+    fn from_nalgebra(value: Affine2<real>) -> Self {
+        let cols: [[real; 3]; 3] = *value.matrix().as_ref();
+        let [x, y, origin] = cols;
+        transform2d_from_cols([[x[0], x[1]], [y[0], y[1]], [origin[0], origin[1]]])
+    }
+}
+
+impl NalgebraConv for Color {
+    type Nalgebra = NaVector4<f32>;
+
+    fn to_nalgebra(self) -> NaVector4<f32> {
+        NaVector4::new(self.r, self.g, self.b, self.a)
+    }
+
+    fn from_nalgebra(value: NaVector4<f32>) -> Self {
+        Color::rgba(value.x, value.y, value.z, value.w)
+    }
+}
+
+macro_rules! impl_from_nalgebra {
+    ($($Core:ident <=> $Nalgebra:ty,)*) => {
+        $(
+            impl From<$Nalgebra> for $Core {
+                fn from(value: $Nalgebra) -> Self {
+                    $Core::from_nalgebra(value)
+                }
+            }
+
+            impl From<$Core> for $Nalgebra {
+                fn from(value: $Core) -> Self {
+                    value.to_nalgebra()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_nalgebra! {
+    Basis <=> Matrix3<real>,
+    Transform <=> Affine3<real>,
+    Color <=> NaVector4<f32>,
+}
+
+impl_variant_for_foreign!(NalgebraConv::to_nalgebra / from_nalgebra {
+    NaVector2<real> => Vector2,
+    NaVector3<real> => Vector3,
+    Quaternion<real> => Quat,
+    Matrix3<real> => Basis,
+    Affine3<real> => Transform,
+    Affine2<real> => Transform2D,
+});
+
+impl crate::ToVariant for UnitQuaternion<real> {
+    fn to_variant(&self) -> crate::Variant {
+        crate::ToVariant::to_variant(&self.into_inner())
+    }
+}
+
+impl crate::FromVariant for UnitQuaternion<real> {
+    fn from_variant(variant: &crate::Variant) -> Result<Self, crate::FromVariantError> {
+        <Quaternion<real> as crate::FromVariant>::from_variant(variant)
+            .map(UnitQuaternion::from_quaternion)
+    }
+}
+
+impl crate::init::Export for UnitQuaternion<real> {
+    type Hint = ();
+    fn export_info(_hint: Option<Self::Hint>) -> crate::init::ExportInfo {
+        crate::init::ExportInfo::new(crate::VariantType::Quat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometry_round_trips() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(NaVector3::new(1.0, 2.0, 3.0), v.to_nalgebra());
+        assert_eq!(v, Vector3::from_nalgebra(v.to_nalgebra()));
+        assert_eq!(
+            NaVector2::new(4.0, 5.0),
+            Vector2::new(4.0, 5.0).to_nalgebra()
+        );
+
+        let quat = Quat::quaternion(0.5, -0.5, 0.5, 0.5);
+        assert_eq!(Quaternion::new(0.5, 0.5, -0.5, 0.5), quat.to_nalgebra());
+        assert_eq!(quat, Quat::from_nalgebra(quat.to_nalgebra()));
+    }
+
+    #[test]
+    fn matrices_use_the_axes_as_columns() {
+        let basis = Basis::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 0.5);
+        let mat = Matrix3::from(basis);
+        assert_eq!(basis.x().to_nalgebra(), mat.column(0).into_owned());
+        assert_eq!(basis.z().to_nalgebra(), mat.column(2).into_owned());
+        assert_eq!(basis, Basis::from(mat));
+
+        let v = Vector3::new(1.0, -2.0, 3.0);
+        assert!((basis.xform(v).to_nalgebra() - mat * v.to_nalgebra()).norm() < 0.0001);
+
+        let transform = Transform {
+            basis,
+            origin: Vector3::new(7.0, 8.0, 9.0),
+        };
+        let affine = transform.to_nalgebra();
+        let point = affine * ::nalgebra::Point3::from(v.to_nalgebra());
+        assert!((transform.xform(v).to_nalgebra() - point.coords).norm() < 0.0001);
+        assert_eq!(transform, Transform::from(affine));
+
+        let transform2d = Transform2D::from_rotation_origin(0.5, Vector2::new(-1.0, 2.0));
+        let affine = transform2d.to_nalgebra();
+        let p = Vector2::new(3.0, 4.0);
+        let point = affine * ::nalgebra::Point2::from(p.to_nalgebra());
+        assert!((transform2d.xform(p).to_nalgebra() - point.coords).norm() < 0.0001);
+        assert_eq!(transform2d, Transform2D::from_nalgebra(affine));
+    }
+
+    #[test]
+    fn colors_are_rgba_vectors() {
+        let color = Color::rgba(0.1, 0.2, 0.3, 0.4);
+        assert_eq!(NaVector4::new(0.1, 0.2, 0.3, 0.4), NaVector4::from(color));
+        assert_eq!(color, Color::from(NaVector4::from(color)));
+    }
+}
@@ -32,63 +32,71 @@ pub extern crate libc;
 extern crate bitflags;
 extern crate parking_lot;
 
+mod macros;
+
 pub mod geom;
 
-mod macros;
 #[macro_use]
 mod class;
 pub mod access;
-mod byte_array;
+#[cfg(feature = "async")]
+pub mod r#async;
+mod closure;
 mod color;
-mod color_array;
 mod dictionary;
-mod float32_array;
 mod free_on_drop;
 mod generated;
 pub mod init;
-mod int32_array;
+#[cfg(any(feature = "glam", feature = "mint", feature = "nalgebra"))]
+pub mod interop;
+pub mod marshal;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+mod name_cache;
 mod node_path;
 #[doc(hidden)]
 pub mod object;
 mod point2;
+mod pool_array;
 mod rid;
+#[cfg(feature = "serde")]
+mod serialize;
 mod string;
-mod string_array;
 mod type_tag;
+mod typed_array;
+mod typed_dictionary;
 pub mod user_data;
 mod variant;
 mod variant_array;
+mod variant_value;
 mod vector2;
-mod vector2_array;
 mod vector3;
-mod vector3_array;
 
-pub use crate::byte_array::*;
 pub use crate::class::*;
+pub use crate::closure::*;
 pub use crate::color::*;
-pub use crate::color_array::*;
 pub use crate::dictionary::*;
-pub use crate::float32_array::*;
 pub use crate::free_on_drop::*;
 pub use crate::generated::*;
 pub use crate::geom::*;
-pub use crate::int32_array::*;
+pub use crate::name_cache::*;
 pub use crate::node_path::*;
 pub use crate::object::GodotObject;
 pub use crate::object::Instanciable;
 pub use crate::point2::*;
+pub use crate::pool_array::*;
 pub use crate::rid::*;
 pub use crate::string::*;
-pub use crate::string_array::*;
+pub use crate::typed_array::*;
+pub use crate::typed_dictionary::*;
 pub use crate::user_data::Map;
 pub use crate::user_data::MapMut;
 pub use crate::user_data::UserData;
 pub use crate::variant::*;
 pub use crate::variant_array::*;
+pub use crate::variant_value::*;
 pub use crate::vector2::*;
-pub use crate::vector2_array::*;
 pub use crate::vector3::*;
-pub use crate::vector3_array::*;
 
 pub use sys::GodotApi;
 
@@ -108,9 +116,29 @@ pub fn get_api() -> &'static GodotApi {
 pub fn get_gdnative_library_sys() -> *mut sys::godot_object {
     unsafe { GDNATIVE_LIBRARY_SYS.expect("GDNativeLibrary not bound") }
 }
+
+/// Registers the classes that the bindings use internally, like the one holding closures
+/// connected with [`connect_closure`](fn.connect_closure.html).
+///
+/// `godot_nativescript_init!` calls this before the user callback. Custom NativeScript init
+/// functions that don't use the macro must call it themselves.
+#[inline]
+pub fn register_internal_classes(handle: init::InitHandle) {
+    closure::register(handle);
+}
+
+#[inline]
+#[doc(hidden)]
+pub fn init_internal_state() {
+    #[cfg(feature = "async")]
+    r#async::init();
+}
 #[inline]
 #[doc(hidden)]
 pub unsafe fn cleanup_internal_state() {
+    #[cfg(feature = "async")]
+    r#async::cleanup();
+    closure::cleanup();
     type_tag::cleanup();
     GODOT_API = None;
 }
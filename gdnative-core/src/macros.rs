@@ -46,6 +46,7 @@ macro_rules! godot_gdnative_init {
             // assume that if the api object is alive we can fetch the method of these types
             // without checking for initialization.
             $crate::ReferenceMethodTable::get(api);
+            $crate::init_internal_state();
 
             $callback(options);
         }
@@ -134,7 +135,9 @@ macro_rules! godot_nativescript_init {
                 return;
             }
             unsafe {
-                $callback($crate::init::InitHandle::new(handle));
+                let handle = $crate::init::InitHandle::new(handle);
+                $crate::register_internal_classes(handle);
+                $callback(handle);
             }
         }
     };
@@ -225,6 +228,25 @@ macro_rules! godot_error {
     })
 }
 
+/// Returns the [`CachedName`](struct.CachedName.html) for a string literal, interning it in the
+/// global name cache on first use.
+///
+/// This avoids allocating a new `GodotString` every time a method or signal name is used:
+///
+/// ```ignore
+/// owner.emit_signal(sname!("tick").into(), &[]);
+///
+/// if variant.has_method(sname!("update")) {
+///     variant.call(sname!("update"), &[]).unwrap();
+/// }
+/// ```
+#[macro_export]
+macro_rules! sname {
+    ($name:literal) => {
+        $crate::CachedName::get($name)
+    };
+}
+
 macro_rules! impl_basic_trait {
     (
         Drop for $Type:ident as $GdType:ident : $gd_method:ident
@@ -333,102 +355,6 @@ macro_rules! impl_common_methods {
     )
 }
 
-macro_rules! impl_guard_common_trait {
-    (
-        Drop for $Type:ident { $access:ident, $len:ident, } : $gd_method:ident
-    ) => {
-        impl<'a> Drop for $Type<'a> {
-            fn drop(&mut self) {
-                unsafe {
-                    ($crate::get_api().$gd_method)(self.$access);
-                }
-            }
-        }
-    };
-
-    (
-        Clone for $Type:ident { $access:ident, $len:ident, } : $gd_method:ident
-    ) => {
-        impl<'a> Clone for $Type<'a> {
-            fn clone(&self) -> Self {
-                let $access = unsafe { ($crate::get_api().$gd_method)(self.$access) };
-
-                $Type {
-                    $access,
-                    $len: self.$len,
-                    _marker: std::marker::PhantomData,
-                }
-            }
-        }
-    };
-}
-
-macro_rules! define_access_guard {
-    (
-        pub struct $Type:ident<'a>: $AccessPtr:ty {
-            $access:ident = $access_gd_method:ident($BasePtr:ty),
-            $len:ident = $len_gd_method:ident,
-        }
-        Guard<Target=$Target:ident> => $guard_gd_method:ident -> $OrigPtr:ty;
-        $( $Trait:ident => $trait_gd_method:ident; )*
-    ) => {
-        pub struct $Type<'a> {
-            $access: *mut $AccessPtr,
-            $len: usize,
-            _marker: std::marker::PhantomData<&'a $Target>,
-        }
-
-        impl<'a> $Type<'a> {
-            unsafe fn new(arr: $BasePtr) -> Self {
-                let $len = ($crate::get_api().$len_gd_method)(arr) as usize;
-                let $access = ($crate::get_api().$access_gd_method)(arr);
-                $Type {
-                    $access,
-                    $len,
-                    _marker: std::marker::PhantomData,
-                }
-            }
-        }
-
-        unsafe impl<'a> $crate::access::Guard for $Type<'a> {
-            type Target = $Target;
-            fn len(&self) -> usize { self.$len }
-            fn read_ptr(&self) -> *const Self::Target {
-                unsafe {
-                    let orig_ptr: $OrigPtr = ($crate::get_api().$guard_gd_method)(self.$access);
-                    orig_ptr as *const Self::Target
-                }
-            }
-        }
-
-        $(
-            impl_guard_common_trait!(
-                $Trait for $Type { $access, $len, } : $trait_gd_method
-            );
-        )*
-    };
-
-    (
-        pub struct $Type:ident<'a>: $AccessPtr:ty {
-            $access:ident = $access_gd_method:ident($BasePtr:ty),
-            $len:ident = $len_gd_method:ident,
-        }
-        Guard<Target=$Target:ident> + WritePtr => $guard_gd_method:ident -> $OrigPtr:ty;
-        $( $Trait:ident => $trait_gd_method:ident; )*
-    ) => {
-        define_access_guard!(
-            pub struct $Type<'a>: $AccessPtr {
-                $access = $access_gd_method($BasePtr),
-                $len = $len_gd_method,
-            }
-            Guard<Target=$Target> => $guard_gd_method -> $OrigPtr;
-            $( $Trait => $trait_gd_method; )*
-        );
-
-        unsafe impl<'a> $crate::access::WritePtr for $Type<'a> { }
-    };
-}
-
 macro_rules! godot_test {
     ($($test_name:ident $body:block)*) => {
         $(
@@ -448,6 +374,18 @@ macro_rules! godot_test {
 
                 ok
             }
+
+            #[cfg(test)]
+            mod $test_name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #[test]
+                fn headless() {
+                    $crate::mock::bind();
+                    $body
+                }
+            }
         )*
     }
 }
@@ -0,0 +1,813 @@
+//! Godot's binary serialization format for variants.
+//!
+//! This is the format used by `var2bytes`, `bytes2var`, `PacketPeer.put_var` and
+//! `File.store_var`. Note that `File.store_var` additionally prefixes each value with its
+//! encoded length as an unsigned 32-bit integer.
+//!
+//! The codec itself works on [`VariantValue`](../enum.VariantValue.html), which doesn't need
+//! the engine, so that packets and save files can be read in tools that run outside of Godot.
+//! `encode_variant` and `decode_variant` convert from and to `Variant` on top of that.
+//!
+//! `Rid`s are not part of the format, and decode to an invalid `Rid` like in the engine.
+//! Null objects are encoded as `Nil`, like in the engine since Godot 3.2.
+//!
+//! Decoding is safe to use on untrusted input: values nested deeper than
+//! [`MAX_DEPTH`](constant.MAX_DEPTH.html) are rejected instead of overflowing the stack.
+
+use crate::geom::real_to_f32;
+use crate::*;
+
+use std::fmt;
+
+const ENCODE_MASK: u32 = 0xFF;
+const ENCODE_FLAG_64: u32 = 1 << 16;
+const ENCODE_FLAG_OBJECT_AS_ID: u32 = 1 << 16;
+
+const NODE_PATH_NEW_FORMAT: u32 = 0x8000_0000;
+const NODE_PATH_FLAG_ABSOLUTE: u32 = 1;
+const NODE_PATH_FLAG_OLD_SUBNAME: u32 = 2;
+
+/// Maximum nesting depth of decoded values. A value that isn't inside of an array, a
+/// dictionary or an object is at depth 1.
+pub const MAX_DEPTH: usize = 256;
+
+const TYPES: [VariantType; 27] = [
+    VariantType::Nil,
+    VariantType::Bool,
+    VariantType::I64,
+    VariantType::F64,
+    VariantType::GodotString,
+    VariantType::Vector2,
+    VariantType::Rect2,
+    VariantType::Vector3,
+    VariantType::Transform2D,
+    VariantType::Plane,
+    VariantType::Quat,
+    VariantType::Aabb,
+    VariantType::Basis,
+    VariantType::Transform,
+    VariantType::Color,
+    VariantType::NodePath,
+    VariantType::Rid,
+    VariantType::Object,
+    VariantType::Dictionary,
+    VariantType::VariantArray,
+    VariantType::ByteArray,
+    VariantType::Int32Array,
+    VariantType::Float32Array,
+    VariantType::StringArray,
+    VariantType::Vector2Array,
+    VariantType::Vector3Array,
+    VariantType::ColorArray,
+];
+
+/// Error while encoding or decoding a value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarshalError {
+    /// The input ended before the value was complete.
+    UnexpectedEof,
+    /// The header of a value contains an unknown type.
+    InvalidType(u32),
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+    /// Values are nested deeper than `MAX_DEPTH`.
+    TooDeep,
+    /// The value contains a non-null object, which can't be converted to a `Variant`.
+    UnsupportedObject,
+}
+
+impl fmt::Display for MarshalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MarshalError::UnexpectedEof => write!(f, "unexpected end of input"),
+            MarshalError::InvalidType(ty) => write!(f, "invalid variant type {}", ty),
+            MarshalError::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            MarshalError::TooDeep => write!(f, "values are nested too deeply"),
+            MarshalError::UnsupportedObject => write!(f, "objects can't be decoded to a Variant"),
+        }
+    }
+}
+
+impl std::error::Error for MarshalError {}
+
+/// Encodes a value like `var2bytes`.
+pub fn encode(value: &VariantValue) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode_into(value, &mut buf);
+    buf
+}
+
+/// Encodes a value like `var2bytes`, appending the result to `buf`.
+pub fn encode_into(value: &VariantValue, buf: &mut Vec<u8>) {
+    Writer(buf).value(value)
+}
+
+/// Decodes a value like `bytes2var`. Returns the value and the number of bytes read.
+///
+/// Trailing bytes after the value are ignored, like in the engine.
+pub fn decode(bytes: &[u8]) -> Result<(VariantValue, usize), MarshalError> {
+    let mut reader = Reader {
+        bytes,
+        pos: 0,
+        depth: 0,
+    };
+    let value = reader.value()?;
+    Ok((value, reader.pos))
+}
+
+/// Encodes a `Variant` like `var2bytes` without `full_objects`.
+pub fn encode_variant(variant: &Variant) -> Vec<u8> {
+    encode(&VariantValue::from(variant))
+}
+
+/// Decodes a `Variant` like `bytes2var`. Returns the variant and the number of bytes read.
+///
+/// Non-null objects can't be converted to a `Variant`, see `VariantValue::try_to_variant`.
+pub fn decode_variant(bytes: &[u8]) -> Result<(Variant, usize), MarshalError> {
+    let (value, len) = decode(bytes)?;
+    let variant = value
+        .try_to_variant()
+        .ok_or(MarshalError::UnsupportedObject)?;
+    Ok((variant, len))
+}
+
+fn padding(len: usize) -> usize {
+    (4 - len % 4) % 4
+}
+
+/// Splits a node path into its absolute flag, names and subnames.
+fn split_node_path(path: &str) -> (bool, Vec<&str>, Vec<&str>) {
+    let absolute = path.starts_with('/');
+    let (path, subpath) = match path.find(':') {
+        Some(pos) => (&path[..pos], Some(&path[pos + 1..])),
+        None => (path, None),
+    };
+    let names = path.split('/').filter(|name| !name.is_empty()).collect();
+    let subnames = subpath
+        .map(|subpath| subpath.split(':').filter(|name| !name.is_empty()).collect())
+        .unwrap_or_default();
+    (absolute, names, subnames)
+}
+
+fn join_node_path(absolute: bool, names: &[String], subnames: &[String]) -> String {
+    let mut path = if absolute {
+        String::from("/")
+    } else {
+        String::new()
+    };
+    path.push_str(&names.join("/"));
+    for subname in subnames {
+        path.push(':');
+        path.push_str(subname);
+    }
+    path
+}
+
+struct Writer<'a>(&'a mut Vec<u8>);
+
+impl<'a> Writer<'a> {
+    fn u32(&mut self, v: u32) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn header(&mut self, ty: VariantType, flags: u32) {
+        self.u32(ty as u32 | flags);
+    }
+
+    fn floats(&mut self, floats: &[f32]) {
+        for float in floats {
+            self.0.extend_from_slice(&float.to_le_bytes());
+        }
+    }
+
+    /// Writes reals as 32-bit floats, like the engine does even with `real_t=double`.
+    fn reals(&mut self, reals: &[real]) {
+        for &value in reals {
+            self.0.extend_from_slice(&real_to_f32(value).to_le_bytes());
+        }
+    }
+
+    fn string(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.0.extend_from_slice(s.as_bytes());
+        self.pad(s.len());
+    }
+
+    fn pad(&mut self, len: usize) {
+        self.0.resize(self.0.len() + padding(len), 0);
+    }
+
+    fn value(&mut self, value: &VariantValue) {
+        let ty = value.get_type();
+        match value {
+            VariantValue::Nil | VariantValue::Rid(_) => self.header(ty, 0),
+            // Like in the engine, null objects are written as `Nil`.
+            VariantValue::Object(ObjectValue::Id(0)) => self.header(VariantType::Nil, 0),
+            VariantValue::Object(ObjectValue::Full { class, .. }) if class.is_empty() => {
+                self.header(VariantType::Nil, 0)
+            }
+            VariantValue::Bool(b) => {
+                self.header(ty, 0);
+                self.u32(*b as u32);
+            }
+            VariantValue::I64(i) => {
+                if *i >= i64::from(i32::MIN) && *i <= i64::from(i32::MAX) {
+                    self.header(ty, 0);
+                    self.0.extend_from_slice(&(*i as i32).to_le_bytes());
+                } else {
+                    self.header(ty, ENCODE_FLAG_64);
+                    self.0.extend_from_slice(&i.to_le_bytes());
+                }
+            }
+            VariantValue::F64(f) => {
+                #[allow(clippy::float_cmp)]
+                let fits = f64::from(*f as f32) == *f;
+                if fits {
+                    self.header(ty, 0);
+                    self.floats(&[*f as f32]);
+                } else {
+                    self.header(ty, ENCODE_FLAG_64);
+                    self.0.extend_from_slice(&f.to_le_bytes());
+                }
+            }
+            VariantValue::GodotString(s) => {
+                self.header(ty, 0);
+                self.string(s);
+            }
+            VariantValue::Vector2(v) => {
+                self.header(ty, 0);
+                self.reals(&[v.x, v.y]);
+            }
+            VariantValue::Rect2(r) => {
+                self.header(ty, 0);
+                self.reals(&[r.origin.x, r.origin.y, r.size.width, r.size.height]);
+            }
+            VariantValue::Vector3(v) => {
+                self.header(ty, 0);
+                self.reals(&[v.x, v.y, v.z]);
+            }
+            VariantValue::Transform2D(t) => {
+                self.header(ty, 0);
+                self.reals(&[t.m11, t.m12, t.m21, t.m22, t.m31, t.m32]);
+            }
+            VariantValue::Plane(p) => {
+                self.header(ty, 0);
+                self.reals(&[p.normal.x, p.normal.y, p.normal.z, p.d]);
+            }
+            VariantValue::Quat(q) => {
+                self.header(ty, 0);
+                self.reals(&[q.i, q.j, q.k, q.r]);
+            }
+            VariantValue::Aabb(a) => {
+                self.header(ty, 0);
+                self.reals(&[a.position.x, a.position.y, a.position.z]);
+                self.reals(&[a.size.x, a.size.y, a.size.z]);
+            }
+            VariantValue::Basis(b) => {
+                self.header(ty, 0);
+                self.basis(b);
+            }
+            VariantValue::Transform(t) => {
+                self.header(ty, 0);
+                self.basis(&t.basis);
+                self.reals(&[t.origin.x, t.origin.y, t.origin.z]);
+            }
+            VariantValue::Color(c) => {
+                self.header(ty, 0);
+                self.floats(&[c.r, c.g, c.b, c.a]);
+            }
+            VariantValue::NodePath(path) => {
+                let (absolute, names, subnames) = split_node_path(path);
+                self.header(ty, 0);
+                self.u32(names.len() as u32 | NODE_PATH_NEW_FORMAT);
+                self.u32(subnames.len() as u32);
+                self.u32(if absolute { NODE_PATH_FLAG_ABSOLUTE } else { 0 });
+                for name in names.iter().chain(subnames.iter()) {
+                    self.string(name);
+                }
+            }
+            VariantValue::Object(ObjectValue::Id(id)) => {
+                self.header(ty, ENCODE_FLAG_OBJECT_AS_ID);
+                self.0.extend_from_slice(&id.to_le_bytes());
+            }
+            VariantValue::Object(ObjectValue::Full { class, properties }) => {
+                self.header(ty, 0);
+                self.string(class);
+                if !class.is_empty() {
+                    self.u32(properties.len() as u32);
+                    for (name, value) in properties {
+                        self.string(name);
+                        self.value(value);
+                    }
+                }
+            }
+            VariantValue::Dictionary(entries) => {
+                self.header(ty, 0);
+                self.u32(entries.len() as u32);
+                for (key, value) in entries {
+                    self.value(key);
+                    self.value(value);
+                }
+            }
+            VariantValue::VariantArray(items) => {
+                self.header(ty, 0);
+                self.u32(items.len() as u32);
+                items.iter().for_each(|item| self.value(item));
+            }
+            VariantValue::ByteArray(bytes) => {
+                self.header(ty, 0);
+                self.u32(bytes.len() as u32);
+                self.0.extend_from_slice(bytes);
+                self.pad(bytes.len());
+            }
+            VariantValue::Int32Array(ints) => {
+                self.header(ty, 0);
+                self.u32(ints.len() as u32);
+                for i in ints {
+                    self.0.extend_from_slice(&i.to_le_bytes());
+                }
+            }
+            VariantValue::Float32Array(reals) => {
+                self.header(ty, 0);
+                self.u32(reals.len() as u32);
+                self.reals(reals);
+            }
+            VariantValue::StringArray(strings) => {
+                self.header(ty, 0);
+                self.u32(strings.len() as u32);
+                for s in strings {
+                    // Unlike other strings, these are stored with a null terminator.
+                    self.u32(s.len() as u32 + 1);
+                    self.0.extend_from_slice(s.as_bytes());
+                    self.0.push(0);
+                    self.pad(s.len() + 1);
+                }
+            }
+            VariantValue::Vector2Array(vectors) => {
+                self.header(ty, 0);
+                self.u32(vectors.len() as u32);
+                vectors.iter().for_each(|v| self.reals(&[v.x, v.y]));
+            }
+            VariantValue::Vector3Array(vectors) => {
+                self.header(ty, 0);
+                self.u32(vectors.len() as u32);
+                vectors.iter().for_each(|v| self.reals(&[v.x, v.y, v.z]));
+            }
+            VariantValue::ColorArray(colors) => {
+                self.header(ty, 0);
+                self.u32(colors.len() as u32);
+                colors
+                    .iter()
+                    .for_each(|c| self.floats(&[c.r, c.g, c.b, c.a]));
+            }
+        }
+    }
+
+    fn basis(&mut self, basis: &Basis) {
+        for row in &basis.elements {
+            self.reals(&[row.x, row.y, row.z]);
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], MarshalError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(MarshalError::UnexpectedEof)?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn bytes4(&mut self) -> Result<[u8; 4], MarshalError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(bytes)
+    }
+
+    fn bytes8(&mut self) -> Result<[u8; 8], MarshalError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, MarshalError> {
+        self.bytes4().map(u32::from_le_bytes)
+    }
+
+    fn len(&mut self) -> Result<usize, MarshalError> {
+        self.u32().map(|len| len as usize)
+    }
+
+    fn f32(&mut self) -> Result<f32, MarshalError> {
+        self.bytes4().map(f32::from_le_bytes)
+    }
+
+    /// Reads a 32-bit float as a real.
+    fn real(&mut self) -> Result<real, MarshalError> {
+        self.f32().map(|f| f as real)
+    }
+
+    fn vector2(&mut self) -> Result<Vector2, MarshalError> {
+        Ok(Vector2::new(self.real()?, self.real()?))
+    }
+
+    fn vector3(&mut self) -> Result<Vector3, MarshalError> {
+        Ok(Vector3::new(self.real()?, self.real()?, self.real()?))
+    }
+
+    fn color(&mut self) -> Result<Color, MarshalError> {
+        Ok(Color::rgba(
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+        ))
+    }
+
+    fn basis(&mut self) -> Result<Basis, MarshalError> {
+        Ok(Basis {
+            elements: [self.vector3()?, self.vector3()?, self.vector3()?],
+        })
+    }
+
+    fn string_with_len(&mut self, len: usize) -> Result<String, MarshalError> {
+        let bytes = self.take(len)?;
+        self.take(padding(len))?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| MarshalError::InvalidUtf8)
+    }
+
+    fn string(&mut self) -> Result<String, MarshalError> {
+        let len = self.len()?;
+        self.string_with_len(len)
+    }
+
+    fn value(&mut self) -> Result<VariantValue, MarshalError> {
+        if self.depth == MAX_DEPTH {
+            return Err(MarshalError::TooDeep);
+        }
+
+        self.depth += 1;
+        let value = self.value_at_depth();
+        self.depth -= 1;
+        value
+    }
+
+    fn value_at_depth(&mut self) -> Result<VariantValue, MarshalError> {
+        let header = self.u32()?;
+        let ty = TYPES
+            .get((header & ENCODE_MASK) as usize)
+            .ok_or(MarshalError::InvalidType(header & ENCODE_MASK))?;
+
+        let value = match ty {
+            VariantType::Nil => VariantValue::Nil,
+            VariantType::Bool => VariantValue::Bool(self.u32()? != 0),
+            VariantType::I64 => {
+                if header & ENCODE_FLAG_64 != 0 {
+                    VariantValue::I64(i64::from_le_bytes(self.bytes8()?))
+                } else {
+                    VariantValue::I64(i32::from_le_bytes(self.bytes4()?).into())
+                }
+            }
+            VariantType::F64 => {
+                if header & ENCODE_FLAG_64 != 0 {
+                    VariantValue::F64(f64::from_le_bytes(self.bytes8()?))
+                } else {
+                    VariantValue::F64(self.f32()?.into())
+                }
+            }
+            VariantType::GodotString => VariantValue::GodotString(self.string()?),
+            VariantType::Vector2 => VariantValue::Vector2(self.vector2()?),
+            VariantType::Rect2 => VariantValue::Rect2(euclid::rect(
+                self.real()?,
+                self.real()?,
+                self.real()?,
+                self.real()?,
+            )),
+            VariantType::Vector3 => VariantValue::Vector3(self.vector3()?),
+            VariantType::Transform2D => VariantValue::Transform2D(Transform2D::row_major(
+                self.real()?,
+                self.real()?,
+                self.real()?,
+                self.real()?,
+                self.real()?,
+                self.real()?,
+            )),
+            VariantType::Plane => VariantValue::Plane(Plane {
+                normal: self.vector3()?,
+                d: self.real()?,
+            }),
+            VariantType::Quat => VariantValue::Quat(Quat::quaternion(
+                self.real()?,
+                self.real()?,
+                self.real()?,
+                self.real()?,
+            )),
+            VariantType::Aabb => VariantValue::Aabb(Aabb {
+                position: self.vector3()?,
+                size: self.vector3()?,
+            }),
+            VariantType::Basis => VariantValue::Basis(self.basis()?),
+            VariantType::Transform => VariantValue::Transform(Transform {
+                basis: self.basis()?,
+                origin: self.vector3()?,
+            }),
+            VariantType::Color => VariantValue::Color(self.color()?),
+            VariantType::NodePath => {
+                let len = self.u32()?;
+                if len & NODE_PATH_NEW_FORMAT == 0 {
+                    // Older versions stored the path as a plain string.
+                    VariantValue::NodePath(self.string_with_len(len as usize)?)
+                } else {
+                    let name_count = (len & !NODE_PATH_NEW_FORMAT) as usize;
+                    let mut subname_count = self.len()?;
+                    let flags = self.u32()?;
+                    if flags & NODE_PATH_FLAG_OLD_SUBNAME != 0 {
+                        subname_count += 1;
+                    }
+                    let names = (0..name_count)
+                        .map(|_| self.string())
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let subnames = (0..subname_count)
+                        .map(|_| self.string())
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let absolute = flags & NODE_PATH_FLAG_ABSOLUTE != 0;
+                    VariantValue::NodePath(join_node_path(absolute, &names, &subnames))
+                }
+            }
+            VariantType::Rid => VariantValue::Rid(0),
+            VariantType::Object => {
+                if header & ENCODE_FLAG_OBJECT_AS_ID != 0 {
+                    VariantValue::Object(ObjectValue::Id(u64::from_le_bytes(self.bytes8()?)))
+                } else {
+                    let class = self.string()?;
+                    if class.is_empty() {
+                        VariantValue::Object(ObjectValue::Id(0))
+                    } else {
+                        let count = self.len()?;
+                        let properties = (0..count)
+                            .map(|_| Ok((self.string()?, self.value()?)))
+                            .collect::<Result<_, _>>()?;
+                        VariantValue::Object(ObjectValue::Full { class, properties })
+                    }
+                }
+            }
+            VariantType::Dictionary => {
+                let count = self.u32()? & 0x7FFF_FFFF;
+                let entries = (0..count)
+                    .map(|_| Ok((self.value()?, self.value()?)))
+                    .collect::<Result<_, _>>()?;
+                VariantValue::Dictionary(entries)
+            }
+            VariantType::VariantArray => {
+                let count = self.u32()? & 0x7FFF_FFFF;
+                let items = (0..count).map(|_| self.value()).collect::<Result<_, _>>()?;
+                VariantValue::VariantArray(items)
+            }
+            VariantType::ByteArray => {
+                let len = self.len()?;
+                let bytes = self.take(len)?.to_vec();
+                self.take(padding(len))?;
+                VariantValue::ByteArray(bytes)
+            }
+            VariantType::Int32Array => {
+                let count = self.len()?;
+                let ints = (0..count)
+                    .map(|_| self.bytes4().map(i32::from_le_bytes))
+                    .collect::<Result<_, _>>()?;
+                VariantValue::Int32Array(ints)
+            }
+            VariantType::Float32Array => {
+                let count = self.len()?;
+                let reals = (0..count).map(|_| self.real()).collect::<Result<_, _>>()?;
+                VariantValue::Float32Array(reals)
+            }
+            VariantType::StringArray => {
+                let count = self.len()?;
+                let strings = (0..count)
+                    .map(|_| {
+                        let len = self.len()?;
+                        let bytes = self.take(len)?;
+                        self.take(padding(len))?;
+                        let end = bytes.iter().position(|&b| b == 0).unwrap_or(len);
+                        String::from_utf8(bytes[..end].to_vec())
+                            .map_err(|_| MarshalError::InvalidUtf8)
+                    })
+                    .collect::<Result<_, _>>()?;
+                VariantValue::StringArray(strings)
+            }
+            VariantType::Vector2Array => {
+                let count = self.len()?;
+                let vectors = (0..count)
+                    .map(|_| self.vector2())
+                    .collect::<Result<_, _>>()?;
+                VariantValue::Vector2Array(vectors)
+            }
+            VariantType::Vector3Array => {
+                let count = self.len()?;
+                let vectors = (0..count)
+                    .map(|_| self.vector3())
+                    .collect::<Result<_, _>>()?;
+                VariantValue::Vector3Array(vectors)
+            }
+            VariantType::ColorArray => {
+                let count = self.len()?;
+                let colors = (0..count).map(|_| self.color()).collect::<Result<_, _>>()?;
+                VariantValue::ColorArray(colors)
+            }
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(value: VariantValue) {
+        let bytes = encode(&value);
+        assert_eq!(0, bytes.len() % 4);
+        assert_eq!(Ok((value, bytes.len())), decode(&bytes));
+    }
+
+    #[test]
+    fn it_matches_engine_output() {
+        // var2bytes(42)
+        assert_eq!(
+            vec![2, 0, 0, 0, 42, 0, 0, 0],
+            encode(&VariantValue::I64(42))
+        );
+        // var2bytes(1 << 40)
+        assert_eq!(
+            vec![2, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0],
+            encode(&VariantValue::I64(1 << 40))
+        );
+        // var2bytes(0.5)
+        assert_eq!(
+            vec![3, 0, 0, 0, 0, 0, 0, 63],
+            encode(&VariantValue::F64(0.5))
+        );
+        // var2bytes("abcde")
+        assert_eq!(
+            vec![4, 0, 0, 0, 5, 0, 0, 0, 97, 98, 99, 100, 101, 0, 0, 0],
+            encode(&VariantValue::GodotString("abcde".into()))
+        );
+        // var2bytes(PoolStringArray(["ab"]))
+        assert_eq!(
+            vec![23, 0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 97, 98, 0, 0],
+            encode(&VariantValue::StringArray(vec!["ab".into()]))
+        );
+        // var2bytes(NodePath("/a:b"))
+        assert_eq!(
+            vec![
+                15, 0, 0, 0, 1, 0, 0, 128, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 97, 0, 0, 0, 1, 0,
+                0, 0, 98, 0, 0, 0
+            ],
+            encode(&VariantValue::NodePath("/a:b".into()))
+        );
+        // var2bytes of a null object
+        assert_eq!(
+            vec![0, 0, 0, 0],
+            encode(&VariantValue::Object(ObjectValue::Id(0)))
+        );
+        assert_eq!(
+            Ok((VariantValue::Nil, 4)),
+            decode(&encode(&VariantValue::Object(ObjectValue::Full {
+                class: String::new(),
+                properties: vec![],
+            })))
+        );
+    }
+
+    #[test]
+    fn it_roundtrips_all_types() {
+        roundtrip(VariantValue::Nil);
+        roundtrip(VariantValue::Bool(true));
+        roundtrip(VariantValue::I64(-7));
+        roundtrip(VariantValue::I64(i64::MIN));
+        roundtrip(VariantValue::F64(0.1));
+        roundtrip(VariantValue::GodotString("héllo".into()));
+        roundtrip(VariantValue::Vector2(Vector2::new(1.0, 2.0)));
+        roundtrip(VariantValue::Rect2(euclid::rect(1.0, 2.0, 3.0, 4.0)));
+        roundtrip(VariantValue::Vector3(Vector3::new(1.0, 2.0, 3.0)));
+        roundtrip(VariantValue::Transform2D(Transform2D::row_major(
+            1.0, 2.0, 3.0, 4.0, 5.0, 6.0,
+        )));
+        roundtrip(VariantValue::Plane(Plane {
+            normal: Vector3::new(0.0, 1.0, 0.0),
+            d: 2.0,
+        }));
+        roundtrip(VariantValue::Quat(Quat::quaternion(0.0, 0.0, 0.0, 1.0)));
+        roundtrip(VariantValue::Aabb(Aabb {
+            position: Vector3::new(1.0, 2.0, 3.0),
+            size: Vector3::new(4.0, 5.0, 6.0),
+        }));
+        roundtrip(VariantValue::Basis(Basis::identity()));
+        roundtrip(VariantValue::Transform(Transform::translate(Vector3::new(
+            1.0, 2.0, 3.0,
+        ))));
+        roundtrip(VariantValue::Color(Color::rgb(1.0, 0.5, 0.25)));
+        roundtrip(VariantValue::NodePath("../a/b:c:d".into()));
+        roundtrip(VariantValue::Rid(0));
+        roundtrip(VariantValue::Object(ObjectValue::Id(1234)));
+        roundtrip(VariantValue::Object(ObjectValue::Full {
+            class: "Resource".into(),
+            properties: vec![(
+                "resource_name".into(),
+                VariantValue::GodotString("a".into()),
+            )],
+        }));
+        roundtrip(VariantValue::Dictionary(
+            vec![
+                (
+                    VariantValue::I64(1),
+                    VariantValue::GodotString("one".into()),
+                ),
+                (
+                    VariantValue::GodotString("two".into()),
+                    VariantValue::F64(2.0),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        ));
+        roundtrip(VariantValue::VariantArray(vec![
+            VariantValue::Nil,
+            VariantValue::Bool(false),
+        ]));
+        roundtrip(VariantValue::ByteArray(vec![1, 2, 3, 4, 5]));
+        roundtrip(VariantValue::Int32Array(vec![-1, 2]));
+        roundtrip(VariantValue::Float32Array(vec![0.5, 1.5]));
+        roundtrip(VariantValue::StringArray(vec![
+            "".into(),
+            "abc".into(),
+            "abcd".into(),
+        ]));
+        roundtrip(VariantValue::Vector2Array(vec![Vector2::new(1.0, 2.0)]));
+        roundtrip(VariantValue::Vector3Array(vec![Vector3::new(
+            1.0, 2.0, 3.0,
+        )]));
+        roundtrip(VariantValue::ColorArray(vec![Color::rgba(
+            0.0, 0.25, 0.5, 1.0,
+        )]));
+    }
+
+    #[test]
+    fn it_rejects_invalid_input() {
+        assert_eq!(Err(MarshalError::UnexpectedEof), decode(&[2, 0, 0]));
+        assert_eq!(
+            Err(MarshalError::UnexpectedEof),
+            decode(&[4, 0, 0, 0, 8, 0, 0, 0])
+        );
+        assert_eq!(Err(MarshalError::InvalidType(27)), decode(&[27, 0, 0, 0]));
+        assert_eq!(
+            Err(MarshalError::InvalidUtf8),
+            decode(&[4, 0, 0, 0, 1, 0, 0, 0, 255, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn it_limits_the_depth() {
+        let mut value = VariantValue::Nil;
+        for _ in 1..MAX_DEPTH {
+            value = VariantValue::VariantArray(vec![value]);
+        }
+        roundtrip(value.clone());
+
+        let value = VariantValue::VariantArray(vec![value]);
+        assert_eq!(Err(MarshalError::TooDeep), decode(&encode(&value)));
+
+        // Arrays of one array each, as deep as the input allows.
+        let bytes = [19, 0, 0, 0, 1, 0, 0, 0].repeat(100_000);
+        assert_eq!(Err(MarshalError::TooDeep), decode(&bytes));
+    }
+}
+
+godot_test!(test_marshal_variant {
+    let mut dict = Dictionary::new();
+    dict.set(&Variant::from_str("key"), &Variant::from_i64(1));
+
+    let mut array = VariantArray::new();
+    array.push(&Variant::from_f64(1.5));
+    array.push(&Variant::from_dictionary(&dict));
+    array.push(&Variant::from_node_path(&NodePath::from_str("a/b:c")));
+
+    let bytes = encode_variant(&Variant::from_array(&array));
+    let (variant, len) = decode_variant(&bytes).unwrap();
+    assert_eq!(bytes.len(), len);
+
+    let decoded = variant.to_array();
+    assert_eq!(3, decoded.len());
+    assert_eq!(Some(1.5), decoded.get_ref(0).try_to_f64());
+    assert_eq!(1, decoded.get_ref(1).to_dictionary().get(&Variant::from_str("key")).to_i64());
+    assert_eq!("a/b:c", decoded.get_ref(2).to_node_path().to_string());
+});
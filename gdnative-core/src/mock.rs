@@ -0,0 +1,259 @@
+//! In-process implementation of the core API, for testing without the engine.
+//!
+//! The mock fills a `GodotApi` table with pure-Rust implementations of the string, array,
+//! dictionary, pool array and variant functions, so that the core types of this crate can
+//! be used from ordinary `cargo test` runs. Functions that depend on a running engine, such
+//! as object method calls, abort with a message naming the missing function.
+//!
+//! The mock is always available for tests of `gdnative-core` itself. Other crates can use
+//! it by enabling the `mock` feature:
+//!
+//! ```ignore
+//! #[test]
+//! fn my_test() {
+//!     gdnative::mock::bind();
+//!
+//!     let mut array = gdnative::VariantArray::new();
+//!     array.push(&gdnative::Variant::from_i64(42));
+//!     assert_eq!(1, array.len());
+//! }
+//! ```
+//!
+//! The mock must never be bound in a library that is loaded by the engine.
+
+use crate::sys;
+use crate::GodotApi;
+
+use std::cell::UnsafeCell;
+use std::mem::{self, ManuallyDrop};
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::sync::{Arc, Once};
+
+macro_rules! install {
+    ($api:ident { $($name:ident,)* }) => {
+        $(
+            $api.$name = $name;
+        )*
+    };
+}
+
+mod array;
+mod dictionary;
+mod misc;
+mod node_path;
+mod operator;
+mod pool_array;
+mod string;
+mod variant;
+
+/// Binds the mock API as the global API of this crate, if it isn't already.
+///
+/// Calling this function more than once has no further effect, so every test can call it
+/// before using any engine type.
+pub fn bind() {
+    static BIND: Once = Once::new();
+    BIND.call_once(|| unsafe {
+        if crate::GODOT_API.is_none() {
+            crate::GODOT_API = Some(api());
+        }
+    });
+}
+
+/// Returns a new API table backed by the mock implementation.
+pub fn api() -> GodotApi {
+    let mut api = GodotApi::unimplemented();
+    array::install(&mut api);
+    dictionary::install(&mut api);
+    misc::install(&mut api);
+    node_path::install(&mut api);
+    operator::install(&mut api);
+    pool_array::install(&mut api);
+    string::install(&mut api);
+    variant::install(&mut api);
+    api
+}
+
+/// Opaque engine types whose contents are a single Rust value in the mock.
+///
+/// The value is stored at the start of the opaque byte array. Engine types are not aligned,
+/// so all accesses are unaligned reads and writes.
+pub(crate) unsafe trait Opaque: Sized + Default {
+    type Repr;
+
+    /// Moves `repr` into the uninitialized value at `dest`.
+    unsafe fn init(dest: *mut Self, repr: Self::Repr) {
+        debug_assert!(mem::size_of::<Self::Repr>() <= mem::size_of::<Self>());
+        ptr::write_unaligned(dest as *mut Self::Repr, repr);
+    }
+
+    /// Creates a new value from `repr`.
+    fn from_repr(repr: Self::Repr) -> Self {
+        let mut value = Self::default();
+        unsafe { Self::init(&mut value, repr) };
+        value
+    }
+
+    /// Moves the contents out of `src`, leaving it uninitialized.
+    unsafe fn take(src: *mut Self) -> Self::Repr {
+        ptr::read_unaligned(src as *const Self::Repr)
+    }
+
+    /// Borrows the contents of `src` without taking ownership.
+    unsafe fn borrow(src: *const Self) -> ManuallyDrop<Self::Repr> {
+        ManuallyDrop::new(ptr::read_unaligned(src as *const Self::Repr))
+    }
+
+    /// Replaces the contents of `dest` with `repr`, dropping the old contents.
+    unsafe fn replace(dest: *mut Self, repr: Self::Repr) {
+        drop(Self::take(dest));
+        Self::init(dest, repr);
+    }
+}
+
+/// Reference-counted storage with interior mutability.
+///
+/// Reference types such as `Array` mutate the shared data directly, while copy-on-write
+/// types such as `String` and the pool arrays call `make_unique` before any mutation.
+pub(crate) struct Shared<T>(Arc<UnsafeCell<T>>);
+
+impl<T> Shared<T> {
+    pub(crate) fn new(data: T) -> Self {
+        Shared(Arc::new(UnsafeCell::new(data)))
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn get_mut(&self) -> &mut T {
+        &mut *self.0.get()
+    }
+
+    pub(crate) fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: Clone> Shared<T> {
+    /// Makes sure that this reference is the only one to the data, copying it if necessary.
+    pub(crate) fn make_unique(&mut self) -> &mut T {
+        if Arc::strong_count(&self.0) > 1 {
+            *self = Shared::new((**self).clone());
+        }
+        unsafe { self.get_mut() }
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Shared(self.0.clone())
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.0.get() }
+    }
+}
+
+/// Values stored in mock containers, as the engine types that are exposed through pointers.
+pub(crate) trait Element: Sized {
+    fn new_default() -> Self;
+    fn duplicate(&self) -> Self;
+    fn destroy(&mut self);
+}
+
+macro_rules! impl_plain_element {
+    ($($Type:ty => $default:expr,)*) => {
+        $(
+            impl Element for $Type {
+                fn new_default() -> Self {
+                    $default
+                }
+
+                fn duplicate(&self) -> Self {
+                    *self
+                }
+
+                fn destroy(&mut self) {}
+            }
+        )*
+    };
+}
+
+impl_plain_element! {
+    u8 => 0,
+    sys::godot_int => 0,
+    sys::godot_real => 0.0,
+    sys::godot_vector2 => sys::godot_vector2::default(),
+    sys::godot_vector3 => sys::godot_vector3::default(),
+    sys::godot_color => unsafe { mem::transmute(crate::Color::rgba(0.0, 0.0, 0.0, 1.0)) },
+}
+
+impl<A: Element, B: Element> Element for (A, B) {
+    fn new_default() -> Self {
+        (A::new_default(), B::new_default())
+    }
+
+    fn duplicate(&self) -> Self {
+        (self.0.duplicate(), self.1.duplicate())
+    }
+
+    fn destroy(&mut self) {
+        self.0.destroy();
+        self.1.destroy();
+    }
+}
+
+/// A vector of elements that are copied and destroyed with their own semantics.
+pub(crate) struct Elements<T: Element>(pub(crate) Vec<T>);
+
+impl<T: Element> Elements<T> {
+    pub(crate) fn new() -> Self {
+        Elements(Vec::new())
+    }
+
+    pub(crate) fn resize(&mut self, size: usize) {
+        if size < self.0.len() {
+            for mut elem in self.0.drain(size..) {
+                elem.destroy();
+            }
+        } else {
+            let additional = size - self.0.len();
+            self.0.extend((0..additional).map(|_| T::new_default()));
+        }
+    }
+}
+
+impl<T: Element> Clone for Elements<T> {
+    fn clone(&self) -> Self {
+        Elements(self.0.iter().map(Element::duplicate).collect())
+    }
+}
+
+impl<T: Element> Drop for Elements<T> {
+    fn drop(&mut self) {
+        for elem in &mut self.0 {
+            elem.destroy();
+        }
+    }
+}
+
+impl<T: Element> Deref for Elements<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T: Element> DerefMut for Elements<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.0
+    }
+}
+
+/// Reports an error like the engine's `ERR_FAIL` macros, without aborting the caller.
+fn print_error(function: &str, message: &str) {
+    eprintln!("ERROR: {}: {}", function, message);
+}
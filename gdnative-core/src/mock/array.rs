@@ -0,0 +1,420 @@
+use super::dictionary;
+use super::pool_array::{self, Pool};
+use super::variant::{self, ToValue, Value};
+use super::{print_error, Element, Elements, Opaque, Shared};
+use crate::sys;
+use crate::GodotApi;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Elements shared between all references to an array.
+pub(crate) type ArrayRepr = Shared<Elements<sys::godot_variant>>;
+
+unsafe impl Opaque for sys::godot_array {
+    type Repr = ArrayRepr;
+}
+
+/// Creates a new array from the given variants.
+pub(crate) fn new_array(elements: Vec<sys::godot_variant>) -> sys::godot_array {
+    sys::godot_array::from_repr(Shared::new(Elements(elements)))
+}
+
+/// Copies an array, and with `deep` also all arrays and dictionaries in it.
+pub(crate) fn duplicate(arr: &ArrayRepr, deep: bool) -> ArrayRepr {
+    let elements = arr
+        .iter()
+        .map(|v| unsafe { variant::new_variant(duplicate_value(variant::value_of(v), deep)) })
+        .collect();
+    Shared::new(Elements(elements))
+}
+
+/// Copies arrays and dictionaries if `deep` is set, like the engine's `Variant::duplicate`.
+pub(crate) fn duplicate_value(value: Value, deep: bool) -> Value {
+    match value {
+        Value::Array(arr) if deep => Value::Array(duplicate(&arr, true)),
+        Value::Dictionary(dict) if deep => Value::Dictionary(dictionary::duplicate(&dict, true)),
+        value => value,
+    }
+}
+
+unsafe fn elements<'a>(this: *const sys::godot_array) -> &'a mut Elements<sys::godot_variant> {
+    &mut *(sys::godot_array::borrow(this).get_mut() as *mut _)
+}
+
+unsafe fn values_of(this: *const sys::godot_array) -> Vec<Value> {
+    elements(this).iter().map(ToValue::to_value).collect()
+}
+
+fn check_index(function: &str, idx: sys::godot_int, len: usize) -> bool {
+    if idx < 0 || idx as usize >= len {
+        print_error(
+            function,
+            &format!("Index {} is out of bounds (size {}).", idx, len),
+        );
+        false
+    } else {
+        true
+    }
+}
+
+fn random() -> u64 {
+    static STATE: AtomicU64 = AtomicU64::new(0x2545_f491_4f6c_dd1d);
+    let mut x = STATE.load(Ordering::Relaxed);
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    STATE.store(x, Ordering::Relaxed);
+    x
+}
+
+unsafe extern "C" fn godot_array_new(dest: *mut sys::godot_array) {
+    sys::godot_array::init(dest, Shared::new(Elements::new()));
+}
+
+unsafe extern "C" fn godot_array_new_copy(
+    dest: *mut sys::godot_array,
+    src: *const sys::godot_array,
+) {
+    sys::godot_array::init(dest, (*sys::godot_array::borrow(src)).clone());
+}
+
+unsafe extern "C" fn new_pool<P: Pool>(dest: *mut sys::godot_array, pool: *const P) {
+    let elements = pool_array::elements_of(pool)
+        .iter()
+        .map(|elem| variant::new_variant(elem.to_value()))
+        .collect();
+    sys::godot_array::init(dest, Shared::new(Elements(elements)));
+}
+
+unsafe extern "C" fn godot_array_set(
+    this: *mut sys::godot_array,
+    idx: sys::godot_int,
+    value: *const sys::godot_variant,
+) {
+    let elements = elements(this);
+    if check_index("Array::set", idx, elements.len()) {
+        elements[idx as usize].destroy();
+        elements[idx as usize] = (*value).duplicate();
+    }
+}
+
+unsafe extern "C" fn godot_array_get(
+    this: *const sys::godot_array,
+    idx: sys::godot_int,
+) -> sys::godot_variant {
+    (*godot_array_operator_index_const(this, idx)).duplicate()
+}
+
+unsafe extern "C" fn godot_array_operator_index(
+    this: *mut sys::godot_array,
+    idx: sys::godot_int,
+) -> *mut sys::godot_variant {
+    let elements = elements(this);
+    if idx < 0 || idx as usize >= elements.len() {
+        panic!(
+            "index {} out of bounds for array of size {}",
+            idx,
+            elements.len()
+        );
+    }
+    &mut elements[idx as usize]
+}
+
+unsafe extern "C" fn godot_array_operator_index_const(
+    this: *const sys::godot_array,
+    idx: sys::godot_int,
+) -> *const sys::godot_variant {
+    godot_array_operator_index(this as *mut _, idx)
+}
+
+unsafe extern "C" fn godot_array_append(
+    this: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+) {
+    elements(this).push((*value).duplicate());
+}
+
+unsafe extern "C" fn godot_array_clear(this: *mut sys::godot_array) {
+    elements(this).resize(0);
+}
+
+unsafe extern "C" fn godot_array_count(
+    this: *const sys::godot_array,
+    value: *const sys::godot_variant,
+) -> sys::godot_int {
+    let value = variant::value_of(value);
+    values_of(this).iter().filter(|v| v.equals(&value)).count() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_array_empty(this: *const sys::godot_array) -> sys::godot_bool {
+    elements(this).is_empty()
+}
+
+unsafe extern "C" fn godot_array_erase(
+    this: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+) {
+    let idx = godot_array_find(this, value, 0);
+    if idx >= 0 {
+        elements(this).remove(idx as usize).destroy();
+    }
+}
+
+unsafe extern "C" fn godot_array_front(this: *const sys::godot_array) -> sys::godot_variant {
+    match elements(this).first() {
+        Some(value) => value.duplicate(),
+        None => {
+            print_error("Array::front", "Can't take value from empty array.");
+            variant::new_variant(Value::Nil)
+        }
+    }
+}
+
+unsafe extern "C" fn godot_array_back(this: *const sys::godot_array) -> sys::godot_variant {
+    match elements(this).last() {
+        Some(value) => value.duplicate(),
+        None => {
+            print_error("Array::back", "Can't take value from empty array.");
+            variant::new_variant(Value::Nil)
+        }
+    }
+}
+
+unsafe extern "C" fn godot_array_find(
+    this: *const sys::godot_array,
+    what: *const sys::godot_variant,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    let what = variant::value_of(what);
+    values_of(this)
+        .iter()
+        .enumerate()
+        .skip(from.max(0) as usize)
+        .find(|(_, v)| v.equals(&what))
+        .map_or(-1, |(i, _)| i as sys::godot_int)
+}
+
+unsafe extern "C" fn godot_array_find_last(
+    this: *const sys::godot_array,
+    what: *const sys::godot_variant,
+) -> sys::godot_int {
+    godot_array_rfind(this, what, -1)
+}
+
+unsafe extern "C" fn godot_array_has(
+    this: *const sys::godot_array,
+    value: *const sys::godot_variant,
+) -> sys::godot_bool {
+    godot_array_find(this, value, 0) != -1
+}
+
+unsafe extern "C" fn godot_array_hash(this: *const sys::godot_array) -> sys::godot_int {
+    Value::Array((*sys::godot_array::borrow(this)).clone()).hash() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_array_insert(
+    this: *mut sys::godot_array,
+    pos: sys::godot_int,
+    value: *const sys::godot_variant,
+) {
+    let elements = elements(this);
+    if pos < 0 || pos as usize > elements.len() {
+        print_error("Array::insert", &format!("Index {} is out of bounds.", pos));
+        return;
+    }
+    elements.insert(pos as usize, (*value).duplicate());
+}
+
+unsafe extern "C" fn godot_array_invert(this: *mut sys::godot_array) {
+    elements(this).reverse();
+}
+
+unsafe extern "C" fn godot_array_pop_back(this: *mut sys::godot_array) -> sys::godot_variant {
+    elements(this)
+        .pop()
+        .unwrap_or_else(|| variant::new_variant(Value::Nil))
+}
+
+unsafe extern "C" fn godot_array_pop_front(this: *mut sys::godot_array) -> sys::godot_variant {
+    let elements = elements(this);
+    if elements.is_empty() {
+        variant::new_variant(Value::Nil)
+    } else {
+        elements.remove(0)
+    }
+}
+
+unsafe extern "C" fn godot_array_push_front(
+    this: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+) {
+    elements(this).insert(0, (*value).duplicate());
+}
+
+unsafe extern "C" fn godot_array_remove(this: *mut sys::godot_array, idx: sys::godot_int) {
+    let elements = elements(this);
+    if check_index("Array::remove", idx, elements.len()) {
+        elements.remove(idx as usize).destroy();
+    }
+}
+
+unsafe extern "C" fn godot_array_resize(this: *mut sys::godot_array, size: sys::godot_int) {
+    elements(this).resize(size.max(0) as usize);
+}
+
+unsafe extern "C" fn godot_array_rfind(
+    this: *const sys::godot_array,
+    what: *const sys::godot_variant,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    let values = values_of(this);
+    let size = values.len() as sys::godot_int;
+    if size == 0 {
+        return -1;
+    }
+    let mut from = if from < 0 { size + from } else { from };
+    if from < 0 || from >= size {
+        from = size - 1;
+    }
+    let what = variant::value_of(what);
+    (0..=from)
+        .rev()
+        .find(|&i| values[i as usize].equals(&what))
+        .unwrap_or(-1)
+}
+
+unsafe extern "C" fn godot_array_size(this: *const sys::godot_array) -> sys::godot_int {
+    elements(this).len() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_array_sort(this: *mut sys::godot_array) {
+    elements(this).sort_by(|a, b| {
+        let (a, b) = (a.to_value(), b.to_value());
+        if a.less(&b) {
+            std::cmp::Ordering::Less
+        } else if b.less(&a) {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    });
+}
+
+unsafe extern "C" fn godot_array_bsearch(
+    this: *mut sys::godot_array,
+    value: *const sys::godot_variant,
+    before: sys::godot_bool,
+) -> sys::godot_int {
+    let values = values_of(this);
+    let value = variant::value_of(value);
+    let (mut lo, mut hi) = (0, values.len());
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let go_right = if before {
+            values[mid].less(&value)
+        } else {
+            !value.less(&values[mid])
+        };
+        if go_right {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo as sys::godot_int
+}
+
+unsafe extern "C" fn godot_array_destroy(this: *mut sys::godot_array) {
+    drop(sys::godot_array::take(this));
+}
+
+unsafe extern "C" fn godot_array_duplicate(
+    this: *const sys::godot_array,
+    deep: sys::godot_bool,
+) -> sys::godot_array {
+    sys::godot_array::from_repr(duplicate(&sys::godot_array::borrow(this), deep))
+}
+
+unsafe fn extreme(this: *const sys::godot_array, greater: bool) -> sys::godot_variant {
+    let mut values = values_of(this).into_iter();
+    let mut result = match values.next() {
+        Some(value) => value,
+        None => return variant::new_variant(Value::Nil),
+    };
+    for value in values {
+        let replace = if greater {
+            result.try_less(&value)
+        } else {
+            value.try_less(&result)
+        };
+        match replace {
+            Some(true) => result = value,
+            Some(false) => {}
+            None => return variant::new_variant(Value::Nil),
+        }
+    }
+    variant::new_variant(result)
+}
+
+unsafe extern "C" fn godot_array_max(this: *const sys::godot_array) -> sys::godot_variant {
+    extreme(this, true)
+}
+
+unsafe extern "C" fn godot_array_min(this: *const sys::godot_array) -> sys::godot_variant {
+    extreme(this, false)
+}
+
+unsafe extern "C" fn godot_array_shuffle(this: *mut sys::godot_array) {
+    let elements = elements(this);
+    for i in (1..elements.len()).rev() {
+        let j = (random() % (i as u64 + 1)) as usize;
+        elements.swap(i, j);
+    }
+}
+
+pub(super) fn install(api: &mut GodotApi) {
+    api.godot_array_new_pool_color_array = new_pool::<sys::godot_pool_color_array>;
+    api.godot_array_new_pool_vector3_array = new_pool::<sys::godot_pool_vector3_array>;
+    api.godot_array_new_pool_vector2_array = new_pool::<sys::godot_pool_vector2_array>;
+    api.godot_array_new_pool_string_array = new_pool::<sys::godot_pool_string_array>;
+    api.godot_array_new_pool_real_array = new_pool::<sys::godot_pool_real_array>;
+    api.godot_array_new_pool_int_array = new_pool::<sys::godot_pool_int_array>;
+    api.godot_array_new_pool_byte_array = new_pool::<sys::godot_pool_byte_array>;
+
+    install!(api {
+        godot_array_new,
+        godot_array_new_copy,
+        godot_array_set,
+        godot_array_get,
+        godot_array_operator_index,
+        godot_array_operator_index_const,
+        godot_array_append,
+        godot_array_clear,
+        godot_array_count,
+        godot_array_empty,
+        godot_array_erase,
+        godot_array_front,
+        godot_array_back,
+        godot_array_find,
+        godot_array_find_last,
+        godot_array_has,
+        godot_array_hash,
+        godot_array_insert,
+        godot_array_invert,
+        godot_array_pop_back,
+        godot_array_pop_front,
+        godot_array_push_front,
+        godot_array_remove,
+        godot_array_resize,
+        godot_array_rfind,
+        godot_array_size,
+        godot_array_sort,
+        godot_array_bsearch,
+        godot_array_destroy,
+        godot_array_duplicate,
+        godot_array_max,
+        godot_array_min,
+        godot_array_shuffle,
+    });
+    api.godot_array_push_back = godot_array_append;
+}
@@ -0,0 +1,274 @@
+use super::array::{self, duplicate_value};
+use super::string;
+use super::variant::{self, ToValue, Value};
+use super::{Element, Elements, Opaque, Shared};
+use crate::sys;
+use crate::GodotApi;
+
+use std::ptr;
+
+/// Key-value pairs in insertion order, shared between all references to a dictionary.
+pub(crate) type DictionaryRepr = Shared<Elements<(sys::godot_variant, sys::godot_variant)>>;
+
+unsafe impl Opaque for sys::godot_dictionary {
+    type Repr = DictionaryRepr;
+}
+
+/// Copies a dictionary, and with `deep` also all arrays and dictionaries in it.
+pub(crate) fn duplicate(dict: &DictionaryRepr, deep: bool) -> DictionaryRepr {
+    let entries = dict
+        .iter()
+        .map(|(k, v)| unsafe {
+            (
+                k.duplicate(),
+                variant::new_variant(duplicate_value(variant::value_of(v), deep)),
+            )
+        })
+        .collect();
+    Shared::new(Elements(entries))
+}
+
+unsafe fn entries<'a>(
+    this: *const sys::godot_dictionary,
+) -> &'a mut Elements<(sys::godot_variant, sys::godot_variant)> {
+    &mut *(sys::godot_dictionary::borrow(this).get_mut() as *mut _)
+}
+
+/// Returns the index of the entry for `key`, comparing keys with `hash_compare`.
+unsafe fn position(this: *const sys::godot_dictionary, key: &Value) -> Option<usize> {
+    entries(this)
+        .iter()
+        .position(|(k, _)| k.to_value().hash_compare(key))
+}
+
+fn to_json(value: &Value) -> String {
+    match value {
+        Value::Nil => "null".into(),
+        Value::Bool(b) => b.to_string(),
+        Value::Int(i) => i.to_string(),
+        Value::Real(r) => string::num(*r, -1),
+        Value::Array(_) | Value::IntArray(_) | Value::RealArray(_) | Value::StringArray(_) => {
+            let items = value
+                .to_values()
+                .unwrap()
+                .iter()
+                .map(to_json)
+                .collect::<Vec<_>>();
+            format!("[{}]", items.join(","))
+        }
+        Value::Dictionary(dict) => {
+            let mut entries = dict
+                .iter()
+                .map(|(k, v)| (k.to_value(), v.to_value()))
+                .collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| {
+                if a.less(b) {
+                    std::cmp::Ordering::Less
+                } else if b.less(a) {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            });
+            let items = entries
+                .iter()
+                .map(|(k, v)| {
+                    let key = Value::String(string::repr_from_str(&k.to_string()));
+                    format!("{}:{}", to_json(&key), to_json(v))
+                })
+                .collect::<Vec<_>>();
+            format!("{{{}}}", items.join(","))
+        }
+        value => format!("\"{}\"", string::json_escape(&value.to_string())),
+    }
+}
+
+unsafe extern "C" fn godot_dictionary_new(dest: *mut sys::godot_dictionary) {
+    sys::godot_dictionary::init(dest, Shared::new(Elements::new()));
+}
+
+unsafe extern "C" fn godot_dictionary_new_copy(
+    dest: *mut sys::godot_dictionary,
+    src: *const sys::godot_dictionary,
+) {
+    sys::godot_dictionary::init(dest, (*sys::godot_dictionary::borrow(src)).clone());
+}
+
+unsafe extern "C" fn godot_dictionary_destroy(this: *mut sys::godot_dictionary) {
+    drop(sys::godot_dictionary::take(this));
+}
+
+unsafe extern "C" fn godot_dictionary_size(this: *const sys::godot_dictionary) -> sys::godot_int {
+    entries(this).len() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_dictionary_empty(this: *const sys::godot_dictionary) -> sys::godot_bool {
+    entries(this).is_empty()
+}
+
+unsafe extern "C" fn godot_dictionary_clear(this: *mut sys::godot_dictionary) {
+    entries(this).resize(0);
+}
+
+unsafe extern "C" fn godot_dictionary_has(
+    this: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> sys::godot_bool {
+    position(this, &variant::value_of(key)).is_some()
+}
+
+unsafe extern "C" fn godot_dictionary_has_all(
+    this: *const sys::godot_dictionary,
+    keys: *const sys::godot_array,
+) -> sys::godot_bool {
+    let keys = Value::Array((*sys::godot_array::borrow(keys)).clone());
+    keys.to_values()
+        .unwrap()
+        .iter()
+        .all(|key| position(this, key).is_some())
+}
+
+unsafe extern "C" fn godot_dictionary_erase(
+    this: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) {
+    godot_dictionary_erase_with_return(this, key);
+}
+
+unsafe extern "C" fn godot_dictionary_erase_with_return(
+    this: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> bool {
+    match position(this, &variant::value_of(key)) {
+        Some(idx) => {
+            entries(this).remove(idx).destroy();
+            true
+        }
+        None => false,
+    }
+}
+
+unsafe extern "C" fn godot_dictionary_hash(this: *const sys::godot_dictionary) -> sys::godot_int {
+    Value::Dictionary((*sys::godot_dictionary::borrow(this)).clone()).hash() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_dictionary_keys(this: *const sys::godot_dictionary) -> sys::godot_array {
+    array::new_array(entries(this).iter().map(|(k, _)| k.duplicate()).collect())
+}
+
+unsafe extern "C" fn godot_dictionary_values(
+    this: *const sys::godot_dictionary,
+) -> sys::godot_array {
+    array::new_array(entries(this).iter().map(|(_, v)| v.duplicate()).collect())
+}
+
+unsafe extern "C" fn godot_dictionary_get(
+    this: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> sys::godot_variant {
+    match position(this, &variant::value_of(key)) {
+        Some(idx) => entries(this)[idx].1.duplicate(),
+        None => variant::new_variant(Value::Nil),
+    }
+}
+
+unsafe extern "C" fn godot_dictionary_get_with_default(
+    this: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+    default: *const sys::godot_variant,
+) -> sys::godot_variant {
+    match position(this, &variant::value_of(key)) {
+        Some(idx) => entries(this)[idx].1.duplicate(),
+        None => (*default).duplicate(),
+    }
+}
+
+unsafe extern "C" fn godot_dictionary_set(
+    this: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+    value: *const sys::godot_variant,
+) {
+    let slot = godot_dictionary_operator_index(this, key);
+    sys::godot_variant::replace(slot, (*sys::godot_variant::borrow(value)).clone());
+}
+
+unsafe extern "C" fn godot_dictionary_operator_index(
+    this: *mut sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> *mut sys::godot_variant {
+    let entries = entries(this);
+    let idx = match position(this, &variant::value_of(key)) {
+        Some(idx) => idx,
+        None => {
+            entries.push(((*key).duplicate(), sys::godot_variant::new_default()));
+            entries.len() - 1
+        }
+    };
+    &mut entries[idx].1
+}
+
+unsafe extern "C" fn godot_dictionary_operator_index_const(
+    this: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> *const sys::godot_variant {
+    godot_dictionary_operator_index(this as *mut _, key)
+}
+
+unsafe extern "C" fn godot_dictionary_next(
+    this: *const sys::godot_dictionary,
+    key: *const sys::godot_variant,
+) -> *mut sys::godot_variant {
+    let entries = entries(this);
+    let next = if key.is_null() {
+        0
+    } else {
+        match position(this, &variant::value_of(key)) {
+            Some(idx) => idx + 1,
+            None => return ptr::null_mut(),
+        }
+    };
+    match entries.get_mut(next) {
+        Some((k, _)) => k,
+        None => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn godot_dictionary_operator_equal(
+    this: *const sys::godot_dictionary,
+    b: *const sys::godot_dictionary,
+) -> sys::godot_bool {
+    sys::godot_dictionary::borrow(this).ptr_eq(&sys::godot_dictionary::borrow(b))
+}
+
+unsafe extern "C" fn godot_dictionary_to_json(
+    this: *const sys::godot_dictionary,
+) -> sys::godot_string {
+    let dict = Value::Dictionary((*sys::godot_dictionary::borrow(this)).clone());
+    string::new_string(&to_json(&dict))
+}
+
+pub(super) fn install(api: &mut GodotApi) {
+    install!(api {
+        godot_dictionary_new,
+        godot_dictionary_new_copy,
+        godot_dictionary_destroy,
+        godot_dictionary_size,
+        godot_dictionary_empty,
+        godot_dictionary_clear,
+        godot_dictionary_has,
+        godot_dictionary_has_all,
+        godot_dictionary_erase,
+        godot_dictionary_erase_with_return,
+        godot_dictionary_hash,
+        godot_dictionary_keys,
+        godot_dictionary_values,
+        godot_dictionary_get,
+        godot_dictionary_get_with_default,
+        godot_dictionary_set,
+        godot_dictionary_operator_index,
+        godot_dictionary_operator_index_const,
+        godot_dictionary_next,
+        godot_dictionary_operator_equal,
+        godot_dictionary_to_json,
+    });
+}
@@ -0,0 +1,271 @@
+use super::string;
+use super::variant::from_reals;
+use crate::sys;
+use crate::GodotApi;
+
+use libc::c_char;
+use std::ffi::CStr;
+use std::os::raw::c_int;
+use std::ptr;
+
+unsafe fn c_str(s: *const c_char) -> String {
+    CStr::from_ptr(s).to_string_lossy().into_owned()
+}
+
+unsafe extern "C" fn godot_print(message: *const sys::godot_string) {
+    println!("{}", string::string_of(message));
+}
+
+unsafe extern "C" fn godot_print_warning(
+    description: *const c_char,
+    function: *const c_char,
+    file: *const c_char,
+    line: c_int,
+) {
+    eprintln!(
+        "WARNING: {}: {}\n   At: {}:{}",
+        c_str(function),
+        c_str(description),
+        c_str(file),
+        line
+    );
+}
+
+unsafe extern "C" fn godot_print_error(
+    description: *const c_char,
+    function: *const c_char,
+    file: *const c_char,
+    line: c_int,
+) {
+    eprintln!(
+        "ERROR: {}: {}\n   At: {}:{}",
+        c_str(function),
+        c_str(description),
+        c_str(file),
+        line
+    );
+}
+
+unsafe fn rid_id(rid: *const sys::godot_rid) -> usize {
+    ptr::read_unaligned(rid as *const usize)
+}
+
+unsafe extern "C" fn godot_rid_new(dest: *mut sys::godot_rid) {
+    ptr::write(dest, sys::godot_rid::default());
+}
+
+unsafe extern "C" fn godot_rid_get_id(this: *const sys::godot_rid) -> sys::godot_int {
+    rid_id(this) as sys::godot_int
+}
+
+unsafe extern "C" fn godot_rid_operator_equal(
+    this: *const sys::godot_rid,
+    b: *const sys::godot_rid,
+) -> sys::godot_bool {
+    rid_id(this) == rid_id(b)
+}
+
+unsafe extern "C" fn godot_rid_operator_less(
+    this: *const sys::godot_rid,
+    b: *const sys::godot_rid,
+) -> sys::godot_bool {
+    rid_id(this) < rid_id(b)
+}
+
+unsafe fn real_at<T>(value: *const T, idx: usize) -> sys::godot_real {
+    ptr::read_unaligned((value as *const sys::godot_real).add(idx))
+}
+
+unsafe fn set_real_at<T>(value: *mut T, idx: usize, real: sys::godot_real) {
+    ptr::write_unaligned((value as *mut sys::godot_real).add(idx), real)
+}
+
+unsafe extern "C" fn godot_vector2_get_x(this: *const sys::godot_vector2) -> sys::godot_real {
+    real_at(this, 0)
+}
+
+unsafe extern "C" fn godot_vector2_get_y(this: *const sys::godot_vector2) -> sys::godot_real {
+    real_at(this, 1)
+}
+
+unsafe extern "C" fn godot_vector2_set_x(this: *mut sys::godot_vector2, x: sys::godot_real) {
+    set_real_at(this, 0, x)
+}
+
+unsafe extern "C" fn godot_vector2_set_y(this: *mut sys::godot_vector2, y: sys::godot_real) {
+    set_real_at(this, 1, y)
+}
+
+unsafe extern "C" fn godot_vector3_get_axis(
+    this: *const sys::godot_vector3,
+    axis: sys::godot_vector3_axis,
+) -> sys::godot_real {
+    real_at(this, axis as usize)
+}
+
+unsafe extern "C" fn godot_vector3_set_axis(
+    this: *mut sys::godot_vector3,
+    axis: sys::godot_vector3_axis,
+    val: sys::godot_real,
+) {
+    set_real_at(this, axis as usize, val)
+}
+
+// A `Transform2D` is stored as its X axis, Y axis and origin. The engine transforms a point
+// `p` to `x * p.x + y * p.y + origin`.
+
+unsafe extern "C" fn godot_transform2d_new(
+    dest: *mut sys::godot_transform2d,
+    rot: sys::godot_real,
+    pos: *const sys::godot_vector2,
+) {
+    let (cr, sr) = (rot.cos(), rot.sin());
+    *dest = from_reals(&[cr, sr, -sr, cr, real_at(pos, 0), real_at(pos, 1)]);
+}
+
+unsafe extern "C" fn godot_transform2d_new_axis_origin(
+    dest: *mut sys::godot_transform2d,
+    x_axis: *const sys::godot_vector2,
+    y_axis: *const sys::godot_vector2,
+    origin: *const sys::godot_vector2,
+) {
+    *dest = from_reals(&[
+        real_at(x_axis, 0),
+        real_at(x_axis, 1),
+        real_at(y_axis, 0),
+        real_at(y_axis, 1),
+        real_at(origin, 0),
+        real_at(origin, 1),
+    ]);
+}
+
+unsafe extern "C" fn godot_transform2d_get_origin(
+    this: *const sys::godot_transform2d,
+) -> sys::godot_vector2 {
+    from_reals(&[real_at(this, 4), real_at(this, 5)])
+}
+
+unsafe extern "C" fn godot_transform2d_get_rotation(
+    this: *const sys::godot_transform2d,
+) -> sys::godot_real {
+    // The engine orthonormalizes the basis, which leaves the direction of the X axis as it
+    // is, and flips it along Y if the determinant is negative.
+    let det = real_at(this, 0) * real_at(this, 3) - real_at(this, 1) * real_at(this, 2);
+    let y = if det < 0.0 {
+        -real_at(this, 1)
+    } else {
+        real_at(this, 1)
+    };
+    y.atan2(real_at(this, 0))
+}
+
+unsafe extern "C" fn godot_transform2d_xform_vector2(
+    this: *const sys::godot_transform2d,
+    v: *const sys::godot_vector2,
+) -> sys::godot_vector2 {
+    let (x, y) = (real_at(v, 0), real_at(v, 1));
+    from_reals(&[
+        real_at(this, 0) * x + real_at(this, 2) * y + real_at(this, 4),
+        real_at(this, 1) * x + real_at(this, 3) * y + real_at(this, 5),
+    ])
+}
+
+unsafe extern "C" fn godot_transform2d_xform_inv_vector2(
+    this: *const sys::godot_transform2d,
+    v: *const sys::godot_vector2,
+) -> sys::godot_vector2 {
+    let x = real_at(v, 0) - real_at(this, 4);
+    let y = real_at(v, 1) - real_at(this, 5);
+    from_reals(&[
+        real_at(this, 0) * x + real_at(this, 1) * y,
+        real_at(this, 2) * x + real_at(this, 3) * y,
+    ])
+}
+
+// A `Transform` is stored as the rows of its basis, followed by its origin. The axes are the
+// columns of the basis.
+
+unsafe extern "C" fn godot_transform_new_with_axis_origin(
+    dest: *mut sys::godot_transform,
+    x_axis: *const sys::godot_vector3,
+    y_axis: *const sys::godot_vector3,
+    z_axis: *const sys::godot_vector3,
+    origin: *const sys::godot_vector3,
+) {
+    let mut reals = Vec::with_capacity(12);
+    for row in 0..3 {
+        reals.extend(&[
+            real_at(x_axis, row),
+            real_at(y_axis, row),
+            real_at(z_axis, row),
+        ]);
+    }
+    reals.extend(&[real_at(origin, 0), real_at(origin, 1), real_at(origin, 2)]);
+    *dest = from_reals(&reals);
+}
+
+unsafe extern "C" fn godot_transform_get_origin(
+    this: *const sys::godot_transform,
+) -> sys::godot_vector3 {
+    from_reals(&[real_at(this, 9), real_at(this, 10), real_at(this, 11)])
+}
+
+unsafe extern "C" fn godot_transform_xform_vector3(
+    this: *const sys::godot_transform,
+    v: *const sys::godot_vector3,
+) -> sys::godot_vector3 {
+    let reals = (0..3)
+        .map(|row| {
+            (0..3)
+                .map(|col| real_at(this, row * 3 + col) * real_at(v, col))
+                .sum::<sys::godot_real>()
+                + real_at(this, 9 + row)
+        })
+        .collect::<Vec<_>>();
+    from_reals(&reals)
+}
+
+unsafe extern "C" fn godot_transform_xform_inv_vector3(
+    this: *const sys::godot_transform,
+    v: *const sys::godot_vector3,
+) -> sys::godot_vector3 {
+    let local = (0..3)
+        .map(|idx| real_at(v, idx) - real_at(this, 9 + idx))
+        .collect::<Vec<_>>();
+    let reals = (0..3)
+        .map(|col| {
+            (0..3)
+                .map(|row| real_at(this, row * 3 + col) * local[row])
+                .sum::<sys::godot_real>()
+        })
+        .collect::<Vec<_>>();
+    from_reals(&reals)
+}
+
+pub(super) fn install(api: &mut GodotApi) {
+    install!(api {
+        godot_print,
+        godot_print_warning,
+        godot_print_error,
+        godot_rid_new,
+        godot_rid_get_id,
+        godot_rid_operator_equal,
+        godot_rid_operator_less,
+        godot_vector2_get_x,
+        godot_vector2_get_y,
+        godot_vector2_set_x,
+        godot_vector2_set_y,
+        godot_vector3_get_axis,
+        godot_vector3_set_axis,
+        godot_transform2d_new,
+        godot_transform2d_new_axis_origin,
+        godot_transform2d_get_origin,
+        godot_transform2d_get_rotation,
+        godot_transform2d_xform_vector2,
+        godot_transform2d_xform_inv_vector2,
+        godot_transform_new_with_axis_origin,
+        godot_transform_get_origin,
+        godot_transform_xform_vector3,
+        godot_transform_xform_inv_vector3,
+    });
+}
@@ -0,0 +1,217 @@
+use super::string;
+use super::{print_error, Opaque};
+use crate::sys;
+use crate::GodotApi;
+
+use std::sync::Arc;
+
+#[derive(PartialEq)]
+pub(crate) struct NodePathData {
+    absolute: bool,
+    names: Vec<String>,
+    subnames: Vec<String>,
+}
+
+/// Immutable path data, or `None` for the empty path.
+pub(crate) type NodePathRepr = Option<Arc<NodePathData>>;
+
+unsafe impl Opaque for sys::godot_node_path {
+    type Repr = NodePathRepr;
+}
+
+/// Parses a path like the engine's `NodePath(const String &)`.
+pub(crate) fn parse(path: &str) -> NodePathRepr {
+    if path.is_empty() {
+        return None;
+    }
+
+    let absolute = path.starts_with('/');
+    let (path, subpath) = match path.find(':') {
+        Some(pos) => (&path[..pos], Some(&path[pos + 1..])),
+        None => (path, None),
+    };
+
+    let mut subnames = Vec::new();
+    if let Some(subpath) = subpath {
+        let parts = subpath.split(':').collect::<Vec<_>>();
+        for (i, part) in parts.iter().enumerate() {
+            if part.is_empty() {
+                if i == parts.len() - 1 {
+                    continue;
+                }
+                print_error("NodePath::NodePath", "Invalid NodePath.");
+                return None;
+            }
+            subnames.push(part.to_string());
+        }
+    }
+
+    let names = path
+        .split('/')
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    if names.is_empty() && !absolute && subnames.is_empty() {
+        return None;
+    }
+
+    Some(Arc::new(NodePathData {
+        absolute,
+        names,
+        subnames,
+    }))
+}
+
+/// Formats a path like the engine's `NodePath::operator String`.
+pub(crate) fn repr_to_string(repr: &NodePathRepr) -> String {
+    let data = match repr {
+        Some(data) => data,
+        None => return String::new(),
+    };
+    let mut path = if data.absolute {
+        String::from("/")
+    } else {
+        String::new()
+    };
+    path.push_str(&data.names.join("/"));
+    for subname in &data.subnames {
+        path.push(':');
+        path.push_str(subname);
+    }
+    path
+}
+
+unsafe fn data_of<'a>(this: *const sys::godot_node_path) -> Option<&'a NodePathData> {
+    sys::godot_node_path::borrow(this)
+        .as_ref()
+        .map(|data| &*(&**data as *const NodePathData))
+}
+
+unsafe extern "C" fn godot_node_path_new(
+    dest: *mut sys::godot_node_path,
+    from: *const sys::godot_string,
+) {
+    sys::godot_node_path::init(dest, parse(&string::string_of(from)));
+}
+
+unsafe extern "C" fn godot_node_path_new_copy(
+    dest: *mut sys::godot_node_path,
+    src: *const sys::godot_node_path,
+) {
+    sys::godot_node_path::init(dest, (*sys::godot_node_path::borrow(src)).clone());
+}
+
+unsafe extern "C" fn godot_node_path_destroy(this: *mut sys::godot_node_path) {
+    drop(sys::godot_node_path::take(this));
+}
+
+unsafe extern "C" fn godot_node_path_as_string(
+    this: *const sys::godot_node_path,
+) -> sys::godot_string {
+    string::new_string(&repr_to_string(&sys::godot_node_path::borrow(this)))
+}
+
+unsafe extern "C" fn godot_node_path_is_absolute(
+    this: *const sys::godot_node_path,
+) -> sys::godot_bool {
+    data_of(this).map_or(false, |data| data.absolute)
+}
+
+unsafe extern "C" fn godot_node_path_get_name_count(
+    this: *const sys::godot_node_path,
+) -> sys::godot_int {
+    data_of(this).map_or(0, |data| data.names.len() as sys::godot_int)
+}
+
+fn get_part(function: &str, parts: Option<&Vec<String>>, idx: sys::godot_int) -> sys::godot_string {
+    match parts.and_then(|parts| parts.get(idx as usize)) {
+        Some(part) if idx >= 0 => string::new_string(part),
+        _ => {
+            print_error(function, &format!("Index {} is out of bounds.", idx));
+            string::new_string("")
+        }
+    }
+}
+
+unsafe extern "C" fn godot_node_path_get_name(
+    this: *const sys::godot_node_path,
+    idx: sys::godot_int,
+) -> sys::godot_string {
+    get_part(
+        "NodePath::get_name",
+        data_of(this).map(|data| &data.names),
+        idx,
+    )
+}
+
+unsafe extern "C" fn godot_node_path_get_subname_count(
+    this: *const sys::godot_node_path,
+) -> sys::godot_int {
+    data_of(this).map_or(0, |data| data.subnames.len() as sys::godot_int)
+}
+
+unsafe extern "C" fn godot_node_path_get_subname(
+    this: *const sys::godot_node_path,
+    idx: sys::godot_int,
+) -> sys::godot_string {
+    get_part(
+        "NodePath::get_subname",
+        data_of(this).map(|data| &data.subnames),
+        idx,
+    )
+}
+
+unsafe extern "C" fn godot_node_path_get_concatenated_subnames(
+    this: *const sys::godot_node_path,
+) -> sys::godot_string {
+    let subnames = data_of(this).map(|data| data.subnames.join(":"));
+    string::new_string(&subnames.unwrap_or_default())
+}
+
+unsafe extern "C" fn godot_node_path_is_empty(
+    this: *const sys::godot_node_path,
+) -> sys::godot_bool {
+    data_of(this).is_none()
+}
+
+unsafe extern "C" fn godot_node_path_operator_equal(
+    this: *const sys::godot_node_path,
+    b: *const sys::godot_node_path,
+) -> sys::godot_bool {
+    data_of(this) == data_of(b)
+}
+
+unsafe extern "C" fn godot_node_path_get_as_property_path(
+    this: *const sys::godot_node_path,
+) -> sys::godot_node_path {
+    let data = match data_of(this) {
+        Some(data) if !data.names.is_empty() => data,
+        _ => return sys::godot_node_path::from_repr((*sys::godot_node_path::borrow(this)).clone()),
+    };
+    let mut subnames = vec![data.names.join("/")];
+    subnames.extend(data.subnames.iter().cloned());
+    sys::godot_node_path::from_repr(Some(Arc::new(NodePathData {
+        absolute: false,
+        names: Vec::new(),
+        subnames,
+    })))
+}
+
+pub(super) fn install(api: &mut GodotApi) {
+    install!(api {
+        godot_node_path_new,
+        godot_node_path_new_copy,
+        godot_node_path_destroy,
+        godot_node_path_as_string,
+        godot_node_path_is_absolute,
+        godot_node_path_get_name_count,
+        godot_node_path_get_name,
+        godot_node_path_get_subname_count,
+        godot_node_path_get_subname,
+        godot_node_path_get_concatenated_subnames,
+        godot_node_path_is_empty,
+        godot_node_path_operator_equal,
+        godot_node_path_get_as_property_path,
+    });
+}
@@ -0,0 +1,321 @@
+use super::string;
+use super::variant::{from_reals, reals_of, value_of, Value};
+use super::{Element, Elements, Opaque, Shared};
+use crate::sys;
+use crate::GodotApi;
+
+/// Operators in the order of `godot_variant_operator`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Op {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Negate,
+    Positive,
+    Module,
+    StringConcat,
+    ShiftLeft,
+    ShiftRight,
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitNegate,
+    And,
+    Or,
+    Xor,
+    Not,
+    In,
+}
+
+const OPS: [Op; 25] = [
+    Op::Equal,
+    Op::NotEqual,
+    Op::Less,
+    Op::LessEqual,
+    Op::Greater,
+    Op::GreaterEqual,
+    Op::Add,
+    Op::Subtract,
+    Op::Multiply,
+    Op::Divide,
+    Op::Negate,
+    Op::Positive,
+    Op::Module,
+    Op::StringConcat,
+    Op::ShiftLeft,
+    Op::ShiftRight,
+    Op::BitAnd,
+    Op::BitOr,
+    Op::BitXor,
+    Op::BitNegate,
+    Op::And,
+    Op::Or,
+    Op::Xor,
+    Op::Not,
+    Op::In,
+];
+
+fn is_number(value: &Value) -> bool {
+    matches!(value, Value::Int(_) | Value::Real(_))
+}
+
+/// Applies `f` to each pair of components of two geometry values of the same type.
+fn zip_reals<T: Copy + Default>(
+    a: &T,
+    b: &T,
+    f: impl Fn(sys::godot_real, sys::godot_real) -> sys::godot_real,
+) -> T {
+    let reals = reals_of(a)
+        .into_iter()
+        .zip(reals_of(b))
+        .map(|(a, b)| f(a, b))
+        .collect::<Vec<_>>();
+    from_reals(&reals)
+}
+
+/// Applies `f` to each component of a geometry value.
+fn map_reals<T: Copy + Default>(a: &T, f: impl Fn(sys::godot_real) -> sys::godot_real) -> T {
+    let reals = reals_of(a).into_iter().map(f).collect::<Vec<_>>();
+    from_reals(&reals)
+}
+
+/// Evaluates arithmetic on two values of the same component-wise type, or on such a value
+/// and a number.
+fn componentwise(
+    a: &Value,
+    b: &Value,
+    f: impl Fn(sys::godot_real, sys::godot_real) -> sys::godot_real,
+) -> Option<Value> {
+    macro_rules! dispatch {
+        ($($Variant:ident),*) => {
+            match (a, b) {
+                $(
+                    (Value::$Variant(a), Value::$Variant(b)) => Some(Value::$Variant(zip_reals(a, b, f))),
+                    (Value::$Variant(a), b) if is_number(b) => {
+                        let b = b.as_real() as sys::godot_real;
+                        Some(Value::$Variant(map_reals(a, |a| f(a, b))))
+                    }
+                )*
+                _ => None,
+            }
+        };
+    }
+
+    dispatch!(Vector2, Vector3, Quat, Color)
+}
+
+fn quat_mul(a: &sys::godot_quat, b: &sys::godot_quat) -> sys::godot_quat {
+    let (a, b) = (reals_of(a), reals_of(b));
+    from_reals(&[
+        a[3] * b[0] + a[0] * b[3] + a[1] * b[2] - a[2] * b[1],
+        a[3] * b[1] + a[1] * b[3] + a[2] * b[0] - a[0] * b[2],
+        a[3] * b[2] + a[2] * b[3] + a[0] * b[1] - a[1] * b[0],
+        a[3] * b[3] - a[0] * b[0] - a[1] * b[1] - a[2] * b[2],
+    ])
+}
+
+fn compare_numbers(op: Op, a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => match op {
+            Op::Equal => a == b,
+            Op::NotEqual => a != b,
+            Op::Less => a < b,
+            Op::LessEqual => a <= b,
+            Op::Greater => a > b,
+            _ => a >= b,
+        },
+        _ => {
+            let (a, b) = (a.as_real(), b.as_real());
+            match op {
+                Op::Equal => a == b,
+                Op::NotEqual => a != b,
+                Op::Less => a < b,
+                Op::LessEqual => a <= b,
+                Op::Greater => a > b,
+                _ => a >= b,
+            }
+        }
+    }
+}
+
+fn equal(a: &Value, b: &Value) -> Option<bool> {
+    match (a, b) {
+        (Value::Nil, Value::Nil) => Some(true),
+        (Value::Nil, Value::Object(obj)) | (Value::Object(obj), Value::Nil) => Some(obj.is_null()),
+        (Value::Nil, _) | (_, Value::Nil) => Some(false),
+        (a, b) if is_number(a) && is_number(b) => Some(compare_numbers(Op::Equal, a, b)),
+        (a, b) if a.variant_type() == b.variant_type() => Some(a.equals(b)),
+        _ => None,
+    }
+}
+
+fn contains(container: &Value, item: &Value) -> Option<bool> {
+    match (container, item) {
+        (Value::String(s), Value::String(sub)) => {
+            Some(string::repr_to_string(s).contains(&string::repr_to_string(sub)))
+        }
+        (Value::Dictionary(dict), key) => Some(
+            dict.iter()
+                .any(|(k, _)| unsafe { value_of(k) }.hash_compare(key)),
+        ),
+        (Value::Array(_), item) => Some(
+            container
+                .to_values()
+                .unwrap()
+                .iter()
+                .any(|v| v.equals(item)),
+        ),
+        (pool, item) => pool
+            .to_values()
+            .map(|values| values.iter().any(|v| equal(v, item).unwrap_or(false))),
+    }
+}
+
+/// Evaluates an operator like the engine's `Variant::evaluate`, returning `None` for invalid
+/// operands.
+///
+/// Only operators on numbers, strings, vectors, quaternions, colors and arrays are
+/// implemented. Other operands, e.g. for transform multiplication or string formatting,
+/// are treated as invalid.
+fn evaluate(op: Op, a: &Value, b: &Value) -> Option<Value> {
+    let value = match op {
+        Op::Equal => Value::Bool(equal(a, b)?),
+        Op::NotEqual => Value::Bool(!equal(a, b)?),
+        Op::Less | Op::LessEqual | Op::Greater | Op::GreaterEqual => {
+            if is_number(a) && is_number(b) {
+                Value::Bool(compare_numbers(op, a, b))
+            } else {
+                Value::Bool(match op {
+                    Op::Less => a.try_less(b)?,
+                    Op::LessEqual => !b.try_less(a)?,
+                    Op::Greater => b.try_less(a)?,
+                    _ => !a.try_less(b)?,
+                })
+            }
+        }
+        Op::Add | Op::Subtract | Op::Multiply | Op::Divide | Op::Module => arithmetic(op, a, b)?,
+        Op::Negate => match a {
+            Value::Int(i) => Value::Int(i.wrapping_neg()),
+            Value::Real(r) => Value::Real(-r),
+            Value::Vector2(v) => Value::Vector2(map_reals(v, |r| -r)),
+            Value::Vector3(v) => Value::Vector3(map_reals(v, |r| -r)),
+            Value::Plane(v) => Value::Plane(map_reals(v, |r| -r)),
+            Value::Quat(v) => Value::Quat(map_reals(v, |r| -r)),
+            Value::Color(v) => Value::Color(map_reals(v, |r| 1.0 - r)),
+            _ => return None,
+        },
+        Op::Positive => match a {
+            Value::Int(_)
+            | Value::Real(_)
+            | Value::Vector2(_)
+            | Value::Vector3(_)
+            | Value::Plane(_)
+            | Value::Quat(_) => a.clone(),
+            _ => return None,
+        },
+        Op::StringConcat => Value::String(string::repr_from_str(&format!("{}{}", a, b))),
+        Op::ShiftLeft | Op::ShiftRight | Op::BitAnd | Op::BitOr | Op::BitXor => {
+            let (a, b) = match (a, b) {
+                (Value::Int(a), Value::Int(b)) => (*a, *b),
+                _ => return None,
+            };
+            Value::Int(match op {
+                Op::ShiftLeft if (0..64).contains(&b) => a << b,
+                Op::ShiftRight if (0..64).contains(&b) => a >> b,
+                Op::BitAnd => a & b,
+                Op::BitOr => a | b,
+                Op::BitXor => a ^ b,
+                _ => return None,
+            })
+        }
+        Op::BitNegate => match a {
+            Value::Int(i) => Value::Int(!i),
+            _ => return None,
+        },
+        Op::And => Value::Bool(!a.is_zero() && !b.is_zero()),
+        Op::Or => Value::Bool(!a.is_zero() || !b.is_zero()),
+        Op::Xor => Value::Bool(a.is_zero() != b.is_zero()),
+        Op::Not => Value::Bool(a.is_zero()),
+        Op::In => Value::Bool(contains(b, a)?),
+    };
+    Some(value)
+}
+
+fn arithmetic(op: Op, a: &Value, b: &Value) -> Option<Value> {
+    let value = match (a, b) {
+        (Value::Int(a), Value::Int(b)) => Value::Int(match op {
+            Op::Add => a.wrapping_add(*b),
+            Op::Subtract => a.wrapping_sub(*b),
+            Op::Multiply => a.wrapping_mul(*b),
+            Op::Divide if *b != 0 => a.wrapping_div(*b),
+            Op::Module if *b != 0 => a.wrapping_rem(*b),
+            _ => return None,
+        }),
+        (a, b) if is_number(a) && is_number(b) => {
+            let (a, b) = (a.as_real(), b.as_real());
+            Value::Real(match op {
+                Op::Add => a + b,
+                Op::Subtract => a - b,
+                Op::Multiply => a * b,
+                Op::Divide => a / b,
+                _ => return None,
+            })
+        }
+        (Value::String(a), Value::String(b)) if op == Op::Add => {
+            let s = string::repr_to_string(a) + &string::repr_to_string(b);
+            Value::String(string::repr_from_str(&s))
+        }
+        (Value::Array(a), Value::Array(b)) if op == Op::Add => {
+            let elements = a.iter().chain(b.iter()).map(Element::duplicate).collect();
+            Value::Array(Shared::new(Elements(elements)))
+        }
+        (a, b) if is_number(a) && op == Op::Multiply => match b {
+            Value::Vector2(_) | Value::Vector3(_) => return arithmetic(op, b, a),
+            _ => return None,
+        },
+        (Value::Quat(a), Value::Quat(b)) if op == Op::Multiply => Value::Quat(quat_mul(a, b)),
+        (Value::Quat(_), b) if !is_number(b) && (op == Op::Multiply || op == Op::Divide) => {
+            return None
+        }
+        (a, b) => match op {
+            Op::Add | Op::Subtract if a.variant_type() != b.variant_type() => return None,
+            Op::Add => componentwise(a, b, |a, b| a + b)?,
+            Op::Subtract => componentwise(a, b, |a, b| a - b)?,
+            Op::Multiply => componentwise(a, b, |a, b| a * b)?,
+            Op::Divide => componentwise(a, b, |a, b| a / b)?,
+            _ => return None,
+        },
+    };
+    Some(value)
+}
+
+unsafe extern "C" fn godot_variant_evaluate(
+    op: sys::godot_variant_operator,
+    a: *const sys::godot_variant,
+    b: *const sys::godot_variant,
+    ret: *mut sys::godot_variant,
+    valid: *mut sys::godot_bool,
+) {
+    let result = OPS
+        .get(op as usize)
+        .and_then(|&op| evaluate(op, &value_of(a), &value_of(b)));
+    *valid = result.is_some();
+    let repr = match result {
+        None | Some(Value::Nil) => None,
+        Some(value) => Some(Box::new(value)),
+    };
+    sys::godot_variant::replace(ret, repr);
+}
+
+pub(super) fn install(api: &mut GodotApi) {
+    install!(api {
+        godot_variant_evaluate,
+    });
+}
@@ -0,0 +1,466 @@
+use super::variant::{self, FromValue, ToValue, Value};
+use super::{print_error, Element, Elements, Opaque, Shared};
+use crate::sys;
+use crate::GodotApi;
+
+use std::ptr::NonNull;
+
+/// Copy-on-write elements, or `None` for an empty pool array.
+pub(crate) type PoolRepr<T> = Option<Shared<Elements<T>>>;
+
+/// The engine types of a pool array and its accesses.
+pub(crate) trait Pool: Opaque<Repr = PoolRepr<<Self as Pool>::Elem>> {
+    type Elem: Element + ToValue + FromValue;
+    /// The type elements are passed as, either by value or by pointer.
+    type Data;
+    type Read;
+    type Write;
+
+    unsafe fn elem_from_data(data: Self::Data) -> Self::Elem;
+}
+
+macro_rules! impl_pool {
+    ($($Pool:ident, $Read:ident, $Write:ident: $Elem:ty, by $by:ident;)*) => {
+        $(
+            unsafe impl Opaque for sys::$Pool {
+                type Repr = PoolRepr<$Elem>;
+            }
+
+            impl Pool for sys::$Pool {
+                type Elem = $Elem;
+                type Data = impl_pool!(@data $by $Elem);
+                type Read = sys::$Read;
+                type Write = sys::$Write;
+
+                unsafe fn elem_from_data(data: Self::Data) -> Self::Elem {
+                    impl_pool!(@elem $by data)
+                }
+            }
+        )*
+    };
+    (@data value $Elem:ty) => { $Elem };
+    (@data pointer $Elem:ty) => { *const $Elem };
+    (@elem value $data:ident) => { $data };
+    (@elem pointer $data:ident) => { (*$data).duplicate() };
+}
+
+impl_pool! {
+    godot_pool_byte_array, godot_pool_byte_array_read_access, godot_pool_byte_array_write_access:
+        u8, by value;
+    godot_pool_int_array, godot_pool_int_array_read_access, godot_pool_int_array_write_access:
+        sys::godot_int, by value;
+    godot_pool_real_array, godot_pool_real_array_read_access, godot_pool_real_array_write_access:
+        sys::godot_real, by value;
+    godot_pool_string_array, godot_pool_string_array_read_access, godot_pool_string_array_write_access:
+        sys::godot_string, by pointer;
+    godot_pool_vector2_array, godot_pool_vector2_array_read_access, godot_pool_vector2_array_write_access:
+        sys::godot_vector2, by pointer;
+    godot_pool_vector3_array, godot_pool_vector3_array_read_access, godot_pool_vector3_array_write_access:
+        sys::godot_vector3, by pointer;
+    godot_pool_color_array, godot_pool_color_array_read_access, godot_pool_color_array_write_access:
+        sys::godot_color, by pointer;
+}
+
+/// Creates a new pool string array from the given strings.
+pub(crate) fn new_string_array(strings: Vec<sys::godot_string>) -> sys::godot_pool_string_array {
+    sys::godot_pool_string_array::from_repr(from_vec(strings))
+}
+
+pub(crate) fn from_vec<T: Element>(elements: Vec<T>) -> PoolRepr<T> {
+    if elements.is_empty() {
+        None
+    } else {
+        Some(Shared::new(Elements(elements)))
+    }
+}
+
+/// Returns the elements of a pool array for reading.
+pub(crate) unsafe fn elements_of<'a, P: Pool>(this: *const P) -> &'a [P::Elem] {
+    match &*P::borrow(this) {
+        Some(shared) => &*(&shared[..] as *const [P::Elem]),
+        None => &[],
+    }
+}
+
+/// Returns the unique elements of a pool array for writing.
+unsafe fn elements_mut<'a, P: Pool>(this: *mut P) -> &'a mut Elements<P::Elem> {
+    let mut repr = P::take(this).unwrap_or_else(|| Shared::new(Elements::new()));
+    repr.make_unique();
+    let elements = repr.get_mut() as *mut Elements<P::Elem>;
+    P::init(this, Some(repr));
+    &mut *elements
+}
+
+/// Read or write access to the elements of a pool array.
+struct Access<T: Element>(PoolRepr<T>);
+
+impl<T: Element> Access<T> {
+    fn ptr(&self) -> *mut T {
+        match &self.0 {
+            Some(shared) => unsafe { shared.get_mut().as_mut_ptr() },
+            None => NonNull::dangling().as_ptr(),
+        }
+    }
+}
+
+unsafe extern "C" fn new<P: Pool>(dest: *mut P) {
+    P::init(dest, None);
+}
+
+unsafe extern "C" fn new_copy<P: Pool>(dest: *mut P, src: *const P) {
+    P::init(dest, (*P::borrow(src)).clone());
+}
+
+unsafe extern "C" fn new_with_array<P: Pool>(dest: *mut P, a: *const sys::godot_array) {
+    let value = Value::Array((*sys::godot_array::borrow(a)).clone());
+    P::init(dest, variant::to_pool(&value));
+}
+
+unsafe extern "C" fn append<P: Pool>(this: *mut P, data: P::Data) {
+    elements_mut(this).push(P::elem_from_data(data));
+}
+
+unsafe extern "C" fn append_array<P: Pool>(this: *mut P, array: *const P) {
+    let other = elements_of(array)
+        .iter()
+        .map(Element::duplicate)
+        .collect::<Vec<_>>();
+    elements_mut(this).extend(other);
+}
+
+unsafe extern "C" fn insert<P: Pool>(
+    this: *mut P,
+    idx: sys::godot_int,
+    data: P::Data,
+) -> sys::godot_error {
+    let len = elements_of(this).len();
+    if idx < 0 || idx as usize > len {
+        print_error("PoolVector::insert", "Index out of bounds.");
+        return sys::godot_error_GODOT_ERR_INVALID_PARAMETER;
+    }
+    elements_mut(this).insert(idx as usize, P::elem_from_data(data));
+    sys::godot_error_GODOT_OK
+}
+
+unsafe extern "C" fn invert<P: Pool>(this: *mut P) {
+    elements_mut(this).reverse();
+}
+
+unsafe extern "C" fn remove<P: Pool>(this: *mut P, idx: sys::godot_int) {
+    let len = elements_of(this).len();
+    if idx < 0 || idx as usize >= len {
+        print_error("PoolVector::remove", "Index out of bounds.");
+        return;
+    }
+    elements_mut(this).remove(idx as usize).destroy();
+}
+
+unsafe extern "C" fn resize<P: Pool>(this: *mut P, size: sys::godot_int) {
+    elements_mut(this).resize(size.max(0) as usize);
+}
+
+unsafe extern "C" fn read<P: Pool>(this: *const P) -> *mut P::Read {
+    let access = Access((*P::borrow(this)).clone());
+    Box::into_raw(Box::new(access)) as *mut P::Read
+}
+
+unsafe extern "C" fn write<P: Pool>(this: *mut P) -> *mut P::Write {
+    if elements_of(this).is_empty() {
+        return Box::into_raw(Box::new(Access::<P::Elem>(None))) as *mut P::Write;
+    }
+    elements_mut(this);
+    let access = Access((*P::borrow(this)).clone());
+    Box::into_raw(Box::new(access)) as *mut P::Write
+}
+
+unsafe extern "C" fn set<P: Pool>(this: *mut P, idx: sys::godot_int, data: P::Data) {
+    let len = elements_of(this).len();
+    if idx < 0 || idx as usize >= len {
+        print_error("PoolVector::set", "Index out of bounds.");
+        return;
+    }
+    let elements = elements_mut(this);
+    elements[idx as usize].destroy();
+    elements[idx as usize] = P::elem_from_data(data);
+}
+
+unsafe extern "C" fn get<P: Pool>(this: *const P, idx: sys::godot_int) -> P::Elem {
+    let elements = elements_of(this);
+    if idx < 0 || idx as usize >= elements.len() {
+        print_error("PoolVector::get", "Index out of bounds.");
+        return P::Elem::new_default();
+    }
+    elements[idx as usize].duplicate()
+}
+
+unsafe extern "C" fn size<P: Pool>(this: *const P) -> sys::godot_int {
+    elements_of(this).len() as sys::godot_int
+}
+
+unsafe extern "C" fn destroy<P: Pool>(this: *mut P) {
+    drop(P::take(this));
+}
+
+unsafe extern "C" fn access_copy<T: Element, A>(access: *const A) -> *mut A {
+    let access = &*(access as *const Access<T>);
+    Box::into_raw(Box::new(Access(access.0.clone()))) as *mut A
+}
+
+unsafe extern "C" fn access_operator_assign<T: Element, A>(access: *mut A, other: *mut A) {
+    let other = (*(other as *const Access<T>)).0.clone();
+    (*(access as *mut Access<T>)).0 = other;
+}
+
+unsafe extern "C" fn access_destroy<T: Element, A>(access: *mut A) {
+    drop(Box::from_raw(access as *mut Access<T>));
+}
+
+unsafe extern "C" fn read_access_ptr<P: Pool>(access: *const P::Read) -> *const P::Elem {
+    (*(access as *const Access<P::Elem>)).ptr()
+}
+
+unsafe extern "C" fn write_access_ptr<P: Pool>(access: *const P::Write) -> *mut P::Elem {
+    (*(access as *const Access<P::Elem>)).ptr()
+}
+
+macro_rules! install_pool {
+    ($api:ident, $Pool:ty {
+        $new:ident, $new_copy:ident, $new_with_array:ident, $append:ident, $append_array:ident,
+        $insert:ident, $invert:ident, $push_back:ident, $remove:ident, $resize:ident,
+        $read:ident, $write:ident, $set:ident, $get:ident, $size:ident, $destroy:ident,
+        $read_copy:ident, $read_ptr:ident, $read_assign:ident, $read_destroy:ident,
+        $write_copy:ident, $write_ptr:ident, $write_assign:ident, $write_destroy:ident,
+    }) => {
+        $api.$new = new::<$Pool>;
+        $api.$new_copy = new_copy::<$Pool>;
+        $api.$new_with_array = new_with_array::<$Pool>;
+        $api.$append = append::<$Pool>;
+        $api.$append_array = append_array::<$Pool>;
+        $api.$insert = insert::<$Pool>;
+        $api.$invert = invert::<$Pool>;
+        $api.$push_back = append::<$Pool>;
+        $api.$remove = remove::<$Pool>;
+        $api.$resize = resize::<$Pool>;
+        $api.$read = read::<$Pool>;
+        $api.$write = write::<$Pool>;
+        $api.$set = set::<$Pool>;
+        $api.$get = get::<$Pool>;
+        $api.$size = size::<$Pool>;
+        $api.$destroy = destroy::<$Pool>;
+        $api.$read_copy = access_copy::<<$Pool as Pool>::Elem, <$Pool as Pool>::Read>;
+        $api.$read_ptr = read_access_ptr::<$Pool>;
+        $api.$read_assign = access_operator_assign::<<$Pool as Pool>::Elem, <$Pool as Pool>::Read>;
+        $api.$read_destroy = access_destroy::<<$Pool as Pool>::Elem, <$Pool as Pool>::Read>;
+        $api.$write_copy = access_copy::<<$Pool as Pool>::Elem, <$Pool as Pool>::Write>;
+        $api.$write_ptr = write_access_ptr::<$Pool>;
+        $api.$write_assign =
+            access_operator_assign::<<$Pool as Pool>::Elem, <$Pool as Pool>::Write>;
+        $api.$write_destroy = access_destroy::<<$Pool as Pool>::Elem, <$Pool as Pool>::Write>;
+    };
+}
+
+pub(super) fn install(api: &mut GodotApi) {
+    install_pool!(
+        api,
+        sys::godot_pool_byte_array {
+            godot_pool_byte_array_new,
+            godot_pool_byte_array_new_copy,
+            godot_pool_byte_array_new_with_array,
+            godot_pool_byte_array_append,
+            godot_pool_byte_array_append_array,
+            godot_pool_byte_array_insert,
+            godot_pool_byte_array_invert,
+            godot_pool_byte_array_push_back,
+            godot_pool_byte_array_remove,
+            godot_pool_byte_array_resize,
+            godot_pool_byte_array_read,
+            godot_pool_byte_array_write,
+            godot_pool_byte_array_set,
+            godot_pool_byte_array_get,
+            godot_pool_byte_array_size,
+            godot_pool_byte_array_destroy,
+            godot_pool_byte_array_read_access_copy,
+            godot_pool_byte_array_read_access_ptr,
+            godot_pool_byte_array_read_access_operator_assign,
+            godot_pool_byte_array_read_access_destroy,
+            godot_pool_byte_array_write_access_copy,
+            godot_pool_byte_array_write_access_ptr,
+            godot_pool_byte_array_write_access_operator_assign,
+            godot_pool_byte_array_write_access_destroy,
+        }
+    );
+    install_pool!(
+        api,
+        sys::godot_pool_int_array {
+            godot_pool_int_array_new,
+            godot_pool_int_array_new_copy,
+            godot_pool_int_array_new_with_array,
+            godot_pool_int_array_append,
+            godot_pool_int_array_append_array,
+            godot_pool_int_array_insert,
+            godot_pool_int_array_invert,
+            godot_pool_int_array_push_back,
+            godot_pool_int_array_remove,
+            godot_pool_int_array_resize,
+            godot_pool_int_array_read,
+            godot_pool_int_array_write,
+            godot_pool_int_array_set,
+            godot_pool_int_array_get,
+            godot_pool_int_array_size,
+            godot_pool_int_array_destroy,
+            godot_pool_int_array_read_access_copy,
+            godot_pool_int_array_read_access_ptr,
+            godot_pool_int_array_read_access_operator_assign,
+            godot_pool_int_array_read_access_destroy,
+            godot_pool_int_array_write_access_copy,
+            godot_pool_int_array_write_access_ptr,
+            godot_pool_int_array_write_access_operator_assign,
+            godot_pool_int_array_write_access_destroy,
+        }
+    );
+    install_pool!(
+        api,
+        sys::godot_pool_real_array {
+            godot_pool_real_array_new,
+            godot_pool_real_array_new_copy,
+            godot_pool_real_array_new_with_array,
+            godot_pool_real_array_append,
+            godot_pool_real_array_append_array,
+            godot_pool_real_array_insert,
+            godot_pool_real_array_invert,
+            godot_pool_real_array_push_back,
+            godot_pool_real_array_remove,
+            godot_pool_real_array_resize,
+            godot_pool_real_array_read,
+            godot_pool_real_array_write,
+            godot_pool_real_array_set,
+            godot_pool_real_array_get,
+            godot_pool_real_array_size,
+            godot_pool_real_array_destroy,
+            godot_pool_real_array_read_access_copy,
+            godot_pool_real_array_read_access_ptr,
+            godot_pool_real_array_read_access_operator_assign,
+            godot_pool_real_array_read_access_destroy,
+            godot_pool_real_array_write_access_copy,
+            godot_pool_real_array_write_access_ptr,
+            godot_pool_real_array_write_access_operator_assign,
+            godot_pool_real_array_write_access_destroy,
+        }
+    );
+    install_pool!(
+        api,
+        sys::godot_pool_string_array {
+            godot_pool_string_array_new,
+            godot_pool_string_array_new_copy,
+            godot_pool_string_array_new_with_array,
+            godot_pool_string_array_append,
+            godot_pool_string_array_append_array,
+            godot_pool_string_array_insert,
+            godot_pool_string_array_invert,
+            godot_pool_string_array_push_back,
+            godot_pool_string_array_remove,
+            godot_pool_string_array_resize,
+            godot_pool_string_array_read,
+            godot_pool_string_array_write,
+            godot_pool_string_array_set,
+            godot_pool_string_array_get,
+            godot_pool_string_array_size,
+            godot_pool_string_array_destroy,
+            godot_pool_string_array_read_access_copy,
+            godot_pool_string_array_read_access_ptr,
+            godot_pool_string_array_read_access_operator_assign,
+            godot_pool_string_array_read_access_destroy,
+            godot_pool_string_array_write_access_copy,
+            godot_pool_string_array_write_access_ptr,
+            godot_pool_string_array_write_access_operator_assign,
+            godot_pool_string_array_write_access_destroy,
+        }
+    );
+    install_pool!(
+        api,
+        sys::godot_pool_vector2_array {
+            godot_pool_vector2_array_new,
+            godot_pool_vector2_array_new_copy,
+            godot_pool_vector2_array_new_with_array,
+            godot_pool_vector2_array_append,
+            godot_pool_vector2_array_append_array,
+            godot_pool_vector2_array_insert,
+            godot_pool_vector2_array_invert,
+            godot_pool_vector2_array_push_back,
+            godot_pool_vector2_array_remove,
+            godot_pool_vector2_array_resize,
+            godot_pool_vector2_array_read,
+            godot_pool_vector2_array_write,
+            godot_pool_vector2_array_set,
+            godot_pool_vector2_array_get,
+            godot_pool_vector2_array_size,
+            godot_pool_vector2_array_destroy,
+            godot_pool_vector2_array_read_access_copy,
+            godot_pool_vector2_array_read_access_ptr,
+            godot_pool_vector2_array_read_access_operator_assign,
+            godot_pool_vector2_array_read_access_destroy,
+            godot_pool_vector2_array_write_access_copy,
+            godot_pool_vector2_array_write_access_ptr,
+            godot_pool_vector2_array_write_access_operator_assign,
+            godot_pool_vector2_array_write_access_destroy,
+        }
+    );
+    install_pool!(
+        api,
+        sys::godot_pool_vector3_array {
+            godot_pool_vector3_array_new,
+            godot_pool_vector3_array_new_copy,
+            godot_pool_vector3_array_new_with_array,
+            godot_pool_vector3_array_append,
+            godot_pool_vector3_array_append_array,
+            godot_pool_vector3_array_insert,
+            godot_pool_vector3_array_invert,
+            godot_pool_vector3_array_push_back,
+            godot_pool_vector3_array_remove,
+            godot_pool_vector3_array_resize,
+            godot_pool_vector3_array_read,
+            godot_pool_vector3_array_write,
+            godot_pool_vector3_array_set,
+            godot_pool_vector3_array_get,
+            godot_pool_vector3_array_size,
+            godot_pool_vector3_array_destroy,
+            godot_pool_vector3_array_read_access_copy,
+            godot_pool_vector3_array_read_access_ptr,
+            godot_pool_vector3_array_read_access_operator_assign,
+            godot_pool_vector3_array_read_access_destroy,
+            godot_pool_vector3_array_write_access_copy,
+            godot_pool_vector3_array_write_access_ptr,
+            godot_pool_vector3_array_write_access_operator_assign,
+            godot_pool_vector3_array_write_access_destroy,
+        }
+    );
+    install_pool!(
+        api,
+        sys::godot_pool_color_array {
+            godot_pool_color_array_new,
+            godot_pool_color_array_new_copy,
+            godot_pool_color_array_new_with_array,
+            godot_pool_color_array_append,
+            godot_pool_color_array_append_array,
+            godot_pool_color_array_insert,
+            godot_pool_color_array_invert,
+            godot_pool_color_array_push_back,
+            godot_pool_color_array_remove,
+            godot_pool_color_array_resize,
+            godot_pool_color_array_read,
+            godot_pool_color_array_write,
+            godot_pool_color_array_set,
+            godot_pool_color_array_get,
+            godot_pool_color_array_size,
+            godot_pool_color_array_destroy,
+            godot_pool_color_array_read_access_copy,
+            godot_pool_color_array_read_access_ptr,
+            godot_pool_color_array_read_access_operator_assign,
+            godot_pool_color_array_read_access_destroy,
+            godot_pool_color_array_write_access_copy,
+            godot_pool_color_array_write_access_ptr,
+            godot_pool_color_array_write_access_operator_assign,
+            godot_pool_color_array_write_access_destroy,
+        }
+    );
+}
@@ -0,0 +1,2093 @@
+use super::variant::{self, Value};
+use super::{array, pool_array, Element, Opaque};
+use crate::sys;
+use crate::GodotApi;
+
+use libc::c_char;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::mem;
+use std::os::raw::{c_int, c_schar, c_void};
+use std::slice;
+use std::sync::{Arc, Mutex};
+
+/// Null-terminated wide characters, or `None` for the empty string.
+pub(crate) type StringRepr = Option<Arc<Vec<sys::wchar_t>>>;
+
+unsafe impl Opaque for sys::godot_string {
+    type Repr = StringRepr;
+}
+
+/// Null-terminated UTF-8 or ASCII bytes, or `None` for the empty string.
+unsafe impl Opaque for sys::godot_char_string {
+    type Repr = Option<Box<Vec<u8>>>;
+}
+
+/// Interned name, or `None` for the empty name.
+unsafe impl Opaque for sys::godot_string_name {
+    type Repr = Option<&'static String>;
+}
+
+impl Element for sys::godot_string {
+    fn new_default() -> Self {
+        sys::godot_string::from_repr(None)
+    }
+
+    fn duplicate(&self) -> Self {
+        unsafe { sys::godot_string::from_repr((*sys::godot_string::borrow(self)).clone()) }
+    }
+
+    fn destroy(&mut self) {
+        unsafe { drop(sys::godot_string::take(self)) }
+    }
+}
+
+static EMPTY_WIDE: [sys::wchar_t; 1] = [0];
+
+fn to_wide(s: &str) -> Vec<sys::wchar_t> {
+    if mem::size_of::<sys::wchar_t>() == 2 {
+        s.encode_utf16().map(|c| c as sys::wchar_t).collect()
+    } else {
+        s.chars().map(|c| c as u32 as sys::wchar_t).collect()
+    }
+}
+
+fn from_wide(wide: &[sys::wchar_t]) -> String {
+    if mem::size_of::<sys::wchar_t>() == 2 {
+        let units = wide.iter().map(|&c| c as u16).collect::<Vec<_>>();
+        String::from_utf16_lossy(&units)
+    } else {
+        wide.iter()
+            .map(|&c| std::char::from_u32(c as u32).unwrap_or(std::char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
+}
+
+pub(crate) fn repr_from_str(s: &str) -> StringRepr {
+    if s.is_empty() {
+        None
+    } else {
+        let mut wide = to_wide(s);
+        wide.push(0);
+        Some(Arc::new(wide))
+    }
+}
+
+pub(crate) fn repr_to_string(repr: &StringRepr) -> String {
+    match repr {
+        Some(wide) => from_wide(&wide[..wide.len() - 1]),
+        None => String::new(),
+    }
+}
+
+/// Creates a new engine string from a Rust string.
+pub(crate) fn new_string(s: &str) -> sys::godot_string {
+    sys::godot_string::from_repr(repr_from_str(s))
+}
+
+fn new_string_from_chars(chars: &[char]) -> sys::godot_string {
+    new_string(&chars.iter().collect::<String>())
+}
+
+/// Copies the contents of an engine string into a Rust string.
+pub(crate) unsafe fn string_of(s: *const sys::godot_string) -> String {
+    repr_to_string(&sys::godot_string::borrow(s))
+}
+
+unsafe fn chars_of(s: *const sys::godot_string) -> Vec<char> {
+    string_of(s).chars().collect()
+}
+
+unsafe fn c_str_of<'a>(s: *const c_char) -> &'a [u8] {
+    CStr::from_ptr(s).to_bytes()
+}
+
+unsafe fn wide_of<'a>(s: *const sys::wchar_t, len: Option<usize>) -> &'a [sys::wchar_t] {
+    let len = len.unwrap_or_else(|| (0..).take_while(|&i| *s.add(i) != 0).count());
+    slice::from_raw_parts(s, len)
+}
+
+fn lower(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn upper(c: char) -> char {
+    c.to_uppercase().next().unwrap_or(c)
+}
+
+fn find(s: &[char], what: &[char], from: i32) -> i32 {
+    if from < 0 || what.is_empty() || s.is_empty() || what.len() > s.len() {
+        return -1;
+    }
+    (from as usize..=s.len() - what.len())
+        .find(|&i| s[i..].starts_with(what))
+        .map_or(-1, |i| i as i32)
+}
+
+fn rfind(s: &[char], what: &[char], from: i32) -> i32 {
+    let limit = s.len() as i32 - what.len() as i32;
+    if limit < 0 || what.is_empty() || s.is_empty() {
+        return -1;
+    }
+    let from = if from < 0 || from > limit {
+        limit
+    } else {
+        from
+    };
+    (0..=from as usize)
+        .rev()
+        .find(|&i| s[i..].starts_with(what))
+        .map_or(-1, |i| i as i32)
+}
+
+fn to_lower_chars(s: &[char]) -> Vec<char> {
+    s.iter().map(|&c| lower(c)).collect()
+}
+
+fn substr(s: &[char], from: i32, chars: i32) -> Vec<char> {
+    let len = s.len() as i32;
+    let chars = if chars == -1 { len - from } else { chars };
+    if s.is_empty() || from < 0 || from >= len || chars <= 0 {
+        return Vec::new();
+    }
+    let end = (from + chars).min(len);
+    s[from as usize..end as usize].to_vec()
+}
+
+fn replace(s: &[char], key: &[char], with: &[char], max: Option<usize>, nocase: bool) -> Vec<char> {
+    let (haystack, needle) = if nocase {
+        (to_lower_chars(s), to_lower_chars(key))
+    } else {
+        (s.to_vec(), key.to_vec())
+    };
+
+    let mut result = Vec::new();
+    let mut search_from = 0;
+    let mut count = 0;
+    while max.map_or(true, |max| count < max) {
+        let pos = find(&haystack, &needle, search_from);
+        if pos < 0 {
+            break;
+        }
+        result.extend_from_slice(&s[search_from as usize..pos as usize]);
+        result.extend_from_slice(with);
+        search_from = pos + key.len() as i32;
+        count += 1;
+    }
+    result.extend_from_slice(&s[search_from as usize..]);
+    result
+}
+
+fn split(s: &[char], splitter: &[char], allow_empty: bool) -> Vec<Vec<char>> {
+    let mut result = Vec::new();
+    let mut from = 0;
+    loop {
+        let end = find(s, splitter, from as i32);
+        let end = if end < 0 { s.len() } else { end as usize };
+        if allow_empty || end > from {
+            result.push(s[from..end].to_vec());
+        }
+        if end == s.len() {
+            break;
+        }
+        from = end + splitter.len();
+    }
+    result
+}
+
+fn wildcard_match(s: &[char], pattern: &[char]) -> bool {
+    match pattern.split_first() {
+        None => s.is_empty(),
+        Some(('*', rest)) => (0..=s.len()).any(|i| wildcard_match(&s[i..], rest)),
+        Some(('?', rest)) => !s.is_empty() && wildcard_match(&s[1..], rest),
+        Some((c, rest)) => s.first() == Some(c) && wildcard_match(&s[1..], rest),
+    }
+}
+
+fn hash_units<I: IntoIterator<Item = u32>>(units: I) -> u32 {
+    units.into_iter().fold(5381u32, |hash, c| {
+        (hash << 5).wrapping_add(hash).wrapping_add(c)
+    })
+}
+
+fn hash_units_64<I: IntoIterator<Item = u64>>(units: I) -> u64 {
+    units.into_iter().fold(5381u64, |hash, c| {
+        (hash << 5).wrapping_add(hash).wrapping_add(c)
+    })
+}
+
+fn compare<T: Ord>(a: T, b: T) -> c_schar {
+    match a.cmp(&b) {
+        std::cmp::Ordering::Less => -1,
+        std::cmp::Ordering::Equal => 0,
+        std::cmp::Ordering::Greater => 1,
+    }
+}
+
+fn is_digit(c: char) -> bool {
+    c.is_ascii_digit()
+}
+
+fn hex_value(c: char) -> Option<u32> {
+    c.to_digit(16)
+}
+
+pub(crate) fn to_int64(s: &[char]) -> i64 {
+    let to = s.iter().position(|&c| c == '.').unwrap_or(s.len());
+    let mut integer: i64 = 0;
+    let mut sign = 1;
+    for &c in &s[..to] {
+        if is_digit(c) {
+            integer = integer
+                .wrapping_mul(10)
+                .wrapping_add(i64::from(c as u8 - b'0'));
+        } else if integer == 0 && c == '-' {
+            sign = -sign;
+        }
+    }
+    integer.wrapping_mul(sign)
+}
+
+/// Parses the longest prefix of `s` that is a floating point number, ignoring leading whitespace.
+pub(crate) fn to_double(s: &str) -> f64 {
+    let s = s.trim_start();
+    let bytes = s.as_bytes();
+    let mut end = 0;
+    if end < bytes.len() && (bytes[end] == b'+' || bytes[end] == b'-') {
+        end += 1;
+    }
+    let digits_start = end;
+    while end < bytes.len() && bytes[end].is_ascii_digit() {
+        end += 1;
+    }
+    if end < bytes.len() && bytes[end] == b'.' {
+        end += 1;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+    }
+    if end == digits_start || &s[digits_start..end] == "." {
+        return 0.0;
+    }
+    if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+        let mut exp_end = end + 1;
+        if exp_end < bytes.len() && (bytes[exp_end] == b'+' || bytes[exp_end] == b'-') {
+            exp_end += 1;
+        }
+        let exp_digits = exp_end;
+        while exp_end < bytes.len() && bytes[exp_end].is_ascii_digit() {
+            exp_end += 1;
+        }
+        if exp_end > exp_digits {
+            end = exp_end;
+        }
+    }
+    s[..end].parse().unwrap_or(0.0)
+}
+
+fn hex_to_int64(s: &[char], with_prefix: bool) -> i64 {
+    if with_prefix && s.len() < 3 {
+        return 0;
+    }
+    let (sign, s) = match s.split_first() {
+        Some(('-', rest)) => (-1, rest),
+        _ => (1, s),
+    };
+    let s = if with_prefix {
+        if s.len() < 2 || s[0] != '0' || s[1] != 'x' {
+            return 0;
+        }
+        &s[2..]
+    } else {
+        s
+    };
+    let mut hex: i64 = 0;
+    for &c in s {
+        match hex_value(c) {
+            Some(n) => hex = hex.wrapping_mul(16).wrapping_add(i64::from(n)),
+            None => return 0,
+        }
+    }
+    hex * sign
+}
+
+/// Formats a number like the engine's `String::num`.
+pub(crate) fn num(n: f64, decimals: i32) -> String {
+    if n.is_nan() {
+        return "nan".into();
+    }
+    if n.is_infinite() {
+        return if n < 0.0 { "-inf".into() } else { "inf".into() };
+    }
+    let decimals = if decimals < 0 {
+        let magnitude = n.abs().log10().floor();
+        if magnitude.is_finite() {
+            (14 - magnitude as i32).max(0).min(14)
+        } else {
+            14
+        }
+    } else {
+        decimals.min(14)
+    };
+    let mut s = format!("{:.*}", decimals as usize, n);
+    if s.contains('.') {
+        while s.ends_with('0') {
+            s.pop();
+        }
+        if s.ends_with('.') {
+            s.pop();
+        }
+    }
+    s
+}
+
+/// Formats a number like the engine's `String::num_real`.
+pub(crate) fn num_real(n: f64) -> String {
+    num(n, 6)
+}
+
+fn num_int64(n: i64, base: i32, capitalize_hex: bool) -> String {
+    let base = base.max(2).min(36) as u64;
+    let mut magnitude = n.unsigned_abs();
+    let mut digits = Vec::new();
+    loop {
+        let digit = std::char::from_digit((magnitude % base) as u32, base as u32).unwrap();
+        digits.push(if capitalize_hex { upper(digit) } else { digit });
+        magnitude /= base;
+        if magnitude == 0 {
+            break;
+        }
+    }
+    if n < 0 {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
+
+fn camelcase_to_underscore(s: &[char], lowercase: bool) -> String {
+    let is_upper = |c: char| c.is_ascii_uppercase();
+    let is_lower = |c: char| c.is_ascii_lowercase();
+    let at = |i: usize| s.get(i).copied().unwrap_or('\0');
+
+    let mut result = Vec::new();
+    let mut start = 0;
+    for i in 1..s.len() {
+        let upper_here = is_upper(at(i));
+        let number_here = is_digit(at(i));
+        let next_2_lower = is_lower(at(i + 1)) && is_lower(at(i + 2));
+        let next_lower = is_lower(at(i + 1));
+        let next_number = is_digit(at(i + 1));
+        let prev_upper = is_upper(at(i - 1));
+        let prev_number = is_digit(at(i - 1));
+
+        let cond_a = upper_here && !prev_upper && !prev_number;
+        let cond_b = prev_upper && upper_here && next_2_lower;
+        let number_letter = number_here && !prev_number && next_lower;
+        let letter_number = !number_here && prev_number && (next_lower || next_number);
+
+        if cond_a || cond_b || number_letter || letter_number {
+            result.extend_from_slice(&s[start..i]);
+            result.push('_');
+            start = i;
+        }
+    }
+    result.extend_from_slice(&s[start..]);
+
+    let result = result.into_iter().collect::<String>();
+    if lowercase {
+        result.to_lowercase()
+    } else {
+        result
+    }
+}
+
+fn capitalize(s: &[char]) -> String {
+    let aux = camelcase_to_underscore(s, true).replace('_', " ");
+    aux.trim_matches(|c: char| c <= ' ')
+        .split(' ')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().map(upper).into_iter();
+            first.chain(chars).collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn strip_edges(s: &str, left: bool, right: bool) -> &str {
+    let is_space = |c: char| c <= ' ';
+    let s = if left {
+        s.trim_start_matches(is_space)
+    } else {
+        s
+    };
+    if right {
+        s.trim_end_matches(is_space)
+    } else {
+        s
+    }
+}
+
+pub(crate) fn is_abs_path(s: &str) -> bool {
+    s.starts_with('/') || s.starts_with('\\') || s.contains(":/") || s.contains(":\\")
+}
+
+fn get_base_dir(s: &str) -> String {
+    let (base, rest) = if let Some(pos) = s.find("://") {
+        s.split_at(pos + 3)
+    } else if s.starts_with('/') {
+        s.split_at(1)
+    } else {
+        ("", s)
+    };
+    match rest.rfind(|c| c == '/' || c == '\\') {
+        Some(sep) => format!("{}{}", base, &rest[..sep]),
+        None => base.to_string(),
+    }
+}
+
+fn get_file(s: &str) -> &str {
+    match s.rfind(|c| c == '/' || c == '\\') {
+        Some(sep) => &s[sep + 1..],
+        None => s,
+    }
+}
+
+fn extension_pos(s: &str) -> Option<usize> {
+    let dot = s.rfind('.')?;
+    match s.rfind(|c| c == '/' || c == '\\') {
+        Some(sep) if dot < sep => None,
+        _ => Some(dot),
+    }
+}
+
+fn simplify_path(s: &str) -> String {
+    let (drive, path) = if let Some(rest) = s.strip_prefix("local://") {
+        ("", rest)
+    } else if s.starts_with("res://") || s.starts_with("user://") {
+        s.split_at(s.find("://").unwrap() + 3)
+    } else if s.starts_with('/') || s.starts_with('\\') {
+        s.split_at(1)
+    } else {
+        let drive_sep = s.find(":/").or_else(|| s.find(":\\"));
+        match (drive_sep, s.find('/')) {
+            (Some(p), Some(slash)) if p < slash => s.split_at(p + 2),
+            _ => ("", s),
+        }
+    };
+
+    let path = path.replace('\\', "/");
+    let mut dirs: Vec<&str> = Vec::new();
+    for dir in path.split('/').filter(|d| !d.is_empty()) {
+        match dir {
+            "." => {}
+            ".." => {
+                dirs.pop();
+            }
+            _ => dirs.push(dir),
+        }
+    }
+    format!("{}{}", drive, dirs.join("/"))
+}
+
+fn c_escape(s: &str, multiline: bool) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            _ if multiline => result.push(c),
+            '\x07' => result.push_str("\\a"),
+            '\x08' => result.push_str("\\b"),
+            '\x0c' => result.push_str("\\f"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '\x0b' => result.push_str("\\v"),
+            '\'' => result.push_str("\\'"),
+            '?' => result.push_str("\\?"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+fn c_unescape(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        let unescaped = match chars.peek() {
+            Some('a') => '\x07',
+            Some('b') => '\x08',
+            Some('f') => '\x0c',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('v') => '\x0b',
+            Some('\'') => '\'',
+            Some('"') => '"',
+            Some('?') => '?',
+            Some('\\') => '\\',
+            _ => {
+                result.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        result.push(unescaped);
+    }
+    result
+}
+
+pub(crate) fn json_escape(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '\x08' => result.push_str("\\b"),
+            '\x0c' => result.push_str("\\f"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '\x0b' => result.push_str("\\v"),
+            '"' => result.push_str("\\\""),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+fn xml_escape(s: &str, quotes: bool) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '\'' if quotes => result.push_str("&apos;"),
+            '"' if quotes => result.push_str("&quot;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&apos;", "'")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+fn is_unreserved(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'-' || c == b'_' || c == b'~' || c == b'.'
+}
+
+fn percent_encode(s: &str, pad: bool) -> String {
+    let mut result = String::new();
+    for &c in s.as_bytes() {
+        if is_unreserved(c) {
+            result.push(c as char);
+        } else if pad {
+            result.push_str(&format!("%{:02X}", c));
+        } else {
+            result.push_str(&format!("%{:X}", c));
+        }
+    }
+    result
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hi = hex_value(bytes[i + 1] as char);
+            let lo = hex_value(bytes[i + 2] as char);
+            if let (Some(hi), Some(lo)) = (hi, lo) {
+                result.push((hi * 16 + lo) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        result.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+fn is_valid_integer(s: &[char]) -> bool {
+    let from = if s.len() > 1 && (s[0] == '+' || s[0] == '-') {
+        1
+    } else {
+        0
+    };
+    !s.is_empty() && s[from..].iter().all(|&c| is_digit(c))
+}
+
+fn is_valid_float(s: &[char]) -> bool {
+    let from = match s.first() {
+        Some('+') | Some('-') => 1,
+        _ => 0,
+    };
+    let mut exponent_found = false;
+    let mut period_found = false;
+    let mut sign_found = false;
+    let mut exponent_values_found = false;
+    let mut numbers_found = false;
+    for &c in &s[from.min(s.len())..] {
+        if is_digit(c) {
+            if exponent_found {
+                exponent_values_found = true;
+            } else {
+                numbers_found = true;
+            }
+        } else if numbers_found && !exponent_found && c == 'e' {
+            exponent_found = true;
+        } else if !period_found && !exponent_found && c == '.' {
+            period_found = true;
+        } else if (c == '-' || c == '+') && exponent_found && !exponent_values_found && !sign_found
+        {
+            sign_found = true;
+        } else {
+            return false;
+        }
+    }
+    numbers_found
+}
+
+fn is_valid_hex_number(s: &[char], with_prefix: bool) -> bool {
+    let mut from = if s.len() > 1 && (s[0] == '+' || s[0] == '-') {
+        1
+    } else {
+        0
+    };
+    if with_prefix {
+        if s.len() < from + 2 || s[from] != '0' || s[from + 1] != 'x' {
+            return false;
+        }
+        from += 2;
+    }
+    !s.is_empty() && s[from..].iter().all(|&c| hex_value(c).is_some())
+}
+
+fn is_valid_identifier(s: &[char]) -> bool {
+    !s.is_empty()
+        && s.iter()
+            .enumerate()
+            .all(|(i, &c)| c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit()))
+}
+
+fn is_valid_html_color(s: &str) -> bool {
+    let s = s.strip_prefix('#').unwrap_or(s);
+    (s.len() == 6 || s.len() == 8) && s.chars().all(|c| hex_value(c).is_some())
+}
+
+fn bigrams(s: &[char]) -> Vec<&[char]> {
+    s.windows(2).collect()
+}
+
+fn similarity(a: &[char], b: &[char]) -> f32 {
+    if a == b {
+        return 1.0;
+    }
+    if a.len() < 2 || b.len() < 2 {
+        return 0.0;
+    }
+    let src = bigrams(a);
+    let tgt = bigrams(b);
+    let inter = src.iter().filter(|bigram| tgt.contains(bigram)).count();
+    (2.0 * inter as f32) / (src.len() + tgt.len()) as f32
+}
+
+fn pad_zeros(s: &[char], digits: i32) -> Vec<char> {
+    let mut s = s.to_vec();
+    let mut end = s.iter().position(|&c| c == '.').unwrap_or(s.len());
+    if end == 0 {
+        return s;
+    }
+    let begin = s[..end].iter().position(|&c| is_digit(c)).unwrap_or(end);
+    if begin >= end {
+        return s;
+    }
+    while ((end - begin) as i32) < digits {
+        s.insert(begin, '0');
+        end += 1;
+    }
+    s
+}
+
+fn pad_decimals(s: &[char], digits: i32) -> Vec<char> {
+    let mut s = s.to_vec();
+    let dot = match s.iter().position(|&c| c == '.') {
+        Some(dot) if digits <= 0 => {
+            s.truncate(dot);
+            return s;
+        }
+        Some(dot) => dot,
+        None if digits <= 0 => return s,
+        None => {
+            s.push('.');
+            s.len() - 1
+        }
+    };
+    let digits = digits as usize;
+    if s.len() - (dot + 1) > digits {
+        s.truncate(dot + digits + 1);
+    } else {
+        while s.len() - (dot + 1) < digits {
+            s.push('0');
+        }
+    }
+    s
+}
+
+fn pad(s: &[char], min_length: i32, character: &[char], left: bool) -> Vec<char> {
+    let padding = (min_length - s.len() as i32).max(0) as usize;
+    let fill = character.iter().cycle().take(padding * character.len());
+    if left {
+        fill.chain(s.iter()).copied().collect()
+    } else {
+        s.iter().chain(fill).copied().collect()
+    }
+}
+
+fn format(s: &str, values: &Value, placeholder: &str) -> String {
+    fn unquote(s: String) -> String {
+        if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+            s[1..s.len() - 1].to_string()
+        } else {
+            s
+        }
+    }
+
+    let mut result = s.to_string();
+    match values {
+        Value::Array(arr) => {
+            for (i, value) in arr.iter().enumerate() {
+                let value = unsafe { variant::value_of(value) };
+                match value {
+                    Value::Array(pair) if pair.len() == 2 => {
+                        let (key, val) = unsafe {
+                            (
+                                variant::value_of(&pair[0]).to_string(),
+                                variant::value_of(&pair[1]).to_string(),
+                            )
+                        };
+                        let key = placeholder.replace('_', &unquote(key));
+                        result = result.replace(&key, &unquote(val));
+                    }
+                    Value::Array(_) => {
+                        super::print_error(
+                            "String::format",
+                            &format!("Inner Array at index {} is not a key-value pair.", i),
+                        );
+                    }
+                    value => {
+                        let val = unquote(value.to_string());
+                        if placeholder.contains('_') {
+                            let key = placeholder.replace('_', &i.to_string());
+                            result = result.replace(&key, &val);
+                        } else {
+                            result = result.replacen(placeholder, &val, 1);
+                        }
+                    }
+                }
+            }
+        }
+        Value::Dictionary(dict) => {
+            for (key, val) in dict.iter() {
+                let (key, val) = unsafe {
+                    (
+                        variant::value_of(key).to_string(),
+                        variant::value_of(val).to_string(),
+                    )
+                };
+                let key = placeholder.replace('_', &unquote(key));
+                result = result.replace(&key, &unquote(val));
+            }
+        }
+        _ => super::print_error("String::format", "Invalid type: use Array or Dictionary."),
+    }
+    result
+}
+
+fn new_string_array<I: IntoIterator<Item = Vec<char>>>(slices: I) -> sys::godot_array {
+    array::new_array(
+        slices
+            .into_iter()
+            .map(|s| {
+                variant::new_variant(Value::String(repr_from_str(&s.iter().collect::<String>())))
+            })
+            .collect(),
+    )
+}
+
+fn new_value_array<I: IntoIterator<Item = Value>>(values: I) -> sys::godot_array {
+    array::new_array(values.into_iter().map(variant::new_variant).collect())
+}
+
+unsafe extern "C" fn godot_string_new(dest: *mut sys::godot_string) {
+    sys::godot_string::init(dest, None);
+}
+
+unsafe extern "C" fn godot_string_new_copy(
+    dest: *mut sys::godot_string,
+    src: *const sys::godot_string,
+) {
+    sys::godot_string::init(dest, (*sys::godot_string::borrow(src)).clone());
+}
+
+unsafe extern "C" fn godot_string_new_with_wide_string(
+    dest: *mut sys::godot_string,
+    contents: *const sys::wchar_t,
+    size: c_int,
+) {
+    let len = if size < 0 { None } else { Some(size as usize) };
+    let s = from_wide(wide_of(contents, len));
+    sys::godot_string::init(dest, repr_from_str(&s));
+}
+
+unsafe extern "C" fn godot_string_destroy(this: *mut sys::godot_string) {
+    drop(sys::godot_string::take(this));
+}
+
+unsafe extern "C" fn godot_string_chars_to_utf8(utf8: *const c_char) -> sys::godot_string {
+    new_string(&String::from_utf8_lossy(c_str_of(utf8)))
+}
+
+unsafe extern "C" fn godot_string_chars_to_utf8_with_len(
+    utf8: *const c_char,
+    len: sys::godot_int,
+) -> sys::godot_string {
+    let bytes = slice::from_raw_parts(utf8 as *const u8, len.max(0) as usize);
+    new_string(&String::from_utf8_lossy(bytes))
+}
+
+unsafe extern "C" fn godot_string_parse_utf8(
+    this: *mut sys::godot_string,
+    utf8: *const c_char,
+) -> sys::godot_bool {
+    let bytes = c_str_of(utf8);
+    sys::godot_string::replace(this, repr_from_str(&String::from_utf8_lossy(bytes)));
+    std::str::from_utf8(bytes).is_err()
+}
+
+unsafe extern "C" fn godot_string_parse_utf8_with_len(
+    this: *mut sys::godot_string,
+    utf8: *const c_char,
+    len: sys::godot_int,
+) -> sys::godot_bool {
+    let bytes = slice::from_raw_parts(utf8 as *const u8, len.max(0) as usize);
+    sys::godot_string::replace(this, repr_from_str(&String::from_utf8_lossy(bytes)));
+    std::str::from_utf8(bytes).is_err()
+}
+
+unsafe extern "C" fn godot_string_wide_str(this: *const sys::godot_string) -> *const sys::wchar_t {
+    match &*sys::godot_string::borrow(this) {
+        Some(wide) => wide.as_ptr(),
+        None => EMPTY_WIDE.as_ptr(),
+    }
+}
+
+unsafe extern "C" fn godot_string_operator_index(
+    this: *mut sys::godot_string,
+    idx: sys::godot_int,
+) -> *const sys::wchar_t {
+    let len = godot_string_length(this);
+    if idx < 0 || idx > len {
+        panic!("index {} out of bounds for string of length {}", idx, len);
+    }
+    godot_string_wide_str(this).add(idx as usize)
+}
+
+unsafe extern "C" fn godot_string_operator_index_const(
+    this: *const sys::godot_string,
+    idx: sys::godot_int,
+) -> sys::wchar_t {
+    *godot_string_operator_index(this as *mut _, idx)
+}
+
+unsafe extern "C" fn godot_string_ord_at(
+    this: *const sys::godot_string,
+    idx: sys::godot_int,
+) -> sys::wchar_t {
+    godot_string_operator_index_const(this, idx)
+}
+
+unsafe extern "C" fn godot_string_operator_equal(
+    this: *const sys::godot_string,
+    b: *const sys::godot_string,
+) -> sys::godot_bool {
+    *sys::godot_string::borrow(this) == *sys::godot_string::borrow(b)
+}
+
+unsafe extern "C" fn godot_string_operator_less(
+    this: *const sys::godot_string,
+    b: *const sys::godot_string,
+) -> sys::godot_bool {
+    chars_of(this) < chars_of(b)
+}
+
+unsafe extern "C" fn godot_string_operator_plus(
+    this: *const sys::godot_string,
+    b: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&(string_of(this) + &string_of(b)))
+}
+
+unsafe extern "C" fn godot_string_length(this: *const sys::godot_string) -> sys::godot_int {
+    match &*sys::godot_string::borrow(this) {
+        Some(wide) => (wide.len() - 1) as sys::godot_int,
+        None => 0,
+    }
+}
+
+unsafe extern "C" fn godot_string_empty(this: *const sys::godot_string) -> sys::godot_bool {
+    godot_string_length(this) == 0
+}
+
+unsafe extern "C" fn godot_string_casecmp_to(
+    this: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> c_schar {
+    compare(chars_of(this), chars_of(string))
+}
+
+unsafe extern "C" fn godot_string_nocasecmp_to(
+    this: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> c_schar {
+    compare(
+        to_lower_chars(&chars_of(this)),
+        to_lower_chars(&chars_of(string)),
+    )
+}
+
+unsafe extern "C" fn godot_string_naturalnocasecmp_to(
+    this: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> c_schar {
+    godot_string_nocasecmp_to(this, string)
+}
+
+unsafe extern "C" fn godot_string_begins_with(
+    this: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> sys::godot_bool {
+    string_of(this).starts_with(&string_of(string))
+}
+
+unsafe extern "C" fn godot_string_begins_with_char_array(
+    this: *const sys::godot_string,
+    char_array: *const c_char,
+) -> sys::godot_bool {
+    string_of(this).starts_with(&*String::from_utf8_lossy(c_str_of(char_array)))
+}
+
+unsafe extern "C" fn godot_string_ends_with(
+    this: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> sys::godot_bool {
+    let s = chars_of(this);
+    let what = chars_of(string);
+    let pos = rfind(&s, &what, -1);
+    pos != -1 && pos as usize + what.len() == s.len()
+}
+
+unsafe extern "C" fn godot_string_is_subsequence_of(
+    this: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> sys::godot_bool {
+    let mut haystack = chars_of(string).into_iter();
+    chars_of(this).into_iter().all(|c| haystack.any(|h| h == c))
+}
+
+unsafe extern "C" fn godot_string_is_subsequence_ofi(
+    this: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> sys::godot_bool {
+    let mut haystack = to_lower_chars(&chars_of(string)).into_iter();
+    to_lower_chars(&chars_of(this))
+        .into_iter()
+        .all(|c| haystack.any(|h| h == c))
+}
+
+unsafe extern "C" fn godot_string_match(
+    this: *const sys::godot_string,
+    wildcard: *const sys::godot_string,
+) -> sys::godot_bool {
+    wildcard_match(&chars_of(this), &chars_of(wildcard))
+}
+
+unsafe extern "C" fn godot_string_matchn(
+    this: *const sys::godot_string,
+    wildcard: *const sys::godot_string,
+) -> sys::godot_bool {
+    wildcard_match(
+        &to_lower_chars(&chars_of(this)),
+        &to_lower_chars(&chars_of(wildcard)),
+    )
+}
+
+unsafe extern "C" fn godot_string_bigrams(this: *const sys::godot_string) -> sys::godot_array {
+    new_string_array(bigrams(&chars_of(this)).into_iter().map(<[char]>::to_vec))
+}
+
+unsafe extern "C" fn godot_string_chr(character: sys::wchar_t) -> sys::godot_string {
+    new_string(&from_wide(&[character]))
+}
+
+// Like in the engine, strings passed by value are borrowed from the caller, which keeps
+// ownership of them.
+
+unsafe extern "C" fn godot_string_find(
+    this: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    find(&chars_of(this), &chars_of(&what), 0)
+}
+
+unsafe extern "C" fn godot_string_find_from(
+    this: *const sys::godot_string,
+    what: sys::godot_string,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    find(&chars_of(this), &chars_of(&what), from)
+}
+
+unsafe extern "C" fn godot_string_findn(
+    this: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    godot_string_findn_from(this, what, 0)
+}
+
+unsafe extern "C" fn godot_string_findn_from(
+    this: *const sys::godot_string,
+    what: sys::godot_string,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    find(
+        &to_lower_chars(&chars_of(this)),
+        &to_lower_chars(&chars_of(&what)),
+        from,
+    )
+}
+
+unsafe extern "C" fn godot_string_find_last(
+    this: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    godot_string_rfind_from(this, what, -1)
+}
+
+unsafe extern "C" fn godot_string_rfind(
+    this: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    godot_string_rfind_from(this, what, -1)
+}
+
+unsafe extern "C" fn godot_string_rfind_from(
+    this: *const sys::godot_string,
+    what: sys::godot_string,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    rfind(&chars_of(this), &chars_of(&what), from)
+}
+
+unsafe extern "C" fn godot_string_rfindn(
+    this: *const sys::godot_string,
+    what: sys::godot_string,
+) -> sys::godot_int {
+    godot_string_rfindn_from(this, what, -1)
+}
+
+unsafe extern "C" fn godot_string_rfindn_from(
+    this: *const sys::godot_string,
+    what: sys::godot_string,
+    from: sys::godot_int,
+) -> sys::godot_int {
+    rfind(
+        &to_lower_chars(&chars_of(this)),
+        &to_lower_chars(&chars_of(&what)),
+        from,
+    )
+}
+
+unsafe extern "C" fn godot_string_format(
+    this: *const sys::godot_string,
+    values: *const sys::godot_variant,
+) -> sys::godot_string {
+    new_string(&format(&string_of(this), &variant::value_of(values), "{_}"))
+}
+
+unsafe extern "C" fn godot_string_format_with_custom_placeholder(
+    this: *const sys::godot_string,
+    values: *const sys::godot_variant,
+    placeholder: *const c_char,
+) -> sys::godot_string {
+    let placeholder = String::from_utf8_lossy(c_str_of(placeholder));
+    new_string(&format(
+        &string_of(this),
+        &variant::value_of(values),
+        &placeholder,
+    ))
+}
+
+unsafe extern "C" fn godot_string_hex_encode_buffer(
+    buffer: *const u8,
+    len: sys::godot_int,
+) -> sys::godot_string {
+    let bytes = slice::from_raw_parts(buffer, len.max(0) as usize);
+    new_string(
+        &bytes
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>(),
+    )
+}
+
+unsafe extern "C" fn godot_string_hex_to_int(this: *const sys::godot_string) -> sys::godot_int {
+    hex_to_int64(&chars_of(this), true) as sys::godot_int
+}
+
+unsafe extern "C" fn godot_string_hex_to_int_without_prefix(
+    this: *const sys::godot_string,
+) -> sys::godot_int {
+    hex_to_int64(&chars_of(this), false) as sys::godot_int
+}
+
+unsafe extern "C" fn godot_string_hex_to_int64(this: *const sys::godot_string) -> i64 {
+    hex_to_int64(&chars_of(this), false)
+}
+
+unsafe extern "C" fn godot_string_hex_to_int64_with_prefix(this: *const sys::godot_string) -> i64 {
+    hex_to_int64(&chars_of(this), true)
+}
+
+unsafe extern "C" fn godot_string_insert(
+    this: *const sys::godot_string,
+    at_pos: sys::godot_int,
+    string: sys::godot_string,
+) -> sys::godot_string {
+    let mut s = chars_of(this);
+    if at_pos >= 0 {
+        let at_pos = (at_pos as usize).min(s.len());
+        let insert = chars_of(&string);
+        s.splice(at_pos..at_pos, insert);
+    }
+    new_string_from_chars(&s)
+}
+
+unsafe extern "C" fn godot_string_erase(
+    this: *mut sys::godot_string,
+    pos: sys::godot_int,
+    chars: sys::godot_int,
+) {
+    let s = chars_of(this);
+    let len = s.len() as i32;
+    let mut result = if pos <= 0 {
+        Vec::new()
+    } else {
+        s[..pos.min(len) as usize].to_vec()
+    };
+    result.extend(substr(&s, pos + chars, len - (pos + chars)));
+    sys::godot_string::replace(this, repr_from_str(&result.iter().collect::<String>()));
+}
+
+unsafe extern "C" fn godot_string_is_numeric(this: *const sys::godot_string) -> sys::godot_bool {
+    let s = chars_of(this);
+    let from = if s.first() == Some(&'-') { 1 } else { 0 };
+    !s.is_empty() && s[from..].iter().all(|&c| is_digit(c))
+}
+
+unsafe extern "C" fn godot_string_lpad(
+    this: *const sys::godot_string,
+    min_length: sys::godot_int,
+) -> sys::godot_string {
+    new_string_from_chars(&pad(&chars_of(this), min_length, &[' '], true))
+}
+
+unsafe extern "C" fn godot_string_lpad_with_custom_character(
+    this: *const sys::godot_string,
+    min_length: sys::godot_int,
+    character: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string_from_chars(&pad(
+        &chars_of(this),
+        min_length,
+        &chars_of(character),
+        true,
+    ))
+}
+
+unsafe extern "C" fn godot_string_rpad(
+    this: *const sys::godot_string,
+    min_length: sys::godot_int,
+) -> sys::godot_string {
+    new_string_from_chars(&pad(&chars_of(this), min_length, &[' '], false))
+}
+
+unsafe extern "C" fn godot_string_rpad_with_custom_character(
+    this: *const sys::godot_string,
+    min_length: sys::godot_int,
+    character: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string_from_chars(&pad(
+        &chars_of(this),
+        min_length,
+        &chars_of(character),
+        false,
+    ))
+}
+
+unsafe extern "C" fn godot_string_num(num: f64) -> sys::godot_string {
+    new_string(&self::num(num, -1))
+}
+
+unsafe extern "C" fn godot_string_num_int64(num: i64, base: sys::godot_int) -> sys::godot_string {
+    new_string(&num_int64(num, base, false))
+}
+
+unsafe extern "C" fn godot_string_num_int64_capitalized(
+    num: i64,
+    base: sys::godot_int,
+    capitalize_hex: sys::godot_bool,
+) -> sys::godot_string {
+    new_string(&num_int64(num, base, capitalize_hex))
+}
+
+unsafe extern "C" fn godot_string_num_real(num: f64) -> sys::godot_string {
+    new_string(&num_real(num))
+}
+
+unsafe extern "C" fn godot_string_num_with_decimals(
+    num: f64,
+    decimals: sys::godot_int,
+) -> sys::godot_string {
+    new_string(&self::num(num, decimals))
+}
+
+unsafe extern "C" fn godot_string_pad_decimals(
+    this: *const sys::godot_string,
+    digits: sys::godot_int,
+) -> sys::godot_string {
+    new_string_from_chars(&pad_decimals(&chars_of(this), digits))
+}
+
+unsafe extern "C" fn godot_string_pad_zeros(
+    this: *const sys::godot_string,
+    digits: sys::godot_int,
+) -> sys::godot_string {
+    new_string_from_chars(&pad_zeros(&chars_of(this), digits))
+}
+
+unsafe fn replace_with(
+    this: *const sys::godot_string,
+    key: sys::godot_string,
+    with: sys::godot_string,
+    max: Option<usize>,
+    nocase: bool,
+) -> sys::godot_string {
+    let result = replace(
+        &chars_of(this),
+        &chars_of(&key),
+        &chars_of(&with),
+        max,
+        nocase,
+    );
+    new_string_from_chars(&result)
+}
+
+unsafe extern "C" fn godot_string_replace_first(
+    this: *const sys::godot_string,
+    key: sys::godot_string,
+    with: sys::godot_string,
+) -> sys::godot_string {
+    replace_with(this, key, with, Some(1), false)
+}
+
+unsafe extern "C" fn godot_string_replace(
+    this: *const sys::godot_string,
+    key: sys::godot_string,
+    with: sys::godot_string,
+) -> sys::godot_string {
+    replace_with(this, key, with, None, false)
+}
+
+unsafe extern "C" fn godot_string_replacen(
+    this: *const sys::godot_string,
+    key: sys::godot_string,
+    with: sys::godot_string,
+) -> sys::godot_string {
+    replace_with(this, key, with, None, true)
+}
+
+unsafe extern "C" fn godot_string_similarity(
+    this: *const sys::godot_string,
+    string: *const sys::godot_string,
+) -> sys::godot_real {
+    similarity(&chars_of(this), &chars_of(string)) as sys::godot_real
+}
+
+unsafe extern "C" fn godot_string_substr(
+    this: *const sys::godot_string,
+    from: sys::godot_int,
+    chars: sys::godot_int,
+) -> sys::godot_string {
+    new_string_from_chars(&substr(&chars_of(this), from, chars))
+}
+
+unsafe extern "C" fn godot_string_to_double(this: *const sys::godot_string) -> f64 {
+    to_double(&string_of(this))
+}
+
+unsafe extern "C" fn godot_string_to_float(this: *const sys::godot_string) -> sys::godot_real {
+    to_double(&string_of(this)) as sys::godot_real
+}
+
+unsafe extern "C" fn godot_string_to_int(this: *const sys::godot_string) -> sys::godot_int {
+    to_int64(&chars_of(this)) as sys::godot_int
+}
+
+unsafe extern "C" fn godot_string_to_int64(this: *const sys::godot_string) -> i64 {
+    to_int64(&chars_of(this))
+}
+
+unsafe extern "C" fn godot_string_camelcase_to_underscore(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&camelcase_to_underscore(&chars_of(this), false))
+}
+
+unsafe extern "C" fn godot_string_camelcase_to_underscore_lowercased(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&camelcase_to_underscore(&chars_of(this), true))
+}
+
+unsafe extern "C" fn godot_string_capitalize(this: *const sys::godot_string) -> sys::godot_string {
+    new_string(&capitalize(&chars_of(this)))
+}
+
+unsafe extern "C" fn godot_string_char_to_double(what: *const c_char) -> f64 {
+    to_double(&String::from_utf8_lossy(c_str_of(what)))
+}
+
+unsafe extern "C" fn godot_string_char_to_int(what: *const c_char) -> sys::godot_int {
+    let s = String::from_utf8_lossy(c_str_of(what));
+    to_int64(&s.chars().collect::<Vec<_>>()) as sys::godot_int
+}
+
+unsafe extern "C" fn godot_string_char_to_int_with_len(
+    what: *const c_char,
+    len: sys::godot_int,
+) -> sys::godot_int {
+    let bytes = slice::from_raw_parts(what as *const u8, len.max(0) as usize);
+    let s = String::from_utf8_lossy(bytes);
+    to_int64(&s.chars().collect::<Vec<_>>()) as sys::godot_int
+}
+
+unsafe extern "C" fn godot_string_wchar_to_int(str: *const sys::wchar_t) -> i64 {
+    to_int64(&from_wide(wide_of(str, None)).chars().collect::<Vec<_>>())
+}
+
+unsafe extern "C" fn godot_string_char_to_int64_with_len(
+    str: *const sys::wchar_t,
+    len: c_int,
+) -> i64 {
+    let wide = wide_of(str, Some(len.max(0) as usize));
+    to_int64(&from_wide(wide).chars().collect::<Vec<_>>())
+}
+
+unsafe extern "C" fn godot_string_get_slice_count(
+    this: *const sys::godot_string,
+    splitter: sys::godot_string,
+) -> sys::godot_int {
+    let s = chars_of(this);
+    let splitter_chars = chars_of(&splitter);
+    if s.is_empty() || splitter_chars.is_empty() {
+        return 0;
+    }
+    split(&s, &splitter_chars, true).len() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_string_get_slice(
+    this: *const sys::godot_string,
+    splitter: sys::godot_string,
+    slice: sys::godot_int,
+) -> sys::godot_string {
+    let s = chars_of(this);
+    let splitter_chars = chars_of(&splitter);
+    if s.is_empty() || splitter_chars.is_empty() || slice < 0 {
+        return new_string("");
+    }
+    match split(&s, &splitter_chars, true).get(slice as usize) {
+        Some(slice) => new_string_from_chars(slice),
+        None => new_string(""),
+    }
+}
+
+unsafe extern "C" fn godot_string_get_slicec(
+    this: *const sys::godot_string,
+    splitter: sys::wchar_t,
+    slice: sys::godot_int,
+) -> sys::godot_string {
+    godot_string_get_slice(this, godot_string_chr(splitter), slice)
+}
+
+unsafe extern "C" fn godot_string_split(
+    this: *const sys::godot_string,
+    splitter: *const sys::godot_string,
+) -> sys::godot_array {
+    new_string_array(split(&chars_of(this), &chars_of(splitter), false))
+}
+
+unsafe extern "C" fn godot_string_split_allow_empty(
+    this: *const sys::godot_string,
+    splitter: *const sys::godot_string,
+) -> sys::godot_array {
+    new_string_array(split(&chars_of(this), &chars_of(splitter), true))
+}
+
+unsafe extern "C" fn godot_string_split_floats(
+    this: *const sys::godot_string,
+    splitter: *const sys::godot_string,
+) -> sys::godot_array {
+    let slices = split(&chars_of(this), &chars_of(splitter), false);
+    new_value_array(
+        slices
+            .into_iter()
+            .map(|s| Value::Real(to_double(&s.iter().collect::<String>()))),
+    )
+}
+
+unsafe extern "C" fn godot_string_split_floats_allows_empty(
+    this: *const sys::godot_string,
+    splitter: *const sys::godot_string,
+) -> sys::godot_array {
+    let slices = split(&chars_of(this), &chars_of(splitter), true);
+    new_value_array(
+        slices
+            .into_iter()
+            .map(|s| Value::Real(to_double(&s.iter().collect::<String>()))),
+    )
+}
+
+unsafe extern "C" fn godot_string_split_ints(
+    this: *const sys::godot_string,
+    splitter: *const sys::godot_string,
+) -> sys::godot_array {
+    let slices = split(&chars_of(this), &chars_of(splitter), false);
+    new_value_array(slices.into_iter().map(|s| Value::Int(to_int64(&s))))
+}
+
+unsafe extern "C" fn godot_string_split_ints_allows_empty(
+    this: *const sys::godot_string,
+    splitter: *const sys::godot_string,
+) -> sys::godot_array {
+    let slices = split(&chars_of(this), &chars_of(splitter), true);
+    new_value_array(slices.into_iter().map(|s| Value::Int(to_int64(&s))))
+}
+
+unsafe extern "C" fn godot_string_split_spaces(this: *const sys::godot_string) -> sys::godot_array {
+    let s = string_of(this);
+    new_string_array(
+        s.split(|c: char| c <= ' ')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.chars().collect()),
+    )
+}
+
+unsafe extern "C" fn godot_string_rsplit(
+    this: *const sys::godot_string,
+    divisor: *const sys::godot_string,
+    allow_empty: sys::godot_bool,
+    maxsplit: sys::godot_int,
+) -> sys::godot_pool_string_array {
+    let s = chars_of(this);
+    let divisor = chars_of(divisor);
+    let mut slices = Vec::new();
+    let mut end = s.len();
+    loop {
+        if maxsplit > 0 && slices.len() as i32 >= maxsplit {
+            slices.push(s[..end].to_vec());
+            break;
+        }
+        let pos = rfind(&s[..end], &divisor, -1);
+        let start = if pos < 0 {
+            0
+        } else {
+            pos as usize + divisor.len()
+        };
+        if allow_empty || end > start {
+            slices.push(s[start..end].to_vec());
+        }
+        if pos < 0 {
+            break;
+        }
+        end = pos as usize;
+    }
+    slices.reverse();
+    pool_array::new_string_array(
+        slices
+            .into_iter()
+            .map(|s| new_string_from_chars(&s))
+            .collect(),
+    )
+}
+
+unsafe extern "C" fn godot_string_char_lowercase(char: sys::wchar_t) -> sys::wchar_t {
+    match std::char::from_u32(char as u32) {
+        Some(c) => lower(c) as u32 as sys::wchar_t,
+        None => char,
+    }
+}
+
+unsafe extern "C" fn godot_string_char_uppercase(char: sys::wchar_t) -> sys::wchar_t {
+    match std::char::from_u32(char as u32) {
+        Some(c) => upper(c) as u32 as sys::wchar_t,
+        None => char,
+    }
+}
+
+unsafe extern "C" fn godot_string_to_lower(this: *const sys::godot_string) -> sys::godot_string {
+    new_string_from_chars(&to_lower_chars(&chars_of(this)))
+}
+
+unsafe extern "C" fn godot_string_to_upper(this: *const sys::godot_string) -> sys::godot_string {
+    new_string_from_chars(&chars_of(this).into_iter().map(upper).collect::<Vec<_>>())
+}
+
+unsafe extern "C" fn godot_string_get_basename(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    let s = string_of(this);
+    match extension_pos(&s) {
+        Some(pos) => new_string(&s[..pos]),
+        None => new_string(&s),
+    }
+}
+
+unsafe extern "C" fn godot_string_get_extension(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    let s = string_of(this);
+    match extension_pos(&s) {
+        Some(pos) => new_string(&s[pos + 1..]),
+        None => new_string(""),
+    }
+}
+
+unsafe extern "C" fn godot_string_left(
+    this: *const sys::godot_string,
+    pos: sys::godot_int,
+) -> sys::godot_string {
+    let s = chars_of(this);
+    new_string_from_chars(&s[..pos.max(0).min(s.len() as i32) as usize])
+}
+
+unsafe extern "C" fn godot_string_right(
+    this: *const sys::godot_string,
+    pos: sys::godot_int,
+) -> sys::godot_string {
+    let s = chars_of(this);
+    new_string_from_chars(&s[pos.max(0).min(s.len() as i32) as usize..])
+}
+
+unsafe extern "C" fn godot_string_plus_file(
+    this: *const sys::godot_string,
+    file: *const sys::godot_string,
+) -> sys::godot_string {
+    let s = string_of(this);
+    let file = string_of(file);
+    if s.is_empty() {
+        new_string(&file)
+    } else if s.ends_with('/') || file.starts_with('/') {
+        new_string(&(s + &file))
+    } else {
+        new_string(&format!("{}/{}", s, file))
+    }
+}
+
+unsafe extern "C" fn godot_string_strip_edges(
+    this: *const sys::godot_string,
+    left: sys::godot_bool,
+    right: sys::godot_bool,
+) -> sys::godot_string {
+    new_string(strip_edges(&string_of(this), left, right))
+}
+
+unsafe extern "C" fn godot_string_strip_escapes(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&string_of(this).replace(|c: char| c < ' ', ""))
+}
+
+unsafe extern "C" fn godot_string_trim_prefix(
+    this: *const sys::godot_string,
+    prefix: *const sys::godot_string,
+) -> sys::godot_string {
+    let s = string_of(this);
+    let prefix = string_of(prefix);
+    new_string(s.strip_prefix(&*prefix).unwrap_or(&s))
+}
+
+unsafe extern "C" fn godot_string_trim_suffix(
+    this: *const sys::godot_string,
+    suffix: *const sys::godot_string,
+) -> sys::godot_string {
+    let s = string_of(this);
+    let suffix = string_of(suffix);
+    new_string(s.strip_suffix(&*suffix).unwrap_or(&s))
+}
+
+unsafe extern "C" fn godot_string_rstrip(
+    this: *const sys::godot_string,
+    chars: *const sys::godot_string,
+) -> sys::godot_string {
+    let chars = chars_of(chars);
+    new_string(string_of(this).trim_end_matches(|c| chars.contains(&c)))
+}
+
+unsafe extern "C" fn godot_string_ascii(this: *const sys::godot_string) -> sys::godot_char_string {
+    let mut bytes = string_of(this)
+        .chars()
+        .map(|c| if c as u32 > 0x7f { b'?' } else { c as u8 })
+        .collect::<Vec<_>>();
+    bytes.push(0);
+    sys::godot_char_string::from_repr(Some(Box::new(bytes)))
+}
+
+unsafe extern "C" fn godot_string_ascii_extended(
+    this: *const sys::godot_string,
+) -> sys::godot_char_string {
+    let mut bytes = string_of(this)
+        .chars()
+        .map(|c| c as u32 as u8)
+        .collect::<Vec<_>>();
+    bytes.push(0);
+    sys::godot_char_string::from_repr(Some(Box::new(bytes)))
+}
+
+unsafe extern "C" fn godot_string_utf8(this: *const sys::godot_string) -> sys::godot_char_string {
+    let mut bytes = string_of(this).into_bytes();
+    bytes.push(0);
+    sys::godot_char_string::from_repr(Some(Box::new(bytes)))
+}
+
+unsafe extern "C" fn godot_string_hash(this: *const sys::godot_string) -> u32 {
+    hash_units(chars_of(this).into_iter().map(|c| c as u32))
+}
+
+unsafe extern "C" fn godot_string_hash64(this: *const sys::godot_string) -> u64 {
+    hash_units_64(chars_of(this).into_iter().map(|c| u64::from(c as u32)))
+}
+
+unsafe extern "C" fn godot_string_hash_chars(cstr: *const c_char) -> u32 {
+    hash_units(c_str_of(cstr).iter().map(|&c| u32::from(c)))
+}
+
+unsafe extern "C" fn godot_string_hash_chars_with_len(
+    cstr: *const c_char,
+    len: sys::godot_int,
+) -> u32 {
+    let bytes = slice::from_raw_parts(cstr as *const u8, len.max(0) as usize);
+    hash_units(bytes.iter().map(|&c| u32::from(c)))
+}
+
+unsafe extern "C" fn godot_string_hash_utf8_chars(str: *const sys::wchar_t) -> u32 {
+    hash_units(wide_of(str, None).iter().map(|&c| c as u32))
+}
+
+unsafe extern "C" fn godot_string_hash_utf8_chars_with_len(
+    str: *const sys::wchar_t,
+    len: sys::godot_int,
+) -> u32 {
+    hash_units(
+        wide_of(str, Some(len.max(0) as usize))
+            .iter()
+            .map(|&c| c as u32),
+    )
+}
+
+unsafe extern "C" fn godot_string_get_base_dir(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&get_base_dir(&string_of(this)))
+}
+
+unsafe extern "C" fn godot_string_get_file(this: *const sys::godot_string) -> sys::godot_string {
+    new_string(get_file(&string_of(this)))
+}
+
+unsafe extern "C" fn godot_string_is_abs_path(this: *const sys::godot_string) -> sys::godot_bool {
+    is_abs_path(&string_of(this))
+}
+
+unsafe extern "C" fn godot_string_is_rel_path(this: *const sys::godot_string) -> sys::godot_bool {
+    !is_abs_path(&string_of(this))
+}
+
+unsafe extern "C" fn godot_string_is_resource_file(
+    this: *const sys::godot_string,
+) -> sys::godot_bool {
+    let s = string_of(this);
+    s.starts_with("res://") && !s.contains("::")
+}
+
+unsafe extern "C" fn godot_string_simplify_path(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&simplify_path(&string_of(this)))
+}
+
+unsafe extern "C" fn godot_string_c_escape(this: *const sys::godot_string) -> sys::godot_string {
+    new_string(&c_escape(&string_of(this), false))
+}
+
+unsafe extern "C" fn godot_string_c_escape_multiline(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&c_escape(&string_of(this), true))
+}
+
+unsafe extern "C" fn godot_string_c_unescape(this: *const sys::godot_string) -> sys::godot_string {
+    new_string(&c_unescape(&string_of(this)))
+}
+
+unsafe extern "C" fn godot_string_http_escape(this: *const sys::godot_string) -> sys::godot_string {
+    new_string(&percent_encode(&string_of(this), false))
+}
+
+unsafe extern "C" fn godot_string_http_unescape(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&percent_decode(&string_of(this)))
+}
+
+unsafe extern "C" fn godot_string_json_escape(this: *const sys::godot_string) -> sys::godot_string {
+    new_string(&json_escape(&string_of(this)))
+}
+
+unsafe extern "C" fn godot_string_xml_escape(this: *const sys::godot_string) -> sys::godot_string {
+    new_string(&xml_escape(&string_of(this), false))
+}
+
+unsafe extern "C" fn godot_string_xml_escape_with_quotes(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&xml_escape(&string_of(this), true))
+}
+
+unsafe extern "C" fn godot_string_xml_unescape(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&xml_unescape(&string_of(this)))
+}
+
+unsafe extern "C" fn godot_string_percent_decode(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&percent_decode(&string_of(this)))
+}
+
+unsafe extern "C" fn godot_string_percent_encode(
+    this: *const sys::godot_string,
+) -> sys::godot_string {
+    new_string(&percent_encode(&string_of(this), true))
+}
+
+unsafe extern "C" fn godot_string_is_valid_float(
+    this: *const sys::godot_string,
+) -> sys::godot_bool {
+    is_valid_float(&chars_of(this))
+}
+
+unsafe extern "C" fn godot_string_is_valid_hex_number(
+    this: *const sys::godot_string,
+    with_prefix: sys::godot_bool,
+) -> sys::godot_bool {
+    is_valid_hex_number(&chars_of(this), with_prefix)
+}
+
+unsafe extern "C" fn godot_string_is_valid_html_color(
+    this: *const sys::godot_string,
+) -> sys::godot_bool {
+    is_valid_html_color(&string_of(this))
+}
+
+unsafe extern "C" fn godot_string_is_valid_identifier(
+    this: *const sys::godot_string,
+) -> sys::godot_bool {
+    is_valid_identifier(&chars_of(this))
+}
+
+unsafe extern "C" fn godot_string_is_valid_integer(
+    this: *const sys::godot_string,
+) -> sys::godot_bool {
+    is_valid_integer(&chars_of(this))
+}
+
+unsafe extern "C" fn godot_string_is_valid_ip_address(
+    this: *const sys::godot_string,
+) -> sys::godot_bool {
+    string_of(this).parse::<std::net::IpAddr>().is_ok()
+}
+
+unsafe extern "C" fn godot_char_string_length(cs: *const sys::godot_char_string) -> sys::godot_int {
+    match &*sys::godot_char_string::borrow(cs) {
+        Some(bytes) => (bytes.len() - 1) as sys::godot_int,
+        None => 0,
+    }
+}
+
+unsafe extern "C" fn godot_char_string_get_data(
+    cs: *const sys::godot_char_string,
+) -> *const c_char {
+    match &*sys::godot_char_string::borrow(cs) {
+        Some(bytes) => bytes.as_ptr() as *const c_char,
+        None => b"\0".as_ptr() as *const c_char,
+    }
+}
+
+unsafe extern "C" fn godot_char_string_destroy(cs: *mut sys::godot_char_string) {
+    drop(sys::godot_char_string::take(cs));
+}
+
+/// Returns the unique, never deallocated data for a name.
+fn intern(name: String) -> Option<&'static String> {
+    lazy_names(|names| {
+        if name.is_empty() {
+            return None;
+        }
+        let interned = names
+            .entry(name.clone())
+            .or_insert_with(|| Box::leak(Box::new(name)));
+        Some(&**interned)
+    })
+}
+
+fn lazy_names<R>(f: impl FnOnce(&mut HashMap<String, &'static String>) -> R) -> R {
+    static NAMES: Mutex<Option<HashMap<String, &'static String>>> = Mutex::new(None);
+    let mut names = NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    f(names.get_or_insert_with(HashMap::new))
+}
+
+unsafe extern "C" fn godot_string_name_new(
+    dest: *mut sys::godot_string_name,
+    name: *const sys::godot_string,
+) {
+    sys::godot_string_name::init(dest, intern(string_of(name)));
+}
+
+unsafe extern "C" fn godot_string_name_new_data(
+    dest: *mut sys::godot_string_name,
+    name: *const c_char,
+) {
+    let name = String::from_utf8_lossy(c_str_of(name)).into_owned();
+    sys::godot_string_name::init(dest, intern(name));
+}
+
+unsafe extern "C" fn godot_string_name_get_name(
+    this: *const sys::godot_string_name,
+) -> sys::godot_string {
+    match *sys::godot_string_name::borrow(this) {
+        Some(name) => new_string(name),
+        None => new_string(""),
+    }
+}
+
+unsafe extern "C" fn godot_string_name_get_hash(this: *const sys::godot_string_name) -> u32 {
+    match *sys::godot_string_name::borrow(this) {
+        Some(name) => hash_units(name.chars().map(|c| c as u32)),
+        None => 0,
+    }
+}
+
+unsafe extern "C" fn godot_string_name_get_data_unique_pointer(
+    this: *const sys::godot_string_name,
+) -> *const c_void {
+    match *sys::godot_string_name::borrow(this) {
+        Some(name) => name as *const String as *const c_void,
+        None => std::ptr::null(),
+    }
+}
+
+unsafe extern "C" fn godot_string_name_operator_equal(
+    this: *const sys::godot_string_name,
+    other: *const sys::godot_string_name,
+) -> sys::godot_bool {
+    godot_string_name_get_data_unique_pointer(this)
+        == godot_string_name_get_data_unique_pointer(other)
+}
+
+unsafe extern "C" fn godot_string_name_operator_less(
+    this: *const sys::godot_string_name,
+    other: *const sys::godot_string_name,
+) -> sys::godot_bool {
+    godot_string_name_get_data_unique_pointer(this)
+        < godot_string_name_get_data_unique_pointer(other)
+}
+
+unsafe extern "C" fn godot_string_name_destroy(this: *mut sys::godot_string_name) {
+    drop(sys::godot_string_name::take(this));
+}
+
+pub(super) fn install(api: &mut GodotApi) {
+    install!(api {
+        godot_string_new,
+        godot_string_new_copy,
+        godot_string_new_with_wide_string,
+        godot_string_destroy,
+        godot_string_chars_to_utf8,
+        godot_string_chars_to_utf8_with_len,
+        godot_string_parse_utf8,
+        godot_string_parse_utf8_with_len,
+        godot_string_wide_str,
+        godot_string_operator_index,
+        godot_string_operator_index_const,
+        godot_string_ord_at,
+        godot_string_operator_equal,
+        godot_string_operator_less,
+        godot_string_operator_plus,
+        godot_string_length,
+        godot_string_empty,
+        godot_string_casecmp_to,
+        godot_string_nocasecmp_to,
+        godot_string_naturalnocasecmp_to,
+        godot_string_begins_with,
+        godot_string_begins_with_char_array,
+        godot_string_ends_with,
+        godot_string_is_subsequence_of,
+        godot_string_is_subsequence_ofi,
+        godot_string_match,
+        godot_string_matchn,
+        godot_string_bigrams,
+        godot_string_chr,
+        godot_string_find,
+        godot_string_find_from,
+        godot_string_findn,
+        godot_string_findn_from,
+        godot_string_find_last,
+        godot_string_rfind,
+        godot_string_rfind_from,
+        godot_string_rfindn,
+        godot_string_rfindn_from,
+        godot_string_format,
+        godot_string_format_with_custom_placeholder,
+        godot_string_hex_encode_buffer,
+        godot_string_hex_to_int,
+        godot_string_hex_to_int_without_prefix,
+        godot_string_hex_to_int64,
+        godot_string_hex_to_int64_with_prefix,
+        godot_string_insert,
+        godot_string_erase,
+        godot_string_is_numeric,
+        godot_string_lpad,
+        godot_string_lpad_with_custom_character,
+        godot_string_rpad,
+        godot_string_rpad_with_custom_character,
+        godot_string_num,
+        godot_string_num_int64,
+        godot_string_num_int64_capitalized,
+        godot_string_num_real,
+        godot_string_num_with_decimals,
+        godot_string_pad_decimals,
+        godot_string_pad_zeros,
+        godot_string_replace_first,
+        godot_string_replace,
+        godot_string_replacen,
+        godot_string_similarity,
+        godot_string_substr,
+        godot_string_to_double,
+        godot_string_to_float,
+        godot_string_to_int,
+        godot_string_to_int64,
+        godot_string_camelcase_to_underscore,
+        godot_string_camelcase_to_underscore_lowercased,
+        godot_string_capitalize,
+        godot_string_char_to_double,
+        godot_string_char_to_int,
+        godot_string_char_to_int_with_len,
+        godot_string_wchar_to_int,
+        godot_string_char_to_int64_with_len,
+        godot_string_get_slice_count,
+        godot_string_get_slice,
+        godot_string_get_slicec,
+        godot_string_split,
+        godot_string_split_allow_empty,
+        godot_string_split_floats,
+        godot_string_split_floats_allows_empty,
+        godot_string_split_ints,
+        godot_string_split_ints_allows_empty,
+        godot_string_split_spaces,
+        godot_string_rsplit,
+        godot_string_char_lowercase,
+        godot_string_char_uppercase,
+        godot_string_to_lower,
+        godot_string_to_upper,
+        godot_string_get_basename,
+        godot_string_get_extension,
+        godot_string_left,
+        godot_string_right,
+        godot_string_plus_file,
+        godot_string_strip_edges,
+        godot_string_strip_escapes,
+        godot_string_trim_prefix,
+        godot_string_trim_suffix,
+        godot_string_rstrip,
+        godot_string_ascii,
+        godot_string_ascii_extended,
+        godot_string_utf8,
+        godot_string_hash,
+        godot_string_hash64,
+        godot_string_hash_chars,
+        godot_string_hash_chars_with_len,
+        godot_string_hash_utf8_chars,
+        godot_string_hash_utf8_chars_with_len,
+        godot_string_get_base_dir,
+        godot_string_get_file,
+        godot_string_is_abs_path,
+        godot_string_is_rel_path,
+        godot_string_is_resource_file,
+        godot_string_simplify_path,
+        godot_string_c_escape,
+        godot_string_c_escape_multiline,
+        godot_string_c_unescape,
+        godot_string_http_escape,
+        godot_string_http_unescape,
+        godot_string_json_escape,
+        godot_string_xml_escape,
+        godot_string_xml_escape_with_quotes,
+        godot_string_xml_unescape,
+        godot_string_percent_decode,
+        godot_string_percent_encode,
+        godot_string_is_valid_float,
+        godot_string_is_valid_hex_number,
+        godot_string_is_valid_html_color,
+        godot_string_is_valid_identifier,
+        godot_string_is_valid_integer,
+        godot_string_is_valid_ip_address,
+        godot_char_string_length,
+        godot_char_string_get_data,
+        godot_char_string_destroy,
+        godot_string_name_new,
+        godot_string_name_new_data,
+        godot_string_name_get_name,
+        godot_string_name_get_hash,
+        godot_string_name_get_data_unique_pointer,
+        godot_string_name_operator_equal,
+        godot_string_name_operator_less,
+        godot_string_name_destroy,
+    });
+}
@@ -0,0 +1,981 @@
+use super::array::{self, ArrayRepr};
+use super::dictionary::DictionaryRepr;
+use super::node_path::{self, NodePathRepr};
+use super::pool_array::PoolRepr;
+use super::string::{self, StringRepr};
+use super::{Element, Elements, Opaque, Shared};
+use crate::sys;
+use crate::GodotApi;
+
+use std::fmt;
+use std::mem;
+use std::ptr;
+
+/// The contents of a variant. `Nil` is stored as `None`, so that zeroed variants are valid.
+unsafe impl Opaque for sys::godot_variant {
+    type Repr = Option<Box<Value>>;
+}
+
+impl Element for sys::godot_variant {
+    fn new_default() -> Self {
+        sys::godot_variant::from_repr(None)
+    }
+
+    fn duplicate(&self) -> Self {
+        unsafe { sys::godot_variant::from_repr((*sys::godot_variant::borrow(self)).clone()) }
+    }
+
+    fn destroy(&mut self) {
+        unsafe { drop(sys::godot_variant::take(self)) }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum Value {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Real(f64),
+    String(StringRepr),
+    Vector2(sys::godot_vector2),
+    Rect2(sys::godot_rect2),
+    Vector3(sys::godot_vector3),
+    Transform2D(sys::godot_transform2d),
+    Plane(sys::godot_plane),
+    Quat(sys::godot_quat),
+    Aabb(sys::godot_aabb),
+    Basis(sys::godot_basis),
+    Transform(sys::godot_transform),
+    Color(sys::godot_color),
+    NodePath(NodePathRepr),
+    Rid(sys::godot_rid),
+    Object(*mut sys::godot_object),
+    Dictionary(DictionaryRepr),
+    Array(ArrayRepr),
+    ByteArray(PoolRepr<u8>),
+    IntArray(PoolRepr<sys::godot_int>),
+    RealArray(PoolRepr<sys::godot_real>),
+    StringArray(PoolRepr<sys::godot_string>),
+    Vector2Array(PoolRepr<sys::godot_vector2>),
+    Vector3Array(PoolRepr<sys::godot_vector3>),
+    ColorArray(PoolRepr<sys::godot_color>),
+}
+
+/// Creates a new variant holding `value`.
+pub(crate) fn new_variant(value: Value) -> sys::godot_variant {
+    match value {
+        Value::Nil => sys::godot_variant::from_repr(None),
+        value => sys::godot_variant::from_repr(Some(Box::new(value))),
+    }
+}
+
+/// Returns a copy of the contents of a variant.
+pub(crate) unsafe fn value_of(v: *const sys::godot_variant) -> Value {
+    match &*sys::godot_variant::borrow(v) {
+        Some(value) => (**value).clone(),
+        None => Value::Nil,
+    }
+}
+
+/// Reads the real components of a geometry type.
+pub(crate) fn reals_of<T: Copy>(value: &T) -> Vec<sys::godot_real> {
+    let count = mem::size_of::<T>() / mem::size_of::<sys::godot_real>();
+    let ptr = value as *const T as *const sys::godot_real;
+    (0..count)
+        .map(|i| unsafe { ptr::read_unaligned(ptr.add(i)) })
+        .collect()
+}
+
+/// Creates a geometry type from its real components.
+pub(crate) fn from_reals<T: Default>(reals: &[sys::godot_real]) -> T {
+    debug_assert_eq!(
+        reals.len() * mem::size_of::<sys::godot_real>(),
+        mem::size_of::<T>()
+    );
+    let mut value = T::default();
+    let ptr = &mut value as *mut T as *mut sys::godot_real;
+    for (i, &real) in reals.iter().enumerate() {
+        unsafe { ptr::write_unaligned(ptr.add(i), real) };
+    }
+    value
+}
+
+const IDENTITY_TRANSFORM2D: [sys::godot_real; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+const IDENTITY_QUAT: [sys::godot_real; 4] = [0.0, 0.0, 0.0, 1.0];
+const IDENTITY_BASIS: [sys::godot_real; 9] = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+const IDENTITY_TRANSFORM: [sys::godot_real; 12] =
+    [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0];
+const DEFAULT_COLOR: [sys::godot_real; 4] = [0.0, 0.0, 0.0, 1.0];
+
+fn rid_id(rid: &sys::godot_rid) -> usize {
+    unsafe { ptr::read_unaligned(rid as *const sys::godot_rid as *const usize) }
+}
+
+fn join_reals(reals: &[sys::godot_real], separator: &str) -> String {
+    reals
+        .iter()
+        .map(|&r| string::num(f64::from(r), -1))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn join_elements<T: Element, F: Fn(&T) -> String>(elements: &[T], f: F) -> String {
+    let items = elements.iter().map(f).collect::<Vec<_>>();
+    format!("[{}]", items.join(", "))
+}
+
+fn pool_elements<T: Element>(pool: &PoolRepr<T>) -> &[T] {
+    match pool {
+        Some(shared) => &shared[..],
+        None => &[],
+    }
+}
+
+impl Value {
+    pub(crate) fn variant_type(&self) -> sys::godot_variant_type {
+        match self {
+            Value::Nil => sys::godot_variant_type_GODOT_VARIANT_TYPE_NIL,
+            Value::Bool(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_BOOL,
+            Value::Int(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_INT,
+            Value::Real(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_REAL,
+            Value::String(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_STRING,
+            Value::Vector2(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_VECTOR2,
+            Value::Rect2(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_RECT2,
+            Value::Vector3(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_VECTOR3,
+            Value::Transform2D(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_TRANSFORM2D,
+            Value::Plane(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_PLANE,
+            Value::Quat(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_QUAT,
+            Value::Aabb(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_AABB,
+            Value::Basis(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_BASIS,
+            Value::Transform(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_TRANSFORM,
+            Value::Color(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_COLOR,
+            Value::NodePath(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_NODE_PATH,
+            Value::Rid(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_RID,
+            Value::Object(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_OBJECT,
+            Value::Dictionary(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_DICTIONARY,
+            Value::Array(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_ARRAY,
+            Value::ByteArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_BYTE_ARRAY,
+            Value::IntArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_INT_ARRAY,
+            Value::RealArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_REAL_ARRAY,
+            Value::StringArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_STRING_ARRAY,
+            Value::Vector2Array(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_VECTOR2_ARRAY,
+            Value::Vector3Array(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_VECTOR3_ARRAY,
+            Value::ColorArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_COLOR_ARRAY,
+        }
+    }
+
+    /// Returns the real components of geometry values.
+    fn reals(&self) -> Option<Vec<sys::godot_real>> {
+        match self {
+            Value::Vector2(v) => Some(reals_of(v)),
+            Value::Rect2(v) => Some(reals_of(v)),
+            Value::Vector3(v) => Some(reals_of(v)),
+            Value::Transform2D(v) => Some(reals_of(v)),
+            Value::Plane(v) => Some(reals_of(v)),
+            Value::Quat(v) => Some(reals_of(v)),
+            Value::Aabb(v) => Some(reals_of(v)),
+            Value::Basis(v) => Some(reals_of(v)),
+            Value::Transform(v) => Some(reals_of(v)),
+            Value::Color(v) => Some(reals_of(v)),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of arrays and pool arrays as values.
+    pub(crate) fn to_values(&self) -> Option<Vec<Value>> {
+        fn values<T: Element + ToValue>(pool: &PoolRepr<T>) -> Vec<Value> {
+            pool_elements(pool).iter().map(ToValue::to_value).collect()
+        }
+
+        match self {
+            Value::Array(arr) => Some(arr.iter().map(|v| unsafe { value_of(v) }).collect()),
+            Value::ByteArray(pool) => Some(values(pool)),
+            Value::IntArray(pool) => Some(values(pool)),
+            Value::RealArray(pool) => Some(values(pool)),
+            Value::StringArray(pool) => Some(values(pool)),
+            Value::Vector2Array(pool) => Some(values(pool)),
+            Value::Vector3Array(pool) => Some(values(pool)),
+            Value::ColorArray(pool) => Some(values(pool)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        fn is_default(reals: &[sys::godot_real], default: &[sys::godot_real]) -> bool {
+            reals == default
+        }
+
+        match self {
+            Value::Nil => true,
+            Value::Bool(b) => !b,
+            Value::Int(i) => *i == 0,
+            Value::Real(r) => *r == 0.0,
+            Value::String(s) => s.is_none(),
+            Value::Transform2D(v) => is_default(&reals_of(v), &IDENTITY_TRANSFORM2D),
+            Value::Quat(v) => is_default(&reals_of(v), &IDENTITY_QUAT),
+            Value::Basis(v) => is_default(&reals_of(v), &IDENTITY_BASIS),
+            Value::Transform(v) => is_default(&reals_of(v), &IDENTITY_TRANSFORM),
+            Value::Color(v) => is_default(&reals_of(v), &DEFAULT_COLOR),
+            Value::NodePath(np) => np.is_none(),
+            Value::Rid(rid) => rid_id(rid) == 0,
+            Value::Object(obj) => obj.is_null(),
+            Value::Dictionary(dict) => dict.is_empty(),
+            Value::Array(arr) => arr.is_empty(),
+            Value::ByteArray(pool) => pool_elements(pool).is_empty(),
+            Value::IntArray(pool) => pool_elements(pool).is_empty(),
+            Value::RealArray(pool) => pool_elements(pool).is_empty(),
+            Value::StringArray(pool) => pool_elements(pool).is_empty(),
+            Value::Vector2Array(pool) => pool_elements(pool).is_empty(),
+            Value::Vector3Array(pool) => pool_elements(pool).is_empty(),
+            Value::ColorArray(pool) => pool_elements(pool).is_empty(),
+            value => value.reals().unwrap().iter().all(|&r| r == 0.0),
+        }
+    }
+
+    pub(crate) fn as_int(&self) -> i64 {
+        match self {
+            Value::Bool(b) => i64::from(*b),
+            Value::Int(i) => *i,
+            Value::Real(r) => *r as i64,
+            Value::String(s) => {
+                string::to_int64(&string::repr_to_string(s).chars().collect::<Vec<_>>())
+            }
+            _ => 0,
+        }
+    }
+
+    pub(crate) fn as_real(&self) -> f64 {
+        match self {
+            Value::Bool(b) => f64::from(u8::from(*b)),
+            Value::Int(i) => *i as f64,
+            Value::Real(r) => *r,
+            Value::String(s) => string::to_double(&string::repr_to_string(s)),
+            _ => 0.0,
+        }
+    }
+
+    /// Compares values like the engine's `Variant::operator==`, which requires equal types.
+    pub(crate) fn equals(&self, other: &Value) -> bool {
+        if self.variant_type() != other.variant_type() {
+            return false;
+        }
+        match (self, other) {
+            (Value::Nil, Value::Nil) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Real(a), Value::Real(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::NodePath(a), Value::NodePath(b)) => a == b,
+            (Value::Rid(a), Value::Rid(b)) => rid_id(a) == rid_id(b),
+            (Value::Object(a), Value::Object(b)) => a == b,
+            (Value::Dictionary(a), Value::Dictionary(b)) => a.ptr_eq(b),
+            (a, b) => match (a.reals(), b.reals()) {
+                (Some(a), Some(b)) => a == b,
+                _ => {
+                    let a = a.to_values().unwrap_or_default();
+                    let b = b.to_values().unwrap_or_default();
+                    a.len() == b.len() && a.iter().zip(&b).all(|(a, b)| a.equals(b))
+                }
+            },
+        }
+    }
+
+    /// Compares values like the engine's `Variant::operator<`, which orders different types
+    /// by their type ids.
+    pub(crate) fn less(&self, other: &Value) -> bool {
+        if self.variant_type() != other.variant_type() {
+            return self.variant_type() < other.variant_type();
+        }
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => a < b,
+            (Value::Int(a), Value::Int(b)) => a < b,
+            (Value::Real(a), Value::Real(b)) => a < b,
+            (Value::String(a), Value::String(b)) => {
+                string::repr_to_string(a) < string::repr_to_string(b)
+            }
+            (Value::Vector2(_), Value::Vector2(_)) | (Value::Vector3(_), Value::Vector3(_)) => {
+                self.reals() < other.reals()
+            }
+            (Value::Rid(a), Value::Rid(b)) => rid_id(a) < rid_id(b),
+            (Value::Object(a), Value::Object(b)) => a < b,
+            (a, b) => match (a.to_values(), b.to_values()) {
+                (Some(a), Some(b)) => {
+                    for (a, b) in a.iter().zip(&b) {
+                        if a.less(b) {
+                            return true;
+                        }
+                        if b.less(a) {
+                            return false;
+                        }
+                    }
+                    a.len() < b.len()
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Compares values like the engine's `OP_LESS`, which also compares integers with reals.
+    /// Returns `None` for values that can't be compared.
+    pub(crate) fn try_less(&self, other: &Value) -> Option<bool> {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => Some(a < b),
+            (Value::Int(_), Value::Real(_))
+            | (Value::Real(_), Value::Int(_))
+            | (Value::Real(_), Value::Real(_)) => Some(self.as_real() < other.as_real()),
+            (Value::Bool(_), Value::Bool(_))
+            | (Value::String(_), Value::String(_))
+            | (Value::Vector2(_), Value::Vector2(_))
+            | (Value::Vector3(_), Value::Vector3(_))
+            | (Value::Rid(_), Value::Rid(_))
+            | (Value::Object(_), Value::Object(_)) => Some(self.less(other)),
+            _ => None,
+        }
+    }
+
+    /// Compares values like the engine's `Variant::hash_compare`, which treats NaN as equal
+    /// to itself. This is the comparison used for dictionary keys.
+    pub(crate) fn hash_compare(&self, other: &Value) -> bool {
+        fn reals_eq(a: &[sys::godot_real], b: &[sys::godot_real]) -> bool {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|(a, b)| a == b || (a.is_nan() && b.is_nan()))
+        }
+
+        if self.variant_type() != other.variant_type() {
+            return false;
+        }
+        match (self, other) {
+            (Value::Real(a), Value::Real(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Value::Array(_), Value::Array(_)) => {
+                let a = self.to_values().unwrap();
+                let b = other.to_values().unwrap();
+                a.len() == b.len() && a.iter().zip(&b).all(|(a, b)| a.hash_compare(b))
+            }
+            (a, b) => match (a.reals(), b.reals()) {
+                (Some(a), Some(b)) => reals_eq(&a, &b),
+                _ => a.equals(b),
+            },
+        }
+    }
+
+    pub(crate) fn hash(&self) -> u32 {
+        fn hash_one(value: u32, hash: u32) -> u32 {
+            (hash << 5).wrapping_add(hash).wrapping_add(value)
+        }
+
+        match self {
+            Value::Nil => 0,
+            Value::Bool(b) => u32::from(*b),
+            Value::Int(i) => hash_one((*i >> 32) as u32, *i as u32),
+            Value::Real(r) => {
+                let bits = r.to_bits();
+                hash_one((bits >> 32) as u32, bits as u32)
+            }
+            Value::Object(obj) => *obj as usize as u32,
+            Value::Dictionary(dict) => {
+                dict.iter()
+                    .fold(hash_one(self.variant_type(), 5381), |h, (k, v)| unsafe {
+                        hash_one(value_of(v).hash(), hash_one(value_of(k).hash(), h))
+                    })
+            }
+            value => match value.to_values() {
+                Some(values) => values
+                    .iter()
+                    .fold(hash_one(0, 5381), |h, v| hash_one(v.hash(), h)),
+                None => value
+                    .to_string()
+                    .chars()
+                    .fold(5381, |h, c| hash_one(c as u32, h)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    /// Formats values like the engine's `Variant::operator String`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reals = self.reals().unwrap_or_default();
+        match self {
+            Value::Nil => write!(f, "Null"),
+            Value::Bool(true) => write!(f, "True"),
+            Value::Bool(false) => write!(f, "False"),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Real(r) => write!(f, "{}", string::num(*r, -1)),
+            Value::String(s) => write!(f, "{}", string::repr_to_string(s)),
+            Value::Vector2(_) | Value::Vector3(_) | Value::Rect2(_) | Value::Quat(_) => {
+                write!(f, "({})", join_reals(&reals, ", "))
+            }
+            Value::Transform2D(_) => write!(
+                f,
+                "(({}), ({}), ({}))",
+                join_reals(&reals[0..2], ", "),
+                join_reals(&reals[2..4], ", "),
+                join_reals(&reals[4..6], ", "),
+            ),
+            Value::Plane(_) => write!(f, "{}", join_reals(&reals, ", ")),
+            Value::Aabb(_) => write!(
+                f,
+                "{} - {}",
+                join_reals(&reals[0..3], ", "),
+                join_reals(&reals[3..6], ", "),
+            ),
+            Value::Basis(_) => write!(
+                f,
+                "(({}), ({}), ({}))",
+                join_reals(&reals[0..3], ", "),
+                join_reals(&reals[3..6], ", "),
+                join_reals(&reals[6..9], ", "),
+            ),
+            Value::Transform(_) => write!(
+                f,
+                "{} - {}",
+                join_reals(&reals[0..9], ", "),
+                join_reals(&reals[9..12], ", "),
+            ),
+            Value::Color(_) => write!(f, "{}", join_reals(&reals, ",")),
+            Value::NodePath(np) => write!(f, "{}", node_path::repr_to_string(np)),
+            Value::Rid(_) => write!(f, "[RID]"),
+            Value::Object(obj) if obj.is_null() => write!(f, "[Object:null]"),
+            Value::Object(_) => write!(f, "[Object]"),
+            Value::Dictionary(dict) => {
+                let mut pairs = dict
+                    .iter()
+                    .map(|(k, v)| unsafe { (value_of(k).to_string(), value_of(v).to_string()) })
+                    .collect::<Vec<_>>();
+                pairs.sort();
+                let pairs = pairs
+                    .into_iter()
+                    .map(|(k, v)| format!("{}:{}", k, v))
+                    .collect::<Vec<_>>();
+                write!(f, "{{{}}}", pairs.join(", "))
+            }
+            Value::Array(arr) => write!(
+                f,
+                "{}",
+                join_elements(arr, |v| unsafe { value_of(v).to_string() })
+            ),
+            value => {
+                let values = value.to_values().unwrap();
+                let items = values.iter().map(Value::to_string).collect::<Vec<_>>();
+                write!(f, "[{}]", items.join(", "))
+            }
+        }
+    }
+}
+
+/// Conversion of container elements into values.
+pub(crate) trait ToValue {
+    fn to_value(&self) -> Value;
+}
+
+/// Conversion of values into container elements, like the engine's `Variant` conversion
+/// operators.
+pub(crate) trait FromValue: Sized {
+    fn from_value(value: &Value) -> Self;
+}
+
+impl ToValue for u8 {
+    fn to_value(&self) -> Value {
+        Value::Int(i64::from(*self))
+    }
+}
+
+impl FromValue for u8 {
+    fn from_value(value: &Value) -> Self {
+        value.as_int() as u8
+    }
+}
+
+impl ToValue for sys::godot_int {
+    fn to_value(&self) -> Value {
+        Value::Int(i64::from(*self))
+    }
+}
+
+impl FromValue for sys::godot_int {
+    fn from_value(value: &Value) -> Self {
+        value.as_int() as sys::godot_int
+    }
+}
+
+impl ToValue for sys::godot_real {
+    fn to_value(&self) -> Value {
+        Value::Real(f64::from(*self))
+    }
+}
+
+impl FromValue for sys::godot_real {
+    fn from_value(value: &Value) -> Self {
+        value.as_real() as sys::godot_real
+    }
+}
+
+impl ToValue for sys::godot_string {
+    fn to_value(&self) -> Value {
+        Value::String(unsafe { (*sys::godot_string::borrow(self)).clone() })
+    }
+}
+
+impl FromValue for sys::godot_string {
+    fn from_value(value: &Value) -> Self {
+        match value {
+            Value::String(s) => sys::godot_string::from_repr(s.clone()),
+            value => string::new_string(&value.to_string()),
+        }
+    }
+}
+
+impl ToValue for sys::godot_variant {
+    fn to_value(&self) -> Value {
+        unsafe { value_of(self) }
+    }
+}
+
+impl FromValue for sys::godot_variant {
+    fn from_value(value: &Value) -> Self {
+        new_variant(value.clone())
+    }
+}
+
+impl ToValue for sys::godot_vector2 {
+    fn to_value(&self) -> Value {
+        Value::Vector2(*self)
+    }
+}
+
+impl FromValue for sys::godot_vector2 {
+    fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Vector2(v) => *v,
+            Value::Vector3(v) => from_reals(&reals_of(v)[0..2]),
+            _ => sys::godot_vector2::default(),
+        }
+    }
+}
+
+impl ToValue for sys::godot_vector3 {
+    fn to_value(&self) -> Value {
+        Value::Vector3(*self)
+    }
+}
+
+impl FromValue for sys::godot_vector3 {
+    fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Vector3(v) => *v,
+            Value::Vector2(v) => {
+                let reals = reals_of(v);
+                from_reals(&[reals[0], reals[1], 0.0])
+            }
+            _ => sys::godot_vector3::default(),
+        }
+    }
+}
+
+impl ToValue for sys::godot_color {
+    fn to_value(&self) -> Value {
+        Value::Color(*self)
+    }
+}
+
+impl FromValue for sys::godot_color {
+    fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Color(c) => *c,
+            _ => from_reals(&DEFAULT_COLOR),
+        }
+    }
+}
+
+macro_rules! impl_geometry_from_value {
+    ($($Type:ty => $Variant:ident, $default:expr;)*) => {
+        $(
+            impl FromValue for $Type {
+                fn from_value(value: &Value) -> Self {
+                    match value {
+                        Value::$Variant(v) => *v,
+                        _ => $default,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_geometry_from_value! {
+    sys::godot_rect2 => Rect2, sys::godot_rect2::default();
+    sys::godot_transform2d => Transform2D, from_reals(&IDENTITY_TRANSFORM2D);
+    sys::godot_plane => Plane, sys::godot_plane::default();
+    sys::godot_quat => Quat, from_reals(&IDENTITY_QUAT);
+    sys::godot_aabb => Aabb, sys::godot_aabb::default();
+    sys::godot_basis => Basis, from_reals(&IDENTITY_BASIS);
+    sys::godot_transform => Transform, from_reals(&IDENTITY_TRANSFORM);
+    sys::godot_rid => Rid, sys::godot_rid::default();
+}
+
+/// Converts arrays and pool arrays into pool array contents.
+pub(crate) fn to_pool<T: Element + FromValue>(value: &Value) -> PoolRepr<T> {
+    let values = value.to_values().unwrap_or_default();
+    if values.is_empty() {
+        None
+    } else {
+        Some(Shared::new(Elements(
+            values.iter().map(T::from_value).collect(),
+        )))
+    }
+}
+
+macro_rules! variant_constructors {
+    ($($name:ident($Type:ty) => $make:expr;)*) => {
+        $(
+            unsafe extern "C" fn $name(dest: *mut sys::godot_variant, src: *const $Type) {
+                #[allow(clippy::redundant_closure_call)]
+                let value = ($make)(src);
+                sys::godot_variant::init(dest, Some(Box::new(value)));
+            }
+        )*
+    };
+}
+
+variant_constructors! {
+    godot_variant_new_string(sys::godot_string) =>
+        |s: *const sys::godot_string| Value::String((*sys::godot_string::borrow(s)).clone());
+    godot_variant_new_vector2(sys::godot_vector2) => |v: *const _| Value::Vector2(*v);
+    godot_variant_new_rect2(sys::godot_rect2) => |v: *const _| Value::Rect2(*v);
+    godot_variant_new_vector3(sys::godot_vector3) => |v: *const _| Value::Vector3(*v);
+    godot_variant_new_transform2d(sys::godot_transform2d) => |v: *const _| Value::Transform2D(*v);
+    godot_variant_new_plane(sys::godot_plane) => |v: *const _| Value::Plane(*v);
+    godot_variant_new_quat(sys::godot_quat) => |v: *const _| Value::Quat(*v);
+    godot_variant_new_aabb(sys::godot_aabb) => |v: *const _| Value::Aabb(*v);
+    godot_variant_new_basis(sys::godot_basis) => |v: *const _| Value::Basis(*v);
+    godot_variant_new_transform(sys::godot_transform) => |v: *const _| Value::Transform(*v);
+    godot_variant_new_color(sys::godot_color) => |v: *const _| Value::Color(*v);
+    godot_variant_new_rid(sys::godot_rid) => |v: *const _| Value::Rid(*v);
+    godot_variant_new_node_path(sys::godot_node_path) =>
+        |np: *const sys::godot_node_path| Value::NodePath((*sys::godot_node_path::borrow(np)).clone());
+    godot_variant_new_dictionary(sys::godot_dictionary) =>
+        |d: *const sys::godot_dictionary| Value::Dictionary((*sys::godot_dictionary::borrow(d)).clone());
+    godot_variant_new_array(sys::godot_array) =>
+        |a: *const sys::godot_array| Value::Array((*sys::godot_array::borrow(a)).clone());
+    godot_variant_new_pool_byte_array(sys::godot_pool_byte_array) =>
+        |a: *const sys::godot_pool_byte_array| Value::ByteArray((*sys::godot_pool_byte_array::borrow(a)).clone());
+    godot_variant_new_pool_int_array(sys::godot_pool_int_array) =>
+        |a: *const sys::godot_pool_int_array| Value::IntArray((*sys::godot_pool_int_array::borrow(a)).clone());
+    godot_variant_new_pool_real_array(sys::godot_pool_real_array) =>
+        |a: *const sys::godot_pool_real_array| Value::RealArray((*sys::godot_pool_real_array::borrow(a)).clone());
+    godot_variant_new_pool_string_array(sys::godot_pool_string_array) =>
+        |a: *const sys::godot_pool_string_array| Value::StringArray((*sys::godot_pool_string_array::borrow(a)).clone());
+    godot_variant_new_pool_vector2_array(sys::godot_pool_vector2_array) =>
+        |a: *const sys::godot_pool_vector2_array| Value::Vector2Array((*sys::godot_pool_vector2_array::borrow(a)).clone());
+    godot_variant_new_pool_vector3_array(sys::godot_pool_vector3_array) =>
+        |a: *const sys::godot_pool_vector3_array| Value::Vector3Array((*sys::godot_pool_vector3_array::borrow(a)).clone());
+    godot_variant_new_pool_color_array(sys::godot_pool_color_array) =>
+        |a: *const sys::godot_pool_color_array| Value::ColorArray((*sys::godot_pool_color_array::borrow(a)).clone());
+}
+
+unsafe extern "C" fn godot_variant_get_type(
+    v: *const sys::godot_variant,
+) -> sys::godot_variant_type {
+    value_of(v).variant_type()
+}
+
+unsafe extern "C" fn godot_variant_new_copy(
+    dest: *mut sys::godot_variant,
+    src: *const sys::godot_variant,
+) {
+    sys::godot_variant::init(dest, (*sys::godot_variant::borrow(src)).clone());
+}
+
+unsafe extern "C" fn godot_variant_new_nil(dest: *mut sys::godot_variant) {
+    sys::godot_variant::init(dest, None);
+}
+
+unsafe extern "C" fn godot_variant_new_bool(dest: *mut sys::godot_variant, b: sys::godot_bool) {
+    sys::godot_variant::init(dest, Some(Box::new(Value::Bool(b))));
+}
+
+unsafe extern "C" fn godot_variant_new_uint(dest: *mut sys::godot_variant, i: u64) {
+    sys::godot_variant::init(dest, Some(Box::new(Value::Int(i as i64))));
+}
+
+unsafe extern "C" fn godot_variant_new_int(dest: *mut sys::godot_variant, i: i64) {
+    sys::godot_variant::init(dest, Some(Box::new(Value::Int(i))));
+}
+
+unsafe extern "C" fn godot_variant_new_real(dest: *mut sys::godot_variant, r: f64) {
+    sys::godot_variant::init(dest, Some(Box::new(Value::Real(r))));
+}
+
+unsafe extern "C" fn godot_variant_new_object(
+    dest: *mut sys::godot_variant,
+    obj: *const sys::godot_object,
+) {
+    sys::godot_variant::init(dest, Some(Box::new(Value::Object(obj as *mut _))));
+}
+
+unsafe extern "C" fn godot_variant_as_bool(this: *const sys::godot_variant) -> sys::godot_bool {
+    !value_of(this).is_zero()
+}
+
+unsafe extern "C" fn godot_variant_as_uint(this: *const sys::godot_variant) -> u64 {
+    value_of(this).as_int() as u64
+}
+
+unsafe extern "C" fn godot_variant_as_int(this: *const sys::godot_variant) -> i64 {
+    value_of(this).as_int()
+}
+
+unsafe extern "C" fn godot_variant_as_real(this: *const sys::godot_variant) -> f64 {
+    value_of(this).as_real()
+}
+
+unsafe extern "C" fn godot_variant_as_string(this: *const sys::godot_variant) -> sys::godot_string {
+    sys::godot_string::from_value(&value_of(this))
+}
+
+macro_rules! variant_conversions {
+    ($($name:ident -> $Type:ty;)*) => {
+        $(
+            unsafe extern "C" fn $name(this: *const sys::godot_variant) -> $Type {
+                <$Type>::from_value(&value_of(this))
+            }
+        )*
+    };
+}
+
+variant_conversions! {
+    godot_variant_as_vector2 -> sys::godot_vector2;
+    godot_variant_as_rect2 -> sys::godot_rect2;
+    godot_variant_as_vector3 -> sys::godot_vector3;
+    godot_variant_as_transform2d -> sys::godot_transform2d;
+    godot_variant_as_plane -> sys::godot_plane;
+    godot_variant_as_quat -> sys::godot_quat;
+    godot_variant_as_aabb -> sys::godot_aabb;
+    godot_variant_as_basis -> sys::godot_basis;
+    godot_variant_as_transform -> sys::godot_transform;
+    godot_variant_as_color -> sys::godot_color;
+    godot_variant_as_rid -> sys::godot_rid;
+}
+
+unsafe extern "C" fn godot_variant_as_node_path(
+    this: *const sys::godot_variant,
+) -> sys::godot_node_path {
+    let repr = match value_of(this) {
+        Value::NodePath(np) => np,
+        Value::String(s) => node_path::parse(&string::repr_to_string(&s)),
+        _ => None,
+    };
+    sys::godot_node_path::from_repr(repr)
+}
+
+unsafe extern "C" fn godot_variant_as_object(
+    this: *const sys::godot_variant,
+) -> *mut sys::godot_object {
+    match value_of(this) {
+        Value::Object(obj) => obj,
+        _ => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn godot_variant_as_dictionary(
+    this: *const sys::godot_variant,
+) -> sys::godot_dictionary {
+    match value_of(this) {
+        Value::Dictionary(dict) => sys::godot_dictionary::from_repr(dict),
+        _ => sys::godot_dictionary::from_repr(Shared::new(Elements::new())),
+    }
+}
+
+unsafe extern "C" fn godot_variant_as_array(this: *const sys::godot_variant) -> sys::godot_array {
+    match value_of(this) {
+        Value::Array(arr) => sys::godot_array::from_repr(arr),
+        value => array::new_array(
+            value
+                .to_values()
+                .unwrap_or_default()
+                .into_iter()
+                .map(new_variant)
+                .collect(),
+        ),
+    }
+}
+
+macro_rules! variant_pool_conversions {
+    ($($name:ident -> $Type:ident, $Variant:ident;)*) => {
+        $(
+            unsafe extern "C" fn $name(this: *const sys::godot_variant) -> sys::$Type {
+                match value_of(this) {
+                    Value::$Variant(pool) => sys::$Type::from_repr(pool),
+                    value => sys::$Type::from_repr(to_pool(&value)),
+                }
+            }
+        )*
+    };
+}
+
+variant_pool_conversions! {
+    godot_variant_as_pool_byte_array -> godot_pool_byte_array, ByteArray;
+    godot_variant_as_pool_int_array -> godot_pool_int_array, IntArray;
+    godot_variant_as_pool_real_array -> godot_pool_real_array, RealArray;
+    godot_variant_as_pool_string_array -> godot_pool_string_array, StringArray;
+    godot_variant_as_pool_vector2_array -> godot_pool_vector2_array, Vector2Array;
+    godot_variant_as_pool_vector3_array -> godot_pool_vector3_array, Vector3Array;
+    godot_variant_as_pool_color_array -> godot_pool_color_array, ColorArray;
+}
+
+unsafe extern "C" fn godot_variant_call(
+    _this: *mut sys::godot_variant,
+    _method: *const sys::godot_string,
+    _args: *mut *const sys::godot_variant,
+    _argcount: sys::godot_int,
+    error: *mut sys::godot_variant_call_error,
+) -> sys::godot_variant {
+    if !error.is_null() {
+        (*error).error =
+            sys::godot_variant_call_error_error_GODOT_CALL_ERROR_CALL_ERROR_INVALID_METHOD;
+        (*error).argument = 0;
+        (*error).expected = sys::godot_variant_type_GODOT_VARIANT_TYPE_NIL;
+    }
+    new_variant(Value::Nil)
+}
+
+unsafe extern "C" fn godot_variant_has_method(
+    _this: *const sys::godot_variant,
+    _method: *const sys::godot_string,
+) -> sys::godot_bool {
+    false
+}
+
+unsafe extern "C" fn godot_variant_operator_equal(
+    this: *const sys::godot_variant,
+    other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    value_of(this).equals(&value_of(other))
+}
+
+unsafe extern "C" fn godot_variant_operator_less(
+    this: *const sys::godot_variant,
+    other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    value_of(this).less(&value_of(other))
+}
+
+unsafe extern "C" fn godot_variant_hash_compare(
+    this: *const sys::godot_variant,
+    other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    value_of(this).hash_compare(&value_of(other))
+}
+
+unsafe extern "C" fn godot_variant_booleanize(this: *const sys::godot_variant) -> sys::godot_bool {
+    !value_of(this).is_zero()
+}
+
+unsafe extern "C" fn godot_variant_destroy(this: *mut sys::godot_variant) {
+    drop(sys::godot_variant::take(this));
+}
+
+unsafe extern "C" fn godot_variant_get_operator_name(
+    op: sys::godot_variant_operator,
+) -> sys::godot_string {
+    const NAMES: [&str; 25] = [
+        "==",
+        "!=",
+        "<",
+        "<=",
+        ">",
+        ">=",
+        "+",
+        "-",
+        "*",
+        "/",
+        "- (negation)",
+        "+ (positive)",
+        "%",
+        "+ (concatenation)",
+        "<<",
+        ">>",
+        "&",
+        "|",
+        "^",
+        "~",
+        "and",
+        "or",
+        "xor",
+        "not",
+        "in",
+    ];
+    string::new_string(NAMES.get(op as usize).copied().unwrap_or(""))
+}
+
+pub(super) fn install(api: &mut GodotApi) {
+    install!(api {
+        godot_variant_get_type,
+        godot_variant_new_copy,
+        godot_variant_new_nil,
+        godot_variant_new_bool,
+        godot_variant_new_uint,
+        godot_variant_new_int,
+        godot_variant_new_real,
+        godot_variant_new_string,
+        godot_variant_new_vector2,
+        godot_variant_new_rect2,
+        godot_variant_new_vector3,
+        godot_variant_new_transform2d,
+        godot_variant_new_plane,
+        godot_variant_new_quat,
+        godot_variant_new_aabb,
+        godot_variant_new_basis,
+        godot_variant_new_transform,
+        godot_variant_new_color,
+        godot_variant_new_node_path,
+        godot_variant_new_rid,
+        godot_variant_new_object,
+        godot_variant_new_dictionary,
+        godot_variant_new_array,
+        godot_variant_new_pool_byte_array,
+        godot_variant_new_pool_int_array,
+        godot_variant_new_pool_real_array,
+        godot_variant_new_pool_string_array,
+        godot_variant_new_pool_vector2_array,
+        godot_variant_new_pool_vector3_array,
+        godot_variant_new_pool_color_array,
+        godot_variant_as_bool,
+        godot_variant_as_uint,
+        godot_variant_as_int,
+        godot_variant_as_real,
+        godot_variant_as_string,
+        godot_variant_as_vector2,
+        godot_variant_as_rect2,
+        godot_variant_as_vector3,
+        godot_variant_as_transform2d,
+        godot_variant_as_plane,
+        godot_variant_as_quat,
+        godot_variant_as_aabb,
+        godot_variant_as_basis,
+        godot_variant_as_transform,
+        godot_variant_as_color,
+        godot_variant_as_node_path,
+        godot_variant_as_rid,
+        godot_variant_as_object,
+        godot_variant_as_dictionary,
+        godot_variant_as_array,
+        godot_variant_as_pool_byte_array,
+        godot_variant_as_pool_int_array,
+        godot_variant_as_pool_real_array,
+        godot_variant_as_pool_string_array,
+        godot_variant_as_pool_vector2_array,
+        godot_variant_as_pool_vector3_array,
+        godot_variant_as_pool_color_array,
+        godot_variant_call,
+        godot_variant_has_method,
+        godot_variant_operator_equal,
+        godot_variant_operator_less,
+        godot_variant_hash_compare,
+        godot_variant_booleanize,
+        godot_variant_destroy,
+        godot_variant_get_operator_name,
+    });
+}
@@ -0,0 +1,142 @@
+use crate::{GodotString, StringName};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{PoisonError, RwLock};
+
+/// Names interned so far. Entries are leaked boxes that are never freed, so references to
+/// them stay valid for the lifetime of the program.
+static CACHE: RwLock<Option<HashMap<&'static str, &'static CachedName>>> = RwLock::new(None);
+
+/// A `StringName` interned in the global name cache, together with its name as a
+/// `GodotString`.
+///
+/// Cached names are created once per distinct string. They are usually obtained with the
+/// [`sname!`](macro.sname.html) macro, and are meant for method and signal names that are
+/// used repeatedly, e.g. every frame:
+///
+/// - `CachedName` dereferences to `GodotString`, so it can be passed to methods taking a
+///   `&GodotString`, like `Variant::call` and `Variant::has_method`.
+/// - Generated methods that take a `GodotString` by value, like `Object::emit_signal`,
+///   accept `sname!("name").into()`, which only creates a new reference to the cached
+///   string instead of allocating a new one.
+///
+/// The GDNative API looks methods up by `godot_string`, so the savings come from reusing
+/// the cached `GodotString`. The interned `StringName` is only provided for APIs that take
+/// one directly.
+///
+/// A `CachedName` is never freed, and neither are its strings, so references to it stay valid
+/// as long as the engine is running, even if the library is terminated and initialized
+/// again.
+pub struct CachedName {
+    name: &'static str,
+    string_name: StringName,
+    string: GodotString,
+}
+
+// Safety: cached names are never mutated after being created, and both Godot strings and
+// string names are reference-counted atomically by the engine.
+unsafe impl Send for CachedName {}
+unsafe impl Sync for CachedName {}
+
+impl CachedName {
+    /// Returns the cached name for `name`, interning it if it wasn't cached already.
+    pub fn get(name: &'static str) -> &'static CachedName {
+        if let Some(cached) = CACHE
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .and_then(|cache| cache.get(name))
+        {
+            return cached;
+        }
+
+        let mut cache = CACHE.write().unwrap_or_else(PoisonError::into_inner);
+        cache
+            .get_or_insert_with(HashMap::new)
+            .entry(name)
+            .or_insert_with(|| {
+                let string = GodotString::from_str(name);
+                let string_name = StringName::from_godot_string(&string);
+                Box::leak(Box::new(CachedName {
+                    name,
+                    string_name,
+                    string,
+                }))
+            })
+    }
+
+    /// Returns the name as a string slice.
+    pub fn as_str(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the interned `StringName`.
+    pub fn string_name(&self) -> &StringName {
+        &self.string_name
+    }
+
+    /// Returns the name as a `GodotString`.
+    pub fn godot_string(&self) -> &GodotString {
+        &self.string
+    }
+}
+
+impl Deref for CachedName {
+    type Target = GodotString;
+
+    fn deref(&self) -> &GodotString {
+        &self.string
+    }
+}
+
+impl AsRef<GodotString> for CachedName {
+    fn as_ref(&self) -> &GodotString {
+        &self.string
+    }
+}
+
+impl AsRef<StringName> for CachedName {
+    fn as_ref(&self) -> &StringName {
+        &self.string_name
+    }
+}
+
+impl<'a> From<&'a CachedName> for GodotString {
+    fn from(name: &'a CachedName) -> GodotString {
+        name.string.new_ref()
+    }
+}
+
+impl fmt::Debug for CachedName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.name.fmt(f)
+    }
+}
+
+godot_test!(test_name_cache {
+    use crate::{GodotString, StringName, Variant};
+
+    let tick = sname!("tick");
+    assert_eq!("tick", tick.as_str());
+    assert!(std::ptr::eq(tick, sname!("tick")));
+    assert!(std::ptr::eq(tick, CachedName::get("tick")));
+    assert!(!std::ptr::eq(tick, sname!("bar")));
+
+    assert!(*tick.string_name() == StringName::from_str("tick"));
+    assert_eq!(*tick.godot_string(), "tick");
+    assert_eq!(4, tick.len());
+
+    let string: GodotString = tick.into();
+    assert_eq!(string, "tick");
+    assert_eq!(Variant::from_godot_string(tick).to_string(), "tick");
+
+    let from_threads = (0..4)
+        .map(|_| std::thread::spawn(|| sname!("threaded") as *const CachedName as usize))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .collect::<Vec<_>>();
+    assert!(from_threads.iter().all(|&ptr| ptr == from_threads[0]));
+});
@@ -2,6 +2,7 @@ use crate::get_api;
 use crate::sys;
 use crate::GodotString;
 use std::fmt;
+use std::ops::Range;
 
 /// A reference-counted relative or absolute path in a scene tree, for use with `Node.get_node()` and similar
 /// functions. It can reference a node, a resource within a node, or a property of a node or
@@ -14,6 +15,8 @@ use std::fmt;
 ///
 /// If a string is passed to `Node.get_node()`, it will be automatically converted to a `NodePath`,
 /// but `NodePath` can be parsed ahead of time with `NodePath::from_str` or `NodePath::new`.
+/// `NodePath::parse` additionally reports malformed paths, and `NodePath::builder` builds a
+/// path from its parts.
 ///
 /// A `NodePath` consists of node names, “sub-node” (resource) names, and the name of a property in
 /// the final node or resource.
@@ -40,6 +43,52 @@ impl NodePath {
         }
     }
 
+    /// Parses a `NodePath` from a string, returning an error if the path is malformed.
+    ///
+    /// Unlike `from_str`, which produces an empty path for invalid input like the engine does,
+    /// this rejects empty node names (e.g. `"Player//Sprite"` or `"Player/"`) and empty
+    /// subnames (e.g. `"Sprite::modulate"`). A single trailing colon, which refers to a
+    /// resource, is allowed.
+    pub fn parse(path: &str) -> Result<Self, NodePathError> {
+        if path.is_empty() {
+            return Ok(NodePath::from_str(path));
+        }
+
+        let (names, subpath) = match path.find(':') {
+            Some(pos) => (&path[..pos], Some(&path[pos + 1..])),
+            None => (path, None),
+        };
+
+        let names = names.strip_prefix('/').unwrap_or(names);
+        if !names.is_empty() && names.split('/').any(str::is_empty) {
+            return Err(NodePathError::EmptyName);
+        }
+
+        if let Some(subpath) = subpath {
+            let subpath = subpath.strip_suffix(':').unwrap_or(subpath);
+            if !subpath.is_empty() && subpath.split(':').any(str::is_empty) {
+                return Err(NodePathError::EmptySubname);
+            }
+        }
+
+        Ok(NodePath::from_str(path))
+    }
+
+    /// Returns a builder for a `NodePath`, e.g.
+    /// `NodePath::builder().child("Player").child("Sprite").property("modulate").build()`.
+    pub fn builder() -> NodePathBuilder {
+        NodePathBuilder::default()
+    }
+
+    /// Returns a builder initialized with the parts of this path.
+    pub fn to_builder(&self) -> NodePathBuilder {
+        NodePathBuilder {
+            absolute: self.is_absolute(),
+            names: self.names().map(|name| name.to_string()).collect(),
+            subnames: self.subnames().map(|name| name.to_string()).collect(),
+        }
+    }
+
     /// Create a `NodePath` from a GodotString.
     pub fn new(path: &GodotString) -> Self {
         unsafe {
@@ -60,8 +109,31 @@ impl NodePath {
     }
 
     /// Get the number of node names which make up the path.
-    pub fn name_count(&mut self) -> i32 {
-        unsafe { (get_api().godot_node_path_get_name_count)(&mut self.0) }
+    pub fn name_count(&self) -> i32 {
+        unsafe { (get_api().godot_node_path_get_name_count)(&self.0) }
+    }
+
+    /// Returns the node name of the specified `idx`, 0 to name_count()
+    pub fn get_name(&self, idx: i32) -> GodotString {
+        unsafe { GodotString((get_api().godot_node_path_get_name)(&self.0, idx)) }
+    }
+
+    /// Returns an iterator over the node names in the path.
+    pub fn names(&self) -> NodePathNames<'_> {
+        NodePathNames {
+            path: self,
+            subnames: false,
+            range: 0..self.name_count(),
+        }
+    }
+
+    /// Returns an iterator over the resource and property names in the path.
+    pub fn subnames(&self) -> NodePathNames<'_> {
+        NodePathNames {
+            path: self,
+            subnames: true,
+            range: 0..self.get_subname_count(),
+        }
     }
 
     /// Returns the resource name of the specified `idx`, 0 to subname_count()
@@ -82,6 +154,76 @@ impl NodePath {
         }
     }
 
+    /// Appends the node names and subnames of `other` to the node names of this path.
+    ///
+    /// The subnames of this path are discarded. If `other` is absolute, it replaces this path.
+    /// `"."` and `".."` are kept as they are.
+    pub fn join(&self, other: &NodePath) -> NodePath {
+        if other.is_absolute() {
+            return other.new_ref();
+        }
+
+        let mut builder = self.to_builder();
+        builder
+            .names
+            .extend(other.names().map(|name| name.to_string()));
+        builder.subnames = other.subnames().map(|name| name.to_string()).collect();
+        builder.build_unchecked()
+    }
+
+    /// Returns the path to the parent of the node this path refers to, without subnames.
+    ///
+    /// Returns `None` if the path has no node names. The parent of a relative path with a
+    /// single name is `"."`.
+    pub fn parent(&self) -> Option<NodePath> {
+        let mut builder = self.to_builder();
+        builder.names.pop()?;
+        builder.subnames.clear();
+        if builder.names.is_empty() && !builder.absolute {
+            builder.names.push(".".into());
+        }
+        Some(builder.build_unchecked())
+    }
+
+    /// Returns the relative path from the node at `base` to this path, e.g. `"../Enemy:position"`
+    /// from `"/root/Level/Player"` to `"/root/Level/Enemy:position"`.
+    ///
+    /// The paths are compared lexically after resolving `"."` and `".."`, and the subnames of
+    /// `base` are ignored. Returns `None` if only one of the paths is absolute, or if `base`
+    /// leaves the known part of the tree, e.g. from `"../A"` to `"B"`.
+    pub fn relative_to(&self, base: &NodePath) -> Option<NodePath> {
+        if self.is_absolute() != base.is_absolute() {
+            return None;
+        }
+
+        let absolute = self.is_absolute();
+        let target = self.to_builder();
+        let target_names = normalize_names(target.names, absolute)?;
+        let base_names = normalize_names(base.to_builder().names, absolute)?;
+
+        let common = target_names
+            .iter()
+            .zip(&base_names)
+            .take_while(|(a, b)| a == b)
+            .count();
+        if base_names[common..].iter().any(|name| name == "..") {
+            return None;
+        }
+
+        let mut names = vec![String::from(".."); base_names.len() - common];
+        names.extend_from_slice(&target_names[common..]);
+        if names.is_empty() {
+            names.push(".".into());
+        }
+
+        let builder = NodePathBuilder {
+            absolute: false,
+            names,
+            subnames: target.subnames,
+        };
+        Some(builder.build_unchecked())
+    }
+
     /// Returns the `NodePath` as a `GodotString`
     pub fn to_godot_string(&self) -> GodotString {
         unsafe { GodotString((get_api().godot_node_path_as_string)(&self.0)) }
@@ -107,6 +249,182 @@ impl NodePath {
     }
 }
 
+/// Resolves `"."` and `".."` in a list of node names. Returns `None` if an absolute path goes
+/// above the root.
+fn normalize_names(names: Vec<String>, absolute: bool) -> Option<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(names.len());
+    for name in names {
+        match name.as_str() {
+            "." => {}
+            ".." => match normalized.last() {
+                Some(last) if last != ".." => {
+                    normalized.pop();
+                }
+                _ if absolute => return None,
+                _ => normalized.push(name),
+            },
+            _ => normalized.push(name),
+        }
+    }
+    Some(normalized)
+}
+
+/// Error returned when parsing or building a malformed `NodePath`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NodePathError {
+    /// A node name is empty.
+    EmptyName,
+    /// A resource or property name is empty.
+    EmptySubname,
+    /// A name contains a separator, which is `/` or `:` for node names and `:` for subnames.
+    InvalidName { name: String, separator: char },
+}
+
+impl fmt::Display for NodePathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NodePathError::EmptyName => write!(f, "node name is empty"),
+            NodePathError::EmptySubname => write!(f, "subname is empty"),
+            NodePathError::InvalidName { name, separator } => {
+                write!(f, "name {:?} contains the separator {:?}", name, separator)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NodePathError {}
+
+/// Builder for a `NodePath`, created with `NodePath::builder` or `NodePath::to_builder`.
+///
+/// Names are validated when the path is built.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodePathBuilder {
+    absolute: bool,
+    names: Vec<String>,
+    subnames: Vec<String>,
+}
+
+impl NodePathBuilder {
+    /// Makes the path absolute.
+    pub fn absolute(mut self) -> Self {
+        self.absolute = true;
+        self
+    }
+
+    /// Makes the path relative. This is the default.
+    pub fn relative(mut self) -> Self {
+        self.absolute = false;
+        self
+    }
+
+    /// Appends a node name. `"."` and `".."` refer to the current node and its parent.
+    pub fn child<S>(mut self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.names.push(name.into());
+        self
+    }
+
+    /// Appends a resource name.
+    pub fn subname<S>(mut self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.subnames.push(name.into());
+        self
+    }
+
+    /// Appends a property name. The property is the last subname of a path, so this is the
+    /// same as `subname`.
+    pub fn property<S>(self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.subname(name)
+    }
+
+    /// Builds the path, returning an error if a name is empty or contains a separator.
+    pub fn build(&self) -> Result<NodePath, NodePathError> {
+        for name in &self.names {
+            if name.is_empty() {
+                return Err(NodePathError::EmptyName);
+            }
+            if let Some(separator) = name.chars().find(|&c| c == '/' || c == ':') {
+                return Err(NodePathError::InvalidName {
+                    name: name.clone(),
+                    separator,
+                });
+            }
+        }
+
+        for name in &self.subnames {
+            if name.is_empty() {
+                return Err(NodePathError::EmptySubname);
+            }
+            if name.contains(':') {
+                return Err(NodePathError::InvalidName {
+                    name: name.clone(),
+                    separator: ':',
+                });
+            }
+        }
+
+        Ok(self.build_unchecked())
+    }
+
+    fn build_unchecked(&self) -> NodePath {
+        let mut path = if self.absolute {
+            String::from("/")
+        } else {
+            String::new()
+        };
+        path.push_str(&self.names.join("/"));
+        for subname in &self.subnames {
+            path.push(':');
+            path.push_str(subname);
+        }
+        NodePath::from_str(&path)
+    }
+}
+
+/// Iterator over the node names or subnames of a `NodePath`.
+pub struct NodePathNames<'a> {
+    path: &'a NodePath,
+    subnames: bool,
+    range: Range<i32>,
+}
+
+impl<'a> NodePathNames<'a> {
+    fn get(&self, idx: i32) -> GodotString {
+        if self.subnames {
+            self.path.get_subname(idx)
+        } else {
+            self.path.get_name(idx)
+        }
+    }
+}
+
+impl<'a> Iterator for NodePathNames<'a> {
+    type Item = GodotString;
+
+    fn next(&mut self) -> Option<GodotString> {
+        self.range.next().map(|idx| self.get(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for NodePathNames<'a> {
+    fn next_back(&mut self) -> Option<GodotString> {
+        self.range.next_back().map(|idx| self.get(idx))
+    }
+}
+
+impl<'a> ExactSizeIterator for NodePathNames<'a> {}
+
 impl<S> From<S> for NodePath
 where
     S: AsRef<str>,
@@ -146,3 +464,62 @@ impl fmt::Debug for NodePath {
         write!(f, "NodePath({})", self.to_string())
     }
 }
+
+godot_test!(test_node_path {
+    use crate::{NodePath, NodePathError};
+
+    let path = NodePath::builder()
+        .absolute()
+        .child("root")
+        .child("Player")
+        .child("Sprite")
+        .property("modulate")
+        .subname("a")
+        .build()
+        .unwrap();
+    assert_eq!("/root/Player/Sprite:modulate:a", path.to_string());
+    assert!(path.is_absolute());
+    assert_eq!(3, path.name_count());
+    assert_eq!(
+        vec!["root", "Player", "Sprite"],
+        path.names().map(|name| name.to_string()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["a", "modulate"],
+        path.subnames().rev().map(|name| name.to_string()).collect::<Vec<_>>()
+    );
+    assert_eq!(path, path.to_builder().build().unwrap());
+
+    assert_eq!(
+        Err(NodePathError::InvalidName { name: "A/B".into(), separator: '/' }),
+        NodePath::builder().child("A/B").build()
+    );
+    assert_eq!(Err(NodePathError::EmptyName), NodePath::builder().child("").build());
+    assert_eq!(Err(NodePathError::EmptySubname), NodePath::builder().subname("").build());
+
+    assert_eq!(Ok(NodePath::from_str("A/B:c")), NodePath::parse("A/B:c"));
+    assert_eq!(Ok(NodePath::from_str("/A:res:")), NodePath::parse("/A:res:"));
+    assert!(NodePath::parse("").unwrap().is_empty());
+    assert!(!NodePath::parse("/").unwrap().is_empty());
+    assert_eq!(Err(NodePathError::EmptyName), NodePath::parse("A//B"));
+    assert_eq!(Err(NodePathError::EmptyName), NodePath::parse("A/"));
+    assert_eq!(Err(NodePathError::EmptySubname), NodePath::parse("A::b"));
+
+    let base = NodePath::from_str("/root/Level");
+    assert_eq!("/root/Level/Enemy:position", base.join(&"Enemy:position".into()).to_string());
+    assert_eq!("/other", base.join(&"/other".into()).to_string());
+
+    assert_eq!("/root/Player", path.parent().unwrap().to_string());
+    assert_eq!("/", NodePath::from_str("/root").parent().unwrap().to_string());
+    assert_eq!(".", NodePath::from_str("Sprite").parent().unwrap().to_string());
+    assert!(NodePath::from_str("/").parent().is_none());
+
+    let enemy = NodePath::from_str("/root/Level/Enemy:position");
+    let relative = |to: &NodePath, from: &str| to.relative_to(&from.into()).map(|p| p.to_string());
+    assert_eq!(Some("../Enemy:position".into()), relative(&enemy, "/root/Level/Player"));
+    assert_eq!(Some("Enemy:position".into()), relative(&enemy, "/root/./Level"));
+    assert_eq!(Some(".".into()), relative(&base, "/root/Level/Player/.."));
+    assert_eq!(Some("../../B".into()), relative(&"A/../B".into(), "C/D"));
+    assert_eq!(None, relative(&enemy, "Player"));
+    assert_eq!(None, relative(&"B".into(), "../A"));
+});
@@ -1,4 +1,4 @@
-use crate::{Angle, Point2, Vector2};
+use crate::{real, Angle, Point2, Vector2};
 use euclid::Trig;
 
 /// Helper methods for `Point2`.
@@ -12,10 +12,10 @@ pub trait Point2Godot {
     /// Returns the normalized vector pointing from this point to `other`.
     fn direction_to(self, other: Point2) -> Vector2;
     /// Returns the distance to `other`.
-    fn distance_to(self, other: Point2) -> f32;
+    fn distance_to(self, other: Point2) -> real;
     /// Returns the squared distance to `other`. Prefer this function over distance_to if you
     /// need to sort points or need the squared distance for some formula.
-    fn distance_squared_to(self, other: Point2) -> f32;
+    fn distance_squared_to(self, other: Point2) -> real;
 }
 
 impl Point2Godot for Point2 {
@@ -30,12 +30,12 @@ impl Point2Godot for Point2 {
     }
 
     #[inline]
-    fn distance_to(self, other: Point2) -> f32 {
+    fn distance_to(self, other: Point2) -> real {
         (other - self).length()
     }
 
     #[inline]
-    fn distance_squared_to(self, other: Point2) -> f32 {
+    fn distance_squared_to(self, other: Point2) -> real {
         (other - self).square_length()
     }
 }
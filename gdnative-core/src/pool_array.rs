@@ -0,0 +1,1279 @@
+use std::convert::TryFrom;
+use std::iter::{Extend, FromIterator};
+use std::marker::PhantomData;
+use std::mem::transmute;
+use std::ops::Range;
+use std::slice;
+
+use crate::access::{Aligned, Guard, MaybeUnaligned, WritePtr};
+use crate::get_api;
+use crate::real;
+use crate::sys;
+use crate::Color;
+use crate::GodotApi;
+use crate::GodotString;
+use crate::VariantArray;
+use crate::Vector2;
+use crate::Vector3;
+
+/// A reference-counted copy-on-write vector that uses Godot's pool allocator, generic over
+/// the possible element types.
+///
+/// The element types are listed in the implementations of `PoolElement`. Each of them also
+/// has a type alias, e.g. `ByteArray` for `PoolArray<u8>`.
+///
+/// `PoolArray` doesn't implement `Index`, since a reference into the array's buffer can't
+/// outlive the read access it was taken from. Use `get` to copy an element, or index a
+/// `read()` access to borrow it, e.g. `array.read()[i]`.
+pub struct PoolArray<T: PoolElement> {
+    inner: T::SysArray,
+}
+
+/// A reference-counted vector of bytes that uses Godot's pool allocator.
+pub type ByteArray = PoolArray<u8>;
+
+/// A reference-counted vector of `i32` that uses Godot's pool allocator.
+pub type Int32Array = PoolArray<i32>;
+
+/// A reference-counted vector of `real` that uses Godot's pool allocator. This is the
+/// engine's `PoolRealArray`.
+///
+/// Elements are `f64` if the `double-precision` feature is enabled.
+pub type RealArray = PoolArray<real>;
+
+/// A reference-counted vector of `f32` that uses Godot's pool allocator.
+///
+/// This is the same type as `RealArray`. It is not available with the `double-precision`
+/// feature, where the elements of `RealArray` are `f64`.
+#[cfg(not(feature = "double-precision"))]
+pub type Float32Array = PoolArray<f32>;
+
+/// A reference-counted vector of `GodotString` that uses Godot's pool allocator.
+pub type StringArray = PoolArray<GodotString>;
+
+/// A reference-counted vector of `Vector2` that uses Godot's pool allocator.
+pub type Vector2Array = PoolArray<Vector2>;
+
+/// A reference-counted vector of `Vector3` that uses Godot's pool allocator.
+pub type Vector3Array = PoolArray<Vector3>;
+
+/// A reference-counted vector of `Color` that uses Godot's pool allocator.
+pub type ColorArray = PoolArray<Color>;
+
+/// Read access to the contents of a `PoolArray`.
+pub type Read<'a, T> = Aligned<ReadGuard<'a, T>>;
+
+/// Write access to the contents of a `PoolArray`.
+pub type Write<'a, T> = Aligned<WriteGuard<'a, T>>;
+
+impl<T: PoolElement> PoolArray<T> {
+    /// Creates an empty array.
+    pub fn new() -> Self {
+        PoolArray::default()
+    }
+
+    /// Creates an array by trying to convert each variant.
+    ///
+    /// When no viable conversion exists, the default value of the element type is pushed.
+    pub fn from_variant_array(array: &VariantArray) -> Self {
+        unsafe {
+            let mut inner = T::SysArray::default();
+            (T::new_with_array_fn(get_api()))(&mut inner, &array.0);
+            PoolArray { inner }
+        }
+    }
+
+    /// Creates an array with copies of the elements of a slice.
+    ///
+    /// The array is resized once, and the elements are copied through a write access, which
+    /// amounts to a `memcpy` for all element types but `GodotString`.
+    ///
+    /// # Panics
+    ///
+    /// If the slice is longer than `i32::MAX` elements.
+    pub fn from_slice(src: &[T]) -> Self {
+        let mut array = PoolArray::new();
+        array.clone_from_slice(src);
+        array
+    }
+
+    /// Creates an array from the elements of a `Vec`.
+    pub fn from_vec(src: Vec<T>) -> Self {
+        PoolArray::from_slice(&src)
+    }
+
+    /// Replaces the contents of the array with copies of the elements of a slice, resizing it
+    /// to the length of the slice.
+    ///
+    /// # Panics
+    ///
+    /// If the slice is longer than `i32::MAX` elements.
+    pub fn clone_from_slice(&mut self, src: &[T]) {
+        self.resize(slice_len(src));
+        self.write().clone_from_slice(src);
+    }
+
+    /// Replaces the contents of the array with a `memcpy` of a slice, resizing it to the length
+    /// of the slice.
+    ///
+    /// # Panics
+    ///
+    /// If the slice is longer than `i32::MAX` elements.
+    pub fn copy_from_slice(&mut self, src: &[T])
+    where
+        T: Copy,
+    {
+        self.resize(slice_len(src));
+        self.write().copy_from_slice(src);
+    }
+
+    /// Copies the elements of the array into a `Vec`.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.read().to_vec()
+    }
+
+    /// Appends an element to the end of the array.
+    pub fn push(&mut self, val: &T) {
+        unsafe {
+            (T::append_fn(get_api()))(&mut self.inner, val.element_to_sys_ref());
+        }
+    }
+
+    /// Appends each element of another array to the end of this array.
+    pub fn push_array(&mut self, array: &PoolArray<T>) {
+        unsafe {
+            (T::append_array_fn(get_api()))(&mut self.inner, &array.inner);
+        }
+    }
+
+    // TODO(error handling)
+    /// Inserts an element at the given offset.
+    pub fn insert(&mut self, offset: i32, val: &T) -> bool {
+        unsafe {
+            let status =
+                (T::insert_fn(get_api()))(&mut self.inner, offset, val.element_to_sys_ref());
+            status != sys::godot_error_GODOT_OK
+        }
+    }
+
+    /// Inverts the order of the elements in the array.
+    pub fn invert(&mut self) {
+        unsafe { (T::invert_fn(get_api()))(&mut self.inner) }
+    }
+
+    /// Removes an element at the given offset.
+    pub fn remove(&mut self, idx: i32) {
+        unsafe {
+            (T::remove_fn(get_api()))(&mut self.inner, idx);
+        }
+    }
+
+    /// Changes the size of the array, possibly removing elements or pushing default values.
+    pub fn resize(&mut self, size: i32) {
+        unsafe {
+            (T::resize_fn(get_api()))(&mut self.inner, size);
+        }
+    }
+
+    /// Returns a copy of the element at the given offset.
+    pub fn get(&self, idx: i32) -> T {
+        unsafe { T::element_from_sys((T::get_fn(get_api()))(&self.inner, idx)) }
+    }
+
+    /// Sets the value of the element at the given offset.
+    pub fn set(&mut self, idx: i32, val: &T) {
+        unsafe {
+            (T::set_fn(get_api()))(&mut self.inner, idx, val.element_to_sys_ref());
+        }
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn len(&self) -> i32 {
+        unsafe { (T::size_fn(get_api()))(&self.inner) }
+    }
+
+    /// Returns `true` if the array contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a scoped read-only view into this array.
+    pub fn read(&self) -> Read<'_, T> {
+        unsafe {
+            MaybeUnaligned::new(ReadGuard::new(self.sys()))
+                .try_into_aligned()
+                .expect("Pool array access should be aligned. This indicates a bug in Godot")
+        }
+    }
+
+    /// Returns a scoped read-write view into this array. The array is copied first if its
+    /// contents are shared with another reference.
+    pub fn write(&mut self) -> Write<'_, T> {
+        unsafe {
+            MaybeUnaligned::new(WriteGuard::new(self.sys() as *mut _))
+                .try_into_aligned()
+                .expect("Pool array access should be aligned. This indicates a bug in Godot")
+        }
+    }
+
+    /// Returns an iterator over copies of the elements.
+    ///
+    /// The iterator holds a read access to the array for as long as it lives.
+    pub fn iter(&self) -> PoolArrayIter<'_, T> {
+        let read = self.read();
+        let range = 0..read.len();
+        PoolArrayIter { read, range }
+    }
+
+    /// Creates a new reference to this reference-counted instance.
+    pub fn new_ref(&self) -> Self {
+        unsafe {
+            let mut inner = T::SysArray::default();
+            (T::new_copy_fn(get_api()))(&mut inner, &self.inner);
+            PoolArray { inner }
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn sys(&self) -> *const T::SysArray {
+        &self.inner
+    }
+
+    #[doc(hidden)]
+    pub fn from_sys(sys: T::SysArray) -> Self {
+        PoolArray { inner: sys }
+    }
+}
+
+impl PoolArray<GodotString> {
+    /// Appends a `StringArray` at the end of this array.
+    #[deprecated(note = "use `push_array` instead")]
+    pub fn push_string_array(&mut self, strings: &StringArray) {
+        self.push_array(strings)
+    }
+}
+
+impl<T: PoolElement> Drop for PoolArray<T> {
+    fn drop(&mut self) {
+        unsafe { (T::destroy_fn(get_api()))(&mut self.inner) }
+    }
+}
+
+impl<T: PoolElement> Default for PoolArray<T> {
+    fn default() -> Self {
+        unsafe {
+            let mut inner = T::SysArray::default();
+            (T::new_fn(get_api()))(&mut inner);
+            PoolArray { inner }
+        }
+    }
+}
+
+impl<T: PoolElement> FromIterator<T> for PoolArray<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        PoolArray::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<T: PoolElement> Extend<T> for PoolArray<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(&val);
+        }
+    }
+}
+
+impl<'a, T: PoolElement> IntoIterator for &'a PoolArray<T> {
+    type Item = T;
+    type IntoIter = PoolArrayIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over copies of the elements of a `PoolArray`.
+pub struct PoolArrayIter<'a, T: PoolElement> {
+    read: Read<'a, T>,
+    range: Range<usize>,
+}
+
+impl<'a, T: PoolElement> Iterator for PoolArrayIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|idx| self.read[idx].clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, T: PoolElement> DoubleEndedIterator for PoolArrayIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|idx| self.read[idx].clone())
+    }
+}
+
+impl<'a, T: PoolElement> ExactSizeIterator for PoolArrayIter<'a, T> {}
+
+/// Guard for read access to the contents of a `PoolArray`.
+pub struct ReadGuard<'a, T: PoolElement> {
+    access: *mut T::SysReadAccess,
+    len: usize,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T: PoolElement> ReadGuard<'a, T> {
+    unsafe fn new(arr: *const T::SysArray) -> Self {
+        let api = get_api();
+        let len = (T::size_fn(api))(arr) as usize;
+        let access = (T::read_fn(api))(arr);
+        ReadGuard {
+            access,
+            len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<'a, T: PoolElement> Guard for ReadGuard<'a, T> {
+    type Target = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn read_ptr(&self) -> *const T {
+        unsafe { (T::read_access_ptr_fn(get_api()))(self.access) as *const T }
+    }
+}
+
+impl<'a, T: PoolElement> Drop for ReadGuard<'a, T> {
+    fn drop(&mut self) {
+        unsafe { (T::read_access_destroy_fn(get_api()))(self.access) }
+    }
+}
+
+impl<'a, T: PoolElement> Clone for ReadGuard<'a, T> {
+    fn clone(&self) -> Self {
+        let access = unsafe { (T::read_access_copy_fn(get_api()))(self.access) };
+        ReadGuard {
+            access,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Guard for write access to the contents of a `PoolArray`.
+pub struct WriteGuard<'a, T: PoolElement> {
+    access: *mut T::SysWriteAccess,
+    len: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T: PoolElement> WriteGuard<'a, T> {
+    unsafe fn new(arr: *mut T::SysArray) -> Self {
+        let api = get_api();
+        let len = (T::size_fn(api))(arr) as usize;
+        let access = (T::write_fn(api))(arr);
+        WriteGuard {
+            access,
+            len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<'a, T: PoolElement> Guard for WriteGuard<'a, T> {
+    type Target = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn read_ptr(&self) -> *const T {
+        unsafe { (T::write_access_ptr_fn(get_api()))(self.access) as *const T }
+    }
+}
+
+unsafe impl<'a, T: PoolElement> WritePtr for WriteGuard<'a, T> {}
+
+impl<'a, T: PoolElement> Drop for WriteGuard<'a, T> {
+    fn drop(&mut self) {
+        unsafe { (T::write_access_destroy_fn(get_api()))(self.access) }
+    }
+}
+
+impl<'a, T: F32Components> Read<'a, T> {
+    /// Returns the components of all elements as a flat slice, e.g. `[x0, y0, z0, x1, ...]`
+    /// for `Vector3`.
+    pub fn as_f32_slice(&self) -> &[f32] {
+        let elements = self.as_slice();
+        unsafe {
+            slice::from_raw_parts(
+                elements.as_ptr() as *const f32,
+                elements.len() * T::COMPONENTS,
+            )
+        }
+    }
+}
+
+impl<'a, T: F32Components> Write<'a, T> {
+    /// Returns the components of all elements as a flat slice, e.g. `[x0, y0, z0, x1, ...]`
+    /// for `Vector3`.
+    pub fn as_f32_slice(&self) -> &[f32] {
+        let elements = self.as_slice();
+        unsafe {
+            slice::from_raw_parts(
+                elements.as_ptr() as *const f32,
+                elements.len() * T::COMPONENTS,
+            )
+        }
+    }
+
+    /// Returns the components of all elements as a flat mutable slice, e.g.
+    /// `[x0, y0, z0, x1, ...]` for `Vector3`.
+    pub fn as_f32_slice_mut(&mut self) -> &mut [f32] {
+        let elements = self.as_mut_slice();
+        unsafe {
+            slice::from_raw_parts_mut(
+                elements.as_mut_ptr() as *mut f32,
+                elements.len() * T::COMPONENTS,
+            )
+        }
+    }
+}
+
+/// Returns the length of `src` as the size of a pool array.
+fn slice_len<T>(src: &[T]) -> i32 {
+    i32::try_from(src.len()).expect("slice is too long for a pool array")
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Trait for element types that can be contained in `PoolArray`. This trait is sealed and has
+/// no public interface.
+///
+/// Implementors must have the same memory layout as their counterparts in `sys`, since the
+/// read and write accesses hand out the engine's buffer directly.
+pub trait PoolElement: private::Sealed + Clone {
+    #[doc(hidden)]
+    type SysArray: Default;
+    #[doc(hidden)]
+    type SysReadAccess;
+    #[doc(hidden)]
+    type SysWriteAccess;
+    #[doc(hidden)]
+    type SysTy;
+    #[doc(hidden)]
+    type SysRefTy;
+
+    #[doc(hidden)]
+    fn element_from_sys(sys: Self::SysTy) -> Self;
+    #[doc(hidden)]
+    fn element_to_sys_ref(&self) -> Self::SysRefTy;
+
+    #[doc(hidden)]
+    fn new_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray);
+    #[doc(hidden)]
+    fn new_copy_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray, *const Self::SysArray);
+    #[doc(hidden)]
+    fn new_with_array_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray, *const sys::godot_array);
+    #[doc(hidden)]
+    fn append_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray, Self::SysRefTy);
+    #[doc(hidden)]
+    fn append_array_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray, *const Self::SysArray);
+    #[doc(hidden)]
+    fn insert_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int, Self::SysRefTy) -> sys::godot_error;
+    #[doc(hidden)]
+    fn invert_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray);
+    #[doc(hidden)]
+    fn remove_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int);
+    #[doc(hidden)]
+    fn resize_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int);
+    #[doc(hidden)]
+    fn read_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysArray) -> *mut Self::SysReadAccess;
+    #[doc(hidden)]
+    fn write_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray) -> *mut Self::SysWriteAccess;
+    #[doc(hidden)]
+    fn set_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int, Self::SysRefTy);
+    #[doc(hidden)]
+    fn get_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysArray, sys::godot_int) -> Self::SysTy;
+    #[doc(hidden)]
+    fn size_fn(api: &GodotApi) -> unsafe extern "C" fn(*const Self::SysArray) -> sys::godot_int;
+    #[doc(hidden)]
+    fn destroy_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray);
+    #[doc(hidden)]
+    fn read_access_copy_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysReadAccess) -> *mut Self::SysReadAccess;
+    #[doc(hidden)]
+    fn read_access_ptr_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysReadAccess) -> *const Self::SysTy;
+    #[doc(hidden)]
+    fn read_access_destroy_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysReadAccess);
+    #[doc(hidden)]
+    fn write_access_ptr_fn(
+        api: &GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysWriteAccess) -> *mut Self::SysTy;
+    #[doc(hidden)]
+    fn write_access_destroy_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysWriteAccess);
+}
+
+/// Trait for pool elements made of a fixed number of `f32` components without padding, which
+/// allows their accesses to be viewed as `f32` slices. This trait is sealed.
+///
+/// With the `double-precision` feature, only `Color` is made of `f32` components.
+pub trait F32Components: private::Sealed + PoolElement + Copy {
+    /// Number of `f32` components in one element.
+    const COMPONENTS: usize;
+}
+
+#[cfg(not(feature = "double-precision"))]
+impl F32Components for f32 {
+    const COMPONENTS: usize = 1;
+}
+
+#[cfg(not(feature = "double-precision"))]
+impl F32Components for Vector2 {
+    const COMPONENTS: usize = 2;
+}
+
+#[cfg(not(feature = "double-precision"))]
+impl F32Components for Vector3 {
+    const COMPONENTS: usize = 3;
+}
+
+impl F32Components for Color {
+    const COMPONENTS: usize = 4;
+}
+
+macro_rules! impl_element {
+    (
+        impl PoolElement for $Element:ty : $SysTy:ty, $SysRefTy:ty {
+            array = $SysArray:ident,
+            read_access = $SysReadAccess:ident,
+            write_access = $SysWriteAccess:ident,
+            from_sys = |$sys:ident| $from_sys:expr,
+            to_sys_ref = |$val:ident| $to_sys_ref:expr,
+        }
+        $( $name:ident => $gd_method:ident; )*
+    ) => {
+        impl private::Sealed for $Element {}
+
+        impl PoolElement for $Element {
+            type SysArray = sys::$SysArray;
+            type SysReadAccess = sys::$SysReadAccess;
+            type SysWriteAccess = sys::$SysWriteAccess;
+            type SysTy = $SysTy;
+            type SysRefTy = $SysRefTy;
+
+            #[allow(unused_unsafe)]
+            fn element_from_sys($sys: Self::SysTy) -> Self {
+                unsafe { $from_sys }
+            }
+
+            fn element_to_sys_ref(&self) -> Self::SysRefTy {
+                let $val = self;
+                $to_sys_ref
+            }
+
+            impl_element!(@fns $( $name => $gd_method; )*);
+        }
+    };
+
+    (@fns $( $name:ident => $gd_method:ident; )*) => {
+        $(
+            impl_element!(@fn $name => $gd_method);
+        )*
+    };
+
+    (@fn new => $gd_method:ident) => {
+        fn new_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray) {
+            api.$gd_method
+        }
+    };
+    (@fn new_copy => $gd_method:ident) => {
+        fn new_copy_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*mut Self::SysArray, *const Self::SysArray) {
+            api.$gd_method
+        }
+    };
+    (@fn new_with_array => $gd_method:ident) => {
+        fn new_with_array_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*mut Self::SysArray, *const sys::godot_array) {
+            api.$gd_method
+        }
+    };
+    (@fn append => $gd_method:ident) => {
+        fn append_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*mut Self::SysArray, Self::SysRefTy) {
+            api.$gd_method
+        }
+    };
+    (@fn append_array => $gd_method:ident) => {
+        fn append_array_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*mut Self::SysArray, *const Self::SysArray) {
+            api.$gd_method
+        }
+    };
+    (@fn insert => $gd_method:ident) => {
+        fn insert_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int, Self::SysRefTy) -> sys::godot_error {
+            api.$gd_method
+        }
+    };
+    (@fn invert => $gd_method:ident) => {
+        fn invert_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray) {
+            api.$gd_method
+        }
+    };
+    (@fn remove => $gd_method:ident) => {
+        fn remove_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int) {
+            api.$gd_method
+        }
+    };
+    (@fn resize => $gd_method:ident) => {
+        fn resize_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int) {
+            api.$gd_method
+        }
+    };
+    (@fn read => $gd_method:ident) => {
+        fn read_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*const Self::SysArray) -> *mut Self::SysReadAccess {
+            api.$gd_method
+        }
+    };
+    (@fn write => $gd_method:ident) => {
+        fn write_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*mut Self::SysArray) -> *mut Self::SysWriteAccess {
+            api.$gd_method
+        }
+    };
+    (@fn set => $gd_method:ident) => {
+        fn set_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int, Self::SysRefTy) {
+            api.$gd_method
+        }
+    };
+    (@fn get => $gd_method:ident) => {
+        fn get_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*const Self::SysArray, sys::godot_int) -> Self::SysTy {
+            api.$gd_method
+        }
+    };
+    (@fn size => $gd_method:ident) => {
+        fn size_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*const Self::SysArray) -> sys::godot_int {
+            api.$gd_method
+        }
+    };
+    (@fn destroy => $gd_method:ident) => {
+        fn destroy_fn(api: &GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray) {
+            api.$gd_method
+        }
+    };
+    (@fn read_access_copy => $gd_method:ident) => {
+        fn read_access_copy_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*const Self::SysReadAccess) -> *mut Self::SysReadAccess {
+            api.$gd_method
+        }
+    };
+    (@fn read_access_ptr => $gd_method:ident) => {
+        fn read_access_ptr_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*const Self::SysReadAccess) -> *const Self::SysTy {
+            api.$gd_method
+        }
+    };
+    (@fn read_access_destroy => $gd_method:ident) => {
+        fn read_access_destroy_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*mut Self::SysReadAccess) {
+            api.$gd_method
+        }
+    };
+    (@fn write_access_ptr => $gd_method:ident) => {
+        fn write_access_ptr_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*const Self::SysWriteAccess) -> *mut Self::SysTy {
+            api.$gd_method
+        }
+    };
+    (@fn write_access_destroy => $gd_method:ident) => {
+        fn write_access_destroy_fn(
+            api: &GodotApi,
+        ) -> unsafe extern "C" fn(*mut Self::SysWriteAccess) {
+            api.$gd_method
+        }
+    };
+}
+
+impl_element! {
+    impl PoolElement for u8 : u8, u8 {
+        array = godot_pool_byte_array,
+        read_access = godot_pool_byte_array_read_access,
+        write_access = godot_pool_byte_array_write_access,
+        from_sys = |raw| raw,
+        to_sys_ref = |val| *val,
+    }
+    new => godot_pool_byte_array_new;
+    new_copy => godot_pool_byte_array_new_copy;
+    new_with_array => godot_pool_byte_array_new_with_array;
+    append => godot_pool_byte_array_append;
+    append_array => godot_pool_byte_array_append_array;
+    insert => godot_pool_byte_array_insert;
+    invert => godot_pool_byte_array_invert;
+    remove => godot_pool_byte_array_remove;
+    resize => godot_pool_byte_array_resize;
+    read => godot_pool_byte_array_read;
+    write => godot_pool_byte_array_write;
+    set => godot_pool_byte_array_set;
+    get => godot_pool_byte_array_get;
+    size => godot_pool_byte_array_size;
+    destroy => godot_pool_byte_array_destroy;
+    read_access_copy => godot_pool_byte_array_read_access_copy;
+    read_access_ptr => godot_pool_byte_array_read_access_ptr;
+    read_access_destroy => godot_pool_byte_array_read_access_destroy;
+    write_access_ptr => godot_pool_byte_array_write_access_ptr;
+    write_access_destroy => godot_pool_byte_array_write_access_destroy;
+}
+
+impl_element! {
+    impl PoolElement for i32 : sys::godot_int, sys::godot_int {
+        array = godot_pool_int_array,
+        read_access = godot_pool_int_array_read_access,
+        write_access = godot_pool_int_array_write_access,
+        from_sys = |raw| raw,
+        to_sys_ref = |val| *val,
+    }
+    new => godot_pool_int_array_new;
+    new_copy => godot_pool_int_array_new_copy;
+    new_with_array => godot_pool_int_array_new_with_array;
+    append => godot_pool_int_array_append;
+    append_array => godot_pool_int_array_append_array;
+    insert => godot_pool_int_array_insert;
+    invert => godot_pool_int_array_invert;
+    remove => godot_pool_int_array_remove;
+    resize => godot_pool_int_array_resize;
+    read => godot_pool_int_array_read;
+    write => godot_pool_int_array_write;
+    set => godot_pool_int_array_set;
+    get => godot_pool_int_array_get;
+    size => godot_pool_int_array_size;
+    destroy => godot_pool_int_array_destroy;
+    read_access_copy => godot_pool_int_array_read_access_copy;
+    read_access_ptr => godot_pool_int_array_read_access_ptr;
+    read_access_destroy => godot_pool_int_array_read_access_destroy;
+    write_access_ptr => godot_pool_int_array_write_access_ptr;
+    write_access_destroy => godot_pool_int_array_write_access_destroy;
+}
+
+impl_element! {
+    impl PoolElement for real : sys::godot_real, sys::godot_real {
+        array = godot_pool_real_array,
+        read_access = godot_pool_real_array_read_access,
+        write_access = godot_pool_real_array_write_access,
+        from_sys = |raw| raw,
+        to_sys_ref = |val| *val,
+    }
+    new => godot_pool_real_array_new;
+    new_copy => godot_pool_real_array_new_copy;
+    new_with_array => godot_pool_real_array_new_with_array;
+    append => godot_pool_real_array_append;
+    append_array => godot_pool_real_array_append_array;
+    insert => godot_pool_real_array_insert;
+    invert => godot_pool_real_array_invert;
+    remove => godot_pool_real_array_remove;
+    resize => godot_pool_real_array_resize;
+    read => godot_pool_real_array_read;
+    write => godot_pool_real_array_write;
+    set => godot_pool_real_array_set;
+    get => godot_pool_real_array_get;
+    size => godot_pool_real_array_size;
+    destroy => godot_pool_real_array_destroy;
+    read_access_copy => godot_pool_real_array_read_access_copy;
+    read_access_ptr => godot_pool_real_array_read_access_ptr;
+    read_access_destroy => godot_pool_real_array_read_access_destroy;
+    write_access_ptr => godot_pool_real_array_write_access_ptr;
+    write_access_destroy => godot_pool_real_array_write_access_destroy;
+}
+
+impl_element! {
+    impl PoolElement for GodotString : sys::godot_string, *const sys::godot_string {
+        array = godot_pool_string_array,
+        read_access = godot_pool_string_array_read_access,
+        write_access = godot_pool_string_array_write_access,
+        from_sys = |raw| GodotString::from_sys(raw),
+        to_sys_ref = |val| val.sys(),
+    }
+    new => godot_pool_string_array_new;
+    new_copy => godot_pool_string_array_new_copy;
+    new_with_array => godot_pool_string_array_new_with_array;
+    append => godot_pool_string_array_append;
+    append_array => godot_pool_string_array_append_array;
+    insert => godot_pool_string_array_insert;
+    invert => godot_pool_string_array_invert;
+    remove => godot_pool_string_array_remove;
+    resize => godot_pool_string_array_resize;
+    read => godot_pool_string_array_read;
+    write => godot_pool_string_array_write;
+    set => godot_pool_string_array_set;
+    get => godot_pool_string_array_get;
+    size => godot_pool_string_array_size;
+    destroy => godot_pool_string_array_destroy;
+    read_access_copy => godot_pool_string_array_read_access_copy;
+    read_access_ptr => godot_pool_string_array_read_access_ptr;
+    read_access_destroy => godot_pool_string_array_read_access_destroy;
+    write_access_ptr => godot_pool_string_array_write_access_ptr;
+    write_access_destroy => godot_pool_string_array_write_access_destroy;
+}
+
+impl_element! {
+    impl PoolElement for Vector2 : sys::godot_vector2, *const sys::godot_vector2 {
+        array = godot_pool_vector2_array,
+        read_access = godot_pool_vector2_array_read_access,
+        write_access = godot_pool_vector2_array_write_access,
+        from_sys = |raw| transmute::<sys::godot_vector2, Vector2>(raw),
+        to_sys_ref = |val| val as *const Vector2 as *const sys::godot_vector2,
+    }
+    new => godot_pool_vector2_array_new;
+    new_copy => godot_pool_vector2_array_new_copy;
+    new_with_array => godot_pool_vector2_array_new_with_array;
+    append => godot_pool_vector2_array_append;
+    append_array => godot_pool_vector2_array_append_array;
+    insert => godot_pool_vector2_array_insert;
+    invert => godot_pool_vector2_array_invert;
+    remove => godot_pool_vector2_array_remove;
+    resize => godot_pool_vector2_array_resize;
+    read => godot_pool_vector2_array_read;
+    write => godot_pool_vector2_array_write;
+    set => godot_pool_vector2_array_set;
+    get => godot_pool_vector2_array_get;
+    size => godot_pool_vector2_array_size;
+    destroy => godot_pool_vector2_array_destroy;
+    read_access_copy => godot_pool_vector2_array_read_access_copy;
+    read_access_ptr => godot_pool_vector2_array_read_access_ptr;
+    read_access_destroy => godot_pool_vector2_array_read_access_destroy;
+    write_access_ptr => godot_pool_vector2_array_write_access_ptr;
+    write_access_destroy => godot_pool_vector2_array_write_access_destroy;
+}
+
+impl_element! {
+    impl PoolElement for Vector3 : sys::godot_vector3, *const sys::godot_vector3 {
+        array = godot_pool_vector3_array,
+        read_access = godot_pool_vector3_array_read_access,
+        write_access = godot_pool_vector3_array_write_access,
+        from_sys = |raw| transmute::<sys::godot_vector3, Vector3>(raw),
+        to_sys_ref = |val| val as *const Vector3 as *const sys::godot_vector3,
+    }
+    new => godot_pool_vector3_array_new;
+    new_copy => godot_pool_vector3_array_new_copy;
+    new_with_array => godot_pool_vector3_array_new_with_array;
+    append => godot_pool_vector3_array_append;
+    append_array => godot_pool_vector3_array_append_array;
+    insert => godot_pool_vector3_array_insert;
+    invert => godot_pool_vector3_array_invert;
+    remove => godot_pool_vector3_array_remove;
+    resize => godot_pool_vector3_array_resize;
+    read => godot_pool_vector3_array_read;
+    write => godot_pool_vector3_array_write;
+    set => godot_pool_vector3_array_set;
+    get => godot_pool_vector3_array_get;
+    size => godot_pool_vector3_array_size;
+    destroy => godot_pool_vector3_array_destroy;
+    read_access_copy => godot_pool_vector3_array_read_access_copy;
+    read_access_ptr => godot_pool_vector3_array_read_access_ptr;
+    read_access_destroy => godot_pool_vector3_array_read_access_destroy;
+    write_access_ptr => godot_pool_vector3_array_write_access_ptr;
+    write_access_destroy => godot_pool_vector3_array_write_access_destroy;
+}
+
+impl_element! {
+    impl PoolElement for Color : sys::godot_color, *const sys::godot_color {
+        array = godot_pool_color_array,
+        read_access = godot_pool_color_array_read_access,
+        write_access = godot_pool_color_array_write_access,
+        from_sys = |raw| Color::from_sys(raw),
+        to_sys_ref = |val| val.sys(),
+    }
+    new => godot_pool_color_array_new;
+    new_copy => godot_pool_color_array_new_copy;
+    new_with_array => godot_pool_color_array_new_with_array;
+    append => godot_pool_color_array_append;
+    append_array => godot_pool_color_array_append_array;
+    insert => godot_pool_color_array_insert;
+    invert => godot_pool_color_array_invert;
+    remove => godot_pool_color_array_remove;
+    resize => godot_pool_color_array_resize;
+    read => godot_pool_color_array_read;
+    write => godot_pool_color_array_write;
+    set => godot_pool_color_array_set;
+    get => godot_pool_color_array_get;
+    size => godot_pool_color_array_size;
+    destroy => godot_pool_color_array_destroy;
+    read_access_copy => godot_pool_color_array_read_access_copy;
+    read_access_ptr => godot_pool_color_array_read_access_ptr;
+    read_access_destroy => godot_pool_color_array_read_access_destroy;
+    write_access_ptr => godot_pool_color_array_write_access_ptr;
+    write_access_destroy => godot_pool_color_array_write_access_destroy;
+}
+
+godot_test!(
+    test_byte_array_access {
+        let mut arr = ByteArray::new();
+        for i in 0..8 {
+            arr.push(&i);
+        }
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(8, write.len());
+            for i in write.as_mut_slice() {
+                *i *= 2;
+            }
+        }
+
+        for i in 0..8 {
+            assert_eq!(i * 2, cow_arr.get(i as i32));
+        }
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7], original_read.as_slice());
+    }
+);
+
+godot_test!(
+    test_int32_array_access {
+        let mut arr = Int32Array::new();
+        for i in 0..8 {
+            arr.push(&i);
+        }
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(8, write.len());
+            for i in write.as_mut_slice() {
+                *i *= 2;
+            }
+        }
+
+        for i in 0..8 {
+            assert_eq!(i * 2, cow_arr.get(i as i32));
+        }
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7], original_read.as_slice());
+    }
+);
+
+godot_test!(
+    test_float32_array_access {
+        let mut arr = RealArray::new();
+        for i in 0..8 {
+            arr.push(&(i as real));
+        }
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(8, write.len());
+            for i in write.as_mut_slice() {
+                *i *= 2.0;
+            }
+        }
+
+        for i in 0..8 {
+            assert_eq!(i as real * 2.0, cow_arr.get(i as i32));
+        }
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], original_read.as_slice());
+    }
+);
+
+godot_test!(
+    test_string_array_access {
+        let mut arr = StringArray::new();
+        arr.push(&GodotString::from("foo"));
+        arr.push(&GodotString::from("bar"));
+        arr.push(&GodotString::from("baz"));
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[
+                GodotString::from("foo"),
+                GodotString::from("bar"),
+                GodotString::from("baz"),
+            ], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(3, write.len());
+            for s in write.as_mut_slice() {
+                *s = s.to_uppercase();
+            }
+        }
+
+        assert_eq!(GodotString::from("FOO"), cow_arr.get(0));
+        assert_eq!(GodotString::from("BAR"), cow_arr.get(1));
+        assert_eq!(GodotString::from("BAZ"), cow_arr.get(2));
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[
+            GodotString::from("foo"),
+            GodotString::from("bar"),
+            GodotString::from("baz"),
+        ], original_read.as_slice());
+    }
+);
+
+godot_test!(
+    test_vector2_array_access {
+        let mut arr = Vector2Array::new();
+        arr.push(&Vector2::new(1.0, 2.0));
+        arr.push(&Vector2::new(3.0, 4.0));
+        arr.push(&Vector2::new(5.0, 6.0));
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[
+                Vector2::new(1.0, 2.0),
+                Vector2::new(3.0, 4.0),
+                Vector2::new(5.0, 6.0),
+            ], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(3, write.len());
+            for s in write.as_mut_slice() {
+                s.x += 1.0;
+            }
+        }
+
+        assert_eq!(Vector2::new(2.0, 2.0), cow_arr.get(0));
+        assert_eq!(Vector2::new(4.0, 4.0), cow_arr.get(1));
+        assert_eq!(Vector2::new(6.0, 6.0), cow_arr.get(2));
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[
+            Vector2::new(1.0, 2.0),
+            Vector2::new(3.0, 4.0),
+            Vector2::new(5.0, 6.0),
+        ], original_read.as_slice());
+    }
+);
+
+godot_test!(
+    test_vector3_array_access {
+        let mut arr = Vector3Array::new();
+        arr.push(&Vector3::new(1.0, 2.0, 3.0));
+        arr.push(&Vector3::new(3.0, 4.0, 5.0));
+        arr.push(&Vector3::new(5.0, 6.0, 7.0));
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[
+                Vector3::new(1.0, 2.0, 3.0),
+                Vector3::new(3.0, 4.0, 5.0),
+                Vector3::new(5.0, 6.0, 7.0),
+            ], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(3, write.len());
+            for s in write.as_mut_slice() {
+                s.x += 2.0;
+                s.y += 1.0;
+            }
+        }
+
+        assert_eq!(Vector3::new(3.0, 3.0, 3.0), cow_arr.get(0));
+        assert_eq!(Vector3::new(5.0, 5.0, 5.0), cow_arr.get(1));
+        assert_eq!(Vector3::new(7.0, 7.0, 7.0), cow_arr.get(2));
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(3.0, 4.0, 5.0),
+            Vector3::new(5.0, 6.0, 7.0),
+        ], original_read.as_slice());
+    }
+);
+
+godot_test!(
+    test_color_array_access {
+        let mut arr = ColorArray::new();
+        arr.push(&Color::rgb(1.0, 0.0, 0.0));
+        arr.push(&Color::rgb(0.0, 1.0, 0.0));
+        arr.push(&Color::rgb(0.0, 0.0, 1.0));
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[
+                Color::rgb(1.0, 0.0, 0.0),
+                Color::rgb(0.0, 1.0, 0.0),
+                Color::rgb(0.0, 0.0, 1.0),
+            ], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(3, write.len());
+            for i in write.as_mut_slice() {
+                i.b = 1.0;
+            }
+        }
+
+        assert_eq!(Color::rgb(1.0, 0.0, 1.0), cow_arr.get(0));
+        assert_eq!(Color::rgb(0.0, 1.0, 1.0), cow_arr.get(1));
+        assert_eq!(Color::rgb(0.0, 0.0, 1.0), cow_arr.get(2));
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[
+            Color::rgb(1.0, 0.0, 0.0),
+            Color::rgb(0.0, 1.0, 0.0),
+            Color::rgb(0.0, 0.0, 1.0),
+        ], original_read.as_slice());
+    }
+);
+
+godot_test!(
+    test_pool_array_api {
+        let mut arr = Int32Array::from_slice(&[1, 2, 3]);
+        assert_eq!(3, arr.len());
+        assert_eq!(2, arr.get(1));
+        assert_eq!(3, arr.read()[2]);
+
+        arr.insert(0, &0);
+        arr.remove(2);
+        arr.extend(vec![4, 5]);
+        assert_eq!(vec![0, 1, 3, 4, 5], arr.to_vec());
+        assert_eq!(vec![5, 4, 3, 1, 0], arr.iter().rev().collect::<Vec<_>>());
+
+        arr.resize(2);
+        assert_eq!(vec![0, 1], (&arr).into_iter().collect::<Vec<_>>());
+
+        let strings: StringArray = vec!["a", "b"].into_iter().map(GodotString::from).collect();
+        assert_eq!(GodotString::from("b"), strings.read()[1]);
+
+        let mut colors = ColorArray::from_vec(vec![Color::rgb(1.0, 0.0, 0.0)]);
+        colors.push_array(&ColorArray::from_slice(&[Color::rgb(0.0, 1.0, 0.0)]));
+        colors.invert();
+        assert_eq!(Color::rgb(0.0, 1.0, 0.0), colors.get(0));
+
+        let vectors: Vector2Array = (0..3).map(|i| Vector2::new(i as real, 0.0)).collect();
+        assert_eq!(3, vectors.iter().len());
+        assert!(Vector3Array::new().is_empty());
+    }
+);
+
+godot_test!(
+    test_pool_array_slices {
+        use std::mem::size_of;
+
+        #[cfg(not(feature = "double-precision"))]
+        {
+            assert_eq!(size_of::<Vector2>(), Vector2::COMPONENTS * size_of::<f32>());
+            assert_eq!(size_of::<Vector3>(), Vector3::COMPONENTS * size_of::<f32>());
+        }
+        assert_eq!(size_of::<Color>(), Color::COMPONENTS * size_of::<f32>());
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        let mut arr = ByteArray::from_slice(&bytes);
+        assert_eq!(bytes, arr.to_vec());
+
+        arr.copy_from_slice(&bytes[..4]);
+        assert_eq!(&[0, 1, 2, 3], arr.read().as_slice());
+
+        let mut vertices = Vector3Array::new();
+        vertices.copy_from_slice(&[Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0)]);
+        assert_eq!(Vector3::new(4.0, 5.0, 6.0), vertices.get(1));
+        #[cfg(not(feature = "double-precision"))]
+        assert_eq!(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vertices.read().as_f32_slice());
+
+        let mut colors = ColorArray::from_slice(&[Color::rgba(0.0, 0.0, 0.0, 1.0); 2]);
+        {
+            let mut write = colors.write();
+            let components = write.as_f32_slice_mut();
+            assert_eq!(8, components.len());
+            components[4] = 0.5;
+        }
+        assert_eq!(Color::rgba(0.5, 0.0, 0.0, 1.0), colors.get(1));
+
+        let mut strings = StringArray::from_slice(&[GodotString::from("a")]);
+        strings.clone_from_slice(&[GodotString::from("b"), GodotString::from("c")]);
+        assert_eq!(GodotString::from("c"), strings.get(1));
+    }
+);
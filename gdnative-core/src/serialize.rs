@@ -0,0 +1,261 @@
+//! Serde support for the core types, enabled with the `serde` feature.
+//!
+//! Geometry types and `Color` derive their implementations. Strings and node paths are
+//! represented as strings, arrays as sequences, and `Dictionary` as a sequence of key-value
+//! pairs, so that keys of any type survive formats that only allow string keys in maps.
+//!
+//! `Variant` is represented as an externally tagged enum named after its `VariantType`,
+//! e.g. `{"I64":42}` in JSON, so that the type survives a round trip even in formats that
+//! don't distinguish between integers and floats. Variants containing an `Object` or a
+//! `Rid` cannot be serialized.
+
+use crate::*;
+
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+use std::fmt;
+
+impl Serialize for GodotString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for GodotString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(GodotString::from_str)
+    }
+}
+
+impl Serialize for NodePath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for NodePath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|path| NodePath::from_str(&path))
+    }
+}
+
+impl Serialize for VariantArray {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for VariantArray {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ArrayVisitor;
+
+        impl<'de> Visitor<'de> for ArrayVisitor {
+            type Value = VariantArray;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence of variants")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut array = VariantArray::new();
+                while let Some(value) = seq.next_element::<Variant>()? {
+                    array.push(&value);
+                }
+                Ok(array)
+            }
+        }
+
+        deserializer.deserialize_seq(ArrayVisitor)
+    }
+}
+
+impl Serialize for Dictionary {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let keys = self.keys();
+        serializer.collect_seq(keys.iter().map(|key| (key, self.get(key))))
+    }
+}
+
+impl<'de> Deserialize<'de> for Dictionary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DictionaryVisitor;
+
+        impl<'de> Visitor<'de> for DictionaryVisitor {
+            type Value = Dictionary;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence of key-value pairs")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut dict = Dictionary::new();
+                while let Some((key, value)) = seq.next_element::<(Variant, Variant)>()? {
+                    dict.set(&key, &value);
+                }
+                Ok(dict)
+            }
+        }
+
+        deserializer.deserialize_seq(DictionaryVisitor)
+    }
+}
+
+impl<T: PoolElement + Serialize> Serialize for PoolArray<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.read().as_slice())
+    }
+}
+
+impl<'de, T: PoolElement + Deserialize<'de>> Deserialize<'de> for PoolArray<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<T>::deserialize(deserializer).map(PoolArray::from_vec)
+    }
+}
+
+/// Owned contents of a `Variant`, tagged with the name of its `VariantType`.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Variant")]
+enum VariantRepr {
+    Nil,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    GodotString(GodotString),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    NodePath(NodePath),
+    Dictionary(Dictionary),
+    VariantArray(VariantArray),
+    ByteArray(ByteArray),
+    Int32Array(Int32Array),
+    Float32Array(RealArray),
+    StringArray(StringArray),
+    Vector2Array(Vector2Array),
+    Vector3Array(Vector3Array),
+    ColorArray(ColorArray),
+}
+
+impl Serialize for Variant {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self.get_type() {
+            VariantType::Nil => VariantRepr::Nil,
+            VariantType::Bool => VariantRepr::Bool(self.to_bool()),
+            VariantType::I64 => VariantRepr::I64(self.to_i64()),
+            VariantType::F64 => VariantRepr::F64(self.to_f64()),
+            VariantType::GodotString => VariantRepr::GodotString(self.to_godot_string()),
+            VariantType::Vector2 => VariantRepr::Vector2(self.to_vector2()),
+            VariantType::Rect2 => VariantRepr::Rect2(self.to_rect2()),
+            VariantType::Vector3 => VariantRepr::Vector3(self.to_vector3()),
+            VariantType::Transform2D => VariantRepr::Transform2D(self.to_transform2d()),
+            VariantType::Plane => VariantRepr::Plane(self.to_plane()),
+            VariantType::Quat => VariantRepr::Quat(self.to_quat()),
+            VariantType::Aabb => VariantRepr::Aabb(self.to_aabb()),
+            VariantType::Basis => VariantRepr::Basis(self.to_basis()),
+            VariantType::Transform => VariantRepr::Transform(self.to_transform()),
+            VariantType::Color => VariantRepr::Color(self.to_color()),
+            VariantType::NodePath => VariantRepr::NodePath(self.to_node_path()),
+            VariantType::Dictionary => VariantRepr::Dictionary(self.to_dictionary()),
+            VariantType::VariantArray => VariantRepr::VariantArray(self.to_array()),
+            VariantType::ByteArray => VariantRepr::ByteArray(self.to_byte_array()),
+            VariantType::Int32Array => VariantRepr::Int32Array(self.to_int32_array()),
+            VariantType::Float32Array => VariantRepr::Float32Array(self.to_float32_array()),
+            VariantType::StringArray => VariantRepr::StringArray(self.to_string_array()),
+            VariantType::Vector2Array => VariantRepr::Vector2Array(self.to_vector2_array()),
+            VariantType::Vector3Array => VariantRepr::Vector3Array(self.to_vector3_array()),
+            VariantType::ColorArray => VariantRepr::ColorArray(self.to_color_array()),
+            ty @ VariantType::Rid | ty @ VariantType::Object => {
+                return Err(ser::Error::custom(format_args!(
+                    "variants of type {:?} cannot be serialized",
+                    ty
+                )));
+            }
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let variant = match VariantRepr::deserialize(deserializer)? {
+            VariantRepr::Nil => Variant::new(),
+            VariantRepr::Bool(v) => Variant::from_bool(v),
+            VariantRepr::I64(v) => Variant::from_i64(v),
+            VariantRepr::F64(v) => Variant::from_f64(v),
+            VariantRepr::GodotString(v) => Variant::from_godot_string(&v),
+            VariantRepr::Vector2(v) => Variant::from_vector2(&v),
+            VariantRepr::Rect2(v) => Variant::from_rect2(&v),
+            VariantRepr::Vector3(v) => Variant::from_vector3(&v),
+            VariantRepr::Transform2D(v) => Variant::from_transform2d(&v),
+            VariantRepr::Plane(v) => Variant::from_plane(&v),
+            VariantRepr::Quat(v) => Variant::from_quat(&v),
+            VariantRepr::Aabb(v) => Variant::from_aabb(&v),
+            VariantRepr::Basis(v) => Variant::from_basis(&v),
+            VariantRepr::Transform(v) => Variant::from_transform(&v),
+            VariantRepr::Color(v) => Variant::from_color(&v),
+            VariantRepr::NodePath(v) => Variant::from_node_path(&v),
+            VariantRepr::Dictionary(v) => Variant::from_dictionary(&v),
+            VariantRepr::VariantArray(v) => Variant::from_array(&v),
+            VariantRepr::ByteArray(v) => Variant::from_byte_array(&v),
+            VariantRepr::Int32Array(v) => Variant::from_int32_array(&v),
+            VariantRepr::Float32Array(v) => Variant::from_float32_array(&v),
+            VariantRepr::StringArray(v) => Variant::from_string_array(&v),
+            VariantRepr::Vector2Array(v) => Variant::from_vector2_array(&v),
+            VariantRepr::Vector3Array(v) => Variant::from_vector3_array(&v),
+            VariantRepr::ColorArray(v) => Variant::from_color_array(&v),
+        };
+        Ok(variant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variant_serde_roundtrip() {
+        crate::mock::bind();
+
+        let mut dict = Dictionary::new();
+        dict.set(&Variant::from_i64(1), &Variant::from_str("one"));
+        dict.set(
+            &Variant::from_str("pos"),
+            &Variant::from_vector2(&Vector2::new(1.0, 2.0)),
+        );
+
+        let mut array = VariantArray::new();
+        array.push(&Variant::from_f64(1.0));
+        array.push(&Variant::from_i64(1));
+        array.push(&Variant::from_dictionary(&dict));
+        array.push(&Variant::from_color(&Color::rgb(1.0, 0.5, 0.0)));
+        array.push(&Variant::new());
+
+        let variant = Variant::from_array(&array);
+        let json = serde_json::to_string(&variant).unwrap();
+        let back: Variant = serde_json::from_str(&json).unwrap();
+
+        let back = back.to_array();
+        assert_eq!(5, back.len());
+        assert_eq!(VariantType::F64, back.get_ref(0).get_type());
+        assert_eq!(VariantType::I64, back.get_ref(1).get_type());
+        assert_eq!(
+            Some(Color::rgb(1.0, 0.5, 0.0)),
+            back.get_ref(3).try_to_color()
+        );
+        assert!(back.get_ref(4).is_nil());
+
+        let back_dict = back.get_ref(2).to_dictionary();
+        assert_eq!("one", back_dict.get(&Variant::from_i64(1)).to_string());
+        assert_eq!(
+            Some(Vector2::new(1.0, 2.0)),
+            back_dict.get(&Variant::from_str("pos")).try_to_vector2()
+        );
+    }
+}
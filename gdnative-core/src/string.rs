@@ -1,5 +1,7 @@
+use crate::geom::real_to_f32;
 use crate::get_api;
 use crate::sys;
+use crate::{StringArray, Variant, VariantArray};
 
 use std::cmp::Ordering;
 use std::ffi::CStr;
@@ -61,6 +63,10 @@ impl GodotString {
         unsafe { (get_api().godot_string_length)(&self.0) as usize }
     }
 
+    pub fn to_f32(&self) -> f32 {
+        unsafe { real_to_f32((get_api().godot_string_to_float)(&self.0)) }
+    }
+
     impl_methods!(
         pub fn is_empty(&self) -> bool : godot_string_empty;
         pub fn is_numeric(&self) -> bool : godot_string_is_numeric;
@@ -72,7 +78,6 @@ impl GodotString {
         pub fn is_resource_file(&self) -> bool : godot_string_is_resource_file;
         pub fn is_absolute_path(&self) -> bool : godot_string_is_abs_path;
         pub fn is_relative_path(&self) -> bool : godot_string_is_rel_path;
-        pub fn to_f32(&self) -> f32 : godot_string_to_float;
         pub fn to_f64(&self) -> f64 : godot_string_to_double;
         pub fn to_i32(&self) -> i32 : godot_string_to_int;
         pub fn u32_hash(&self) -> u32 : godot_string_hash;
@@ -149,6 +154,85 @@ impl GodotString {
         unsafe { (get_api().godot_string_find_last)(&self.0, what.0) }
     }
 
+    /// Splits the string by a delimiter. Empty substrings are only included if `allow_empty`
+    /// is `true`.
+    pub fn split(&self, delimiter: &GodotString, allow_empty: bool) -> StringArray {
+        unsafe {
+            let api = get_api();
+            let array = if allow_empty {
+                (api.godot_string_split_allow_empty)(&self.0, &delimiter.0)
+            } else {
+                (api.godot_string_split)(&self.0, &delimiter.0)
+            };
+            StringArray::from_variant_array(&VariantArray::from_sys(array))
+        }
+    }
+
+    /// Returns a copy of the string with all occurrences of `what` replaced by `with`.
+    pub fn replace(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_replace)(&self.0, what.0, with.0)) }
+    }
+
+    /// Returns a copy of the string with the first occurrence of `what` replaced by `with`.
+    pub fn replace_first(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_replace_first)(
+                &self.0, what.0, with.0,
+            ))
+        }
+    }
+
+    /// Returns a copy of the string with all occurrences of `what` replaced by `with`,
+    /// ignoring case.
+    pub fn replace_ignore_case(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_replacen)(&self.0, what.0, with.0)) }
+    }
+
+    /// Returns a copy of the string with whitespace and control characters removed from the
+    /// selected ends.
+    pub fn strip_edges(&self, left: bool, right: bool) -> Self {
+        unsafe { GodotString((get_api().godot_string_strip_edges)(&self.0, left, right)) }
+    }
+
+    /// Formats the string by replacing placeholders like `{0}` or `{name}` with the elements
+    /// of `values`, which must be an array or a dictionary.
+    pub fn format(&self, values: &Variant) -> Self {
+        unsafe { GodotString((get_api().godot_string_format)(&self.0, &values.0)) }
+    }
+
+    /// Returns a copy of the string with `what` inserted at the given character position.
+    pub fn insert(&self, at: i32, what: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_insert)(&self.0, at, what.0)) }
+    }
+
+    /// Returns a copy of the string, which must be a number, with its integer part padded
+    /// with zeros to at least `digits` digits.
+    pub fn pad_zeros(&self, digits: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_pad_zeros)(&self.0, digits)) }
+    }
+
+    /// Returns the similarity index of the text compared to another string, between `0.0`
+    /// and `1.0`, based on the Sorensen-Dice coefficient of their bigrams.
+    pub fn similarity(&self, other: &GodotString) -> f32 {
+        unsafe { real_to_f32((get_api().godot_string_similarity)(&self.0, &other.0)) }
+    }
+
+    /// Returns an iterator over the `char`s of the string.
+    ///
+    /// Surrogate pairs, used on platforms where `wchar_t` is 16 bits wide, are combined, and
+    /// invalid code points are replaced by `char::REPLACEMENT_CHARACTER`.
+    pub fn chars(&self) -> Chars<'_> {
+        let len = self.len();
+        let units: &[sys::wchar_t] = if len == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts((get_api().godot_string_wide_str)(&self.0), len) }
+        };
+        Chars {
+            units: units.iter(),
+        }
+    }
+
     /// Returns the internal ffi representation of the string and consumes
     /// the rust object without running the destructor.
     ///
@@ -205,9 +289,39 @@ impl fmt::Debug for GodotString {
     }
 }
 
-impl ToString for GodotString {
-    fn to_string(&self) -> String {
-        self.to_utf8().to_string()
+impl fmt::Display for GodotString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.to_utf8().as_str())
+    }
+}
+
+impl PartialEq<str> for GodotString {
+    fn eq(&self, other: &str) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl PartialEq<&str> for GodotString {
+    fn eq(&self, other: &&str) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl PartialOrd for GodotString {
+    fn partial_cmp(&self, other: &GodotString) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GodotString {
+    fn cmp(&self, other: &GodotString) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else if unsafe { (get_api().godot_string_operator_less)(&self.0, &other.0) } {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
     }
 }
 
@@ -217,6 +331,37 @@ impl std::hash::Hash for GodotString {
     }
 }
 
+/// Iterator over the `char`s of a `GodotString`, created by `GodotString::chars`.
+pub struct Chars<'a> {
+    units: slice::Iter<'a, sys::wchar_t>,
+}
+
+impl<'a> Iterator for Chars<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let unit = *self.units.next()? as u32;
+
+        if (0xD800..0xDC00).contains(&unit) {
+            if let Some(&low) = self.units.as_slice().first() {
+                let low = low as u32;
+                if (0xDC00..0xE000).contains(&low) {
+                    self.units.next();
+                    let code = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+                    return std::char::from_u32(code);
+                }
+            }
+        }
+
+        Some(std::char::from_u32(unit).unwrap_or(std::char::REPLACEMENT_CHARACTER))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.units.len();
+        (len - len / 2, Some(len))
+    }
+}
+
 // TODO: Is it useful to expose this type?
 // Could just make it an internal detail of how to convert to a rust string.
 #[doc(hidden)]
@@ -262,12 +407,12 @@ impl fmt::Debug for Utf8String {
 pub struct StringName(pub(crate) sys::godot_string_name);
 
 impl StringName {
-    pub fn from_str<S>(s: S)
+    pub fn from_str<S>(s: S) -> Self
     where
         S: AsRef<str>,
     {
         let gd_string = GodotString::from_str(s);
-        StringName::from_godot_string(&gd_string);
+        StringName::from_godot_string(&gd_string)
     }
 
     pub fn from_c_str(s: &CStr) -> Self {
@@ -358,3 +503,53 @@ godot_test!(test_string {
 
     assert_eq!(foo.to_utf8().as_str(), "foo");
 });
+
+godot_test!(test_string_functions {
+    use crate::{Dictionary, GodotString, ToVariant, Variant, VariantArray};
+
+    let s = GodotString::from("  a,b,,c  ").strip_edges(true, true);
+    assert_eq!(s, "a,b,,c");
+    assert_eq!(4, s.split(&",".into(), true).len());
+    assert_eq!(
+        vec!["a", "b", "c"],
+        s.split(&",".into(), false)
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+    );
+
+    let s = GodotString::from("Foo foo");
+    assert_eq!(s.replace(&"foo".into(), &"bar".into()), "Foo bar");
+    assert_eq!(s.replace_ignore_case(&"foo".into(), &"bar".into()), "bar bar");
+    assert_eq!(s.replace_first(&"o".into(), &"0".into()), "F0o foo");
+    assert_eq!(s.insert(3, &"!".into()), "Foo! foo");
+    assert_eq!(s.find(&"foo".into()), 4);
+
+    let mut values = VariantArray::new();
+    values.push(&Variant::from_i64(42));
+    let mut names = Dictionary::new();
+    names.set(&"name".into(), &"world".into());
+    assert_eq!(GodotString::from("{0}").format(&values.to_variant()), "42");
+    assert_eq!(
+        GodotString::from("hello {name}").format(&names.to_variant()),
+        "hello world"
+    );
+
+    assert_eq!(GodotString::from("7.5").pad_zeros(3), "007.5");
+    assert_eq!(1.0, s.similarity(&s));
+    assert_eq!(0.0, s.similarity(&"xyz".into()));
+
+    let unicode = GodotString::from("h\u{e9}\u{1F980}");
+    assert_eq!(3, unicode.len());
+    assert_eq!(vec!['h', '\u{e9}', '\u{1F980}'], unicode.chars().collect::<Vec<_>>());
+    assert_eq!(0, GodotString::new().chars().count());
+    assert_eq!("h\u{e9}\u{1F980}", format!("{}", unicode));
+
+    let (a, ab) = (GodotString::from("a"), GodotString::from("ab"));
+    assert!(a < ab);
+    assert_eq!(std::cmp::Ordering::Equal, a.cmp(&a.new_ref()));
+
+    let mut sorted = [GodotString::from("b"), GodotString::from("c"), GodotString::from("a")];
+    sorted.sort();
+    assert_eq!(vec!["a", "b", "c"], sorted.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+});
@@ -0,0 +1,231 @@
+use std::iter::{Extend, FromIterator};
+use std::marker::PhantomData;
+
+use crate::{FromVariant, FromVariantError, ToVariant, Variant, VariantArray};
+
+/// A `VariantArray` whose elements are all of type `T`.
+///
+/// Elements are converted with `ToVariant` and `FromVariant` when they are stored or retrieved.
+/// The `FromVariant` implementation validates every element, so methods taking a
+/// `TypedArray<T>` can rely on all of them being convertible.
+///
+/// The underlying array is reference-counted, and may still be modified through other
+/// references, e.g. from GDScript. Element accessors panic if an element can no longer be
+/// converted to `T`.
+pub struct TypedArray<T> {
+    array: VariantArray,
+    _marker: PhantomData<T>,
+}
+
+impl<T: ToVariant + FromVariant> TypedArray<T> {
+    /// Creates an empty array.
+    pub fn new() -> Self {
+        TypedArray {
+            array: VariantArray::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a typed array from a `VariantArray`, validating every element.
+    pub fn from_variant_array(array: VariantArray) -> Result<Self, FromVariantError> {
+        for (index, item) in array.iter().enumerate() {
+            T::from_variant(item).map_err(|e| FromVariantError::InvalidItem {
+                index,
+                error: Box::new(e),
+            })?;
+        }
+
+        Ok(TypedArray {
+            array,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the underlying `VariantArray`.
+    pub fn as_variant_array(&self) -> &VariantArray {
+        &self.array
+    }
+
+    /// Consumes the typed array and returns the underlying `VariantArray`.
+    pub fn into_variant_array(self) -> VariantArray {
+        self.array
+    }
+
+    /// Returns `true` if the array contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn len(&self) -> i32 {
+        self.array.len()
+    }
+
+    /// Returns a copy of the element at the given offset.
+    pub fn get(&self, idx: i32) -> T {
+        Self::convert(self.array.get_ref(idx))
+    }
+
+    /// Sets the value of the element at the given offset.
+    pub fn set(&mut self, idx: i32, val: &T) {
+        self.array.set(idx, &val.to_variant())
+    }
+
+    /// Appends an element at the end of the array.
+    pub fn push(&mut self, val: &T) {
+        self.array.push(&val.to_variant())
+    }
+
+    /// Removes the element at the end of the array and returns it, or `None` if the array
+    /// is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(Self::convert(&self.array.pop()))
+        }
+    }
+
+    /// Inserts an element at the given offset.
+    pub fn insert(&mut self, at: i32, val: &T) {
+        self.array.insert(at, &val.to_variant())
+    }
+
+    /// Removes the element at the given offset.
+    pub fn remove(&mut self, idx: i32) {
+        self.array.remove(idx)
+    }
+
+    /// Clears the array, resizing to 0.
+    pub fn clear(&mut self) {
+        self.array.clear()
+    }
+
+    /// Returns an iterator over copies of the elements.
+    pub fn iter(&self) -> TypedIter<'_, T> {
+        TypedIter {
+            arr: self,
+            range: 0..self.len(),
+        }
+    }
+
+    /// Creates a new reference to the same array.
+    pub fn new_ref(&self) -> Self {
+        TypedArray {
+            array: self.array.new_ref(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn convert(variant: &Variant) -> T {
+        T::from_variant(variant).unwrap_or_else(|e| {
+            panic!(
+                "element of TypedArray<{}> has an invalid type: {}",
+                std::any::type_name::<T>(),
+                e
+            )
+        })
+    }
+}
+
+impl<T: ToVariant + FromVariant> Default for TypedArray<T> {
+    fn default() -> Self {
+        TypedArray::new()
+    }
+}
+
+impl<T: ToVariant + FromVariant> ToVariant for TypedArray<T> {
+    fn to_variant(&self) -> Variant {
+        self.array.to_variant()
+    }
+}
+
+impl<T: ToVariant + FromVariant> FromVariant for TypedArray<T> {
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        TypedArray::from_variant_array(VariantArray::from_variant(variant)?)
+    }
+}
+
+impl<T: ToVariant + FromVariant> FromIterator<T> for TypedArray<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut array = TypedArray::new();
+        array.extend(iter);
+        array
+    }
+}
+
+impl<T: ToVariant + FromVariant> Extend<T> for TypedArray<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(&val);
+        }
+    }
+}
+
+impl<'a, T: ToVariant + FromVariant> IntoIterator for &'a TypedArray<T> {
+    type Item = T;
+    type IntoIter = TypedIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over copies of the elements of a `TypedArray`.
+pub struct TypedIter<'a, T> {
+    arr: &'a TypedArray<T>,
+    range: std::ops::Range<i32>,
+}
+
+impl<'a, T: ToVariant + FromVariant> Iterator for TypedIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|idx| self.arr.get(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, T: ToVariant + FromVariant> DoubleEndedIterator for TypedIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|idx| self.arr.get(idx))
+    }
+}
+
+impl<'a, T: ToVariant + FromVariant> ExactSizeIterator for TypedIter<'a, T> {}
+
+godot_test!(test_typed_array {
+    let mut array: TypedArray<i64> = (1..=3).collect();
+    assert_eq!(3, array.len());
+    assert_eq!(2, array.get(1));
+
+    array.push(&4);
+    array.insert(0, &0);
+    array.set(1, &10);
+    assert_eq!(vec![0, 10, 2, 3, 4], array.iter().collect::<Vec<_>>());
+    assert_eq!(vec![4, 3, 2, 10, 0], array.iter().rev().collect::<Vec<_>>());
+
+    assert_eq!(Some(4), array.pop());
+    array.extend(vec![5, 6]);
+    assert_eq!(vec![0, 10, 2, 3, 5, 6], (&array).into_iter().collect::<Vec<_>>());
+
+    let variant = array.to_variant();
+    let back = TypedArray::<i64>::from_variant(&variant).unwrap();
+    assert_eq!(6, back.len());
+
+    let mut mixed = VariantArray::new();
+    mixed.push(&Variant::from_i64(1));
+    mixed.push(&Variant::from_str("two"));
+    match TypedArray::<i64>::from_variant(&mixed.to_variant()) {
+        Err(FromVariantError::InvalidItem { index: 1, .. }) => {}
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("expected an error"),
+    }
+
+    array.clear();
+    assert!(array.is_empty());
+    assert_eq!(None, array.pop());
+});
@@ -0,0 +1,261 @@
+use std::iter::{Extend, FromIterator};
+use std::marker::PhantomData;
+
+use crate::variant::from_variant_entry;
+use crate::{Dictionary, DictionaryIter, FromVariant, FromVariantError, ToVariant, Variant};
+
+/// A `Dictionary` whose keys are all of type `K` and values all of type `V`.
+///
+/// Keys and values are converted with `ToVariant` and `FromVariant` when they are stored or
+/// retrieved. The `FromVariant` implementation validates every key-value pair.
+///
+/// Like `TypedArray`, the underlying dictionary is reference-counted and may still be modified
+/// through other references. Accessors panic if an entry can no longer be converted.
+pub struct TypedDictionary<K, V> {
+    dict: Dictionary,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> TypedDictionary<K, V>
+where
+    K: ToVariant + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    /// Creates an empty dictionary.
+    pub fn new() -> Self {
+        TypedDictionary {
+            dict: Dictionary::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a typed dictionary from a `Dictionary`, validating every key-value pair.
+    pub fn from_dictionary(dict: Dictionary) -> Result<Self, FromVariantError> {
+        for (key, value) in dict.iter() {
+            from_variant_entry::<K, V>(&key, &value)?;
+        }
+
+        Ok(TypedDictionary {
+            dict,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the underlying `Dictionary`.
+    pub fn as_dictionary(&self) -> &Dictionary {
+        &self.dict
+    }
+
+    /// Consumes the typed dictionary and returns the underlying `Dictionary`.
+    pub fn into_dictionary(self) -> Dictionary {
+        self.dict
+    }
+
+    /// Returns `true` if the dictionary contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.dict.is_empty()
+    }
+
+    /// Returns the number of elements in the dictionary.
+    pub fn len(&self) -> i32 {
+        self.dict.len()
+    }
+
+    /// Returns true if the dictionary contains the specified key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.dict.contains(&key.to_variant())
+    }
+
+    /// Returns a copy of the value corresponding to the key, or `None` if there is none.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let key = key.to_variant();
+        if self.dict.contains(&key) {
+            Some(Self::convert_value(&self.dict.get(&key)))
+        } else {
+            None
+        }
+    }
+
+    /// Sets the value corresponding to the key.
+    pub fn insert(&mut self, key: &K, val: &V) {
+        self.dict.set(&key.to_variant(), &val.to_variant())
+    }
+
+    /// Removes a key from the dictionary, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let key = key.to_variant();
+        if self.dict.contains(&key) {
+            let val = Self::convert_value(&self.dict.get(&key));
+            self.dict.erase(&key);
+            Some(val)
+        } else {
+            None
+        }
+    }
+
+    /// Clears the dictionary, removing all key-value pairs.
+    pub fn clear(&mut self) {
+        self.dict.clear()
+    }
+
+    /// Returns an iterator over copies of the key-value pairs, in insertion order.
+    pub fn iter(&self) -> TypedDictionaryIter<'_, K, V> {
+        TypedDictionaryIter {
+            iter: self.dict.iter(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new reference to the same dictionary.
+    pub fn new_ref(&self) -> Self {
+        TypedDictionary {
+            dict: self.dict.new_ref(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn convert_value(variant: &Variant) -> V {
+        V::from_variant(variant).unwrap_or_else(|e| {
+            panic!(
+                "value of TypedDictionary<_, {}> has an invalid type: {}",
+                std::any::type_name::<V>(),
+                e
+            )
+        })
+    }
+}
+
+impl<K, V> Default for TypedDictionary<K, V>
+where
+    K: ToVariant + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    fn default() -> Self {
+        TypedDictionary::new()
+    }
+}
+
+impl<K, V> ToVariant for TypedDictionary<K, V>
+where
+    K: ToVariant + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    fn to_variant(&self) -> Variant {
+        self.dict.to_variant()
+    }
+}
+
+impl<K, V> FromVariant for TypedDictionary<K, V>
+where
+    K: ToVariant + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        TypedDictionary::from_dictionary(Dictionary::from_variant(variant)?)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for TypedDictionary<K, V>
+where
+    K: ToVariant + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut dict = TypedDictionary::new();
+        dict.extend(iter);
+        dict
+    }
+}
+
+impl<K, V> Extend<(K, V)> for TypedDictionary<K, V>
+where
+    K: ToVariant + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, val) in iter {
+            self.insert(&key, &val);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a TypedDictionary<K, V>
+where
+    K: ToVariant + FromVariant,
+    V: ToVariant + FromVariant,
+{
+    type Item = (K, V);
+    type IntoIter = TypedDictionaryIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over copies of the key-value pairs of a `TypedDictionary`.
+pub struct TypedDictionaryIter<'a, K, V> {
+    iter: DictionaryIter<'a>,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<'a, K: FromVariant, V: FromVariant> Iterator for TypedDictionaryIter<'a, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, value)| {
+            from_variant_entry(&key, &value).unwrap_or_else(|e| {
+                panic!(
+                    "entry of TypedDictionary<{}, {}> has an invalid type: {}",
+                    std::any::type_name::<K>(),
+                    std::any::type_name::<V>(),
+                    e
+                )
+            })
+        })
+    }
+}
+
+godot_test!(test_typed_dictionary {
+    use std::collections::{BTreeMap, HashMap};
+
+    let mut dict: TypedDictionary<String, i64> =
+        vec![("one".to_string(), 1), ("two".to_string(), 2)].into_iter().collect();
+    assert_eq!(2, dict.len());
+    assert_eq!(Some(2), dict.get(&"two".to_string()));
+    assert_eq!(None, dict.get(&"three".to_string()));
+
+    dict.insert(&"three".to_string(), &3);
+    dict.extend(vec![("four".to_string(), 4)]);
+    assert_eq!(Some(1), dict.remove(&"one".to_string()));
+    assert!(!dict.contains_key(&"one".to_string()));
+
+    let map: HashMap<String, i64> = dict.iter().collect();
+    assert_eq!(3, map.len());
+    assert_eq!(Some(&4), map.get("four"));
+
+    let variant = map.to_variant();
+    let back = TypedDictionary::<String, i64>::from_variant(&variant).unwrap();
+    assert_eq!(Some(3), back.get(&"three".to_string()));
+
+    let tree = BTreeMap::<String, i64>::from_variant(&dict.to_variant()).unwrap();
+    assert_eq!(
+        vec!["four", "three", "two"],
+        tree.keys().map(|k| k.as_str()).collect::<Vec<_>>()
+    );
+
+    let mut mixed = Dictionary::new();
+    mixed.set(&Variant::from_str("ok"), &Variant::from_i64(1));
+    mixed.set(&Variant::from_str("bad"), &Variant::from_str("nope"));
+    match HashMap::<String, i64>::from_variant(&mixed.to_variant()) {
+        Err(FromVariantError::InvalidMapValue { ref key, .. }) if key == "bad" => {}
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("expected an error"),
+    }
+
+    mixed.set(&Variant::from_i64(7), &Variant::from_i64(7));
+    match TypedDictionary::<String, Variant>::from_variant(&mixed.to_variant()) {
+        Err(FromVariantError::InvalidMapKey { ref key, .. }) if key == "7" => {}
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("expected an error"),
+    }
+});
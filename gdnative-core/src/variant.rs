@@ -1,7 +1,11 @@
 use super::*;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::default::Default;
 use std::fmt;
+use std::hash::{BuildHasher, Hash};
 use std::mem::{forget, transmute};
+use std::ops;
 
 // TODO: implement Debug, PartialEq, etc.
 
@@ -230,7 +234,7 @@ impl Variant {
         /// Creates a `Variant` wrapping an array of 32bit signed integers.
         pub fn from_int32_array(&Int32Array) -> Self;
         /// Creates a `Variant` wrapping an array of 32bit floats.
-        pub fn from_float32_array(&Float32Array) -> Self;
+        pub fn from_float32_array(&RealArray) -> Self;
         /// Creates a `Variant` wrapping an array of godot strings.
         pub fn from_string_array(&StringArray) -> Self;
         /// Creates a `Variant` wrapping an array of 2d vectors.
@@ -457,10 +461,10 @@ impl Variant {
         /// Returns `Some(Int32Array)` if this variant is one, `None` otherwise.
         pub fn try_to_int32_array(&self) -> Option<Int32Array>;
 
-        /// Do a best effort to create a `Float32Array` out of the variant, possibly returning a default value.
-        pub fn to_float32_array(&self) -> Float32Array : godot_variant_as_pool_real_array;
-        /// Returns `Some(Float32Array)` if this variant is one, `None` otherwise.
-        pub fn try_to_float32_array(&self) -> Option<Float32Array>;
+        /// Do a best effort to create a `RealArray` out of the variant, possibly returning a default value.
+        pub fn to_float32_array(&self) -> RealArray : godot_variant_as_pool_real_array;
+        /// Returns `Some(RealArray)` if this variant is one, `None` otherwise.
+        pub fn try_to_float32_array(&self) -> Option<RealArray>;
 
         /// Do a best effort to create a `StringArray` out of the variant, possibly returning a default value.
         pub fn to_string_array(&self) -> StringArray : godot_variant_as_pool_string_array;
@@ -528,10 +532,18 @@ impl Variant {
         self.get_type() == VariantType::Nil
     }
 
+    /// Returns `true` if the variant has a callable method named `method`.
+    ///
+    /// Names that are checked often can be cached with `sname!`, e.g.
+    /// `variant.has_method(sname!("update"))`.
     pub fn has_method(&self, method: &GodotString) -> bool {
         unsafe { (get_api().godot_variant_has_method)(&self.0, &method.0) }
     }
 
+    /// Calls the method named `method` on the variant, returning its result.
+    ///
+    /// Names of methods that are called often can be cached with `sname!`, e.g.
+    /// `variant.call(sname!("update"), &[])`.
     pub fn call(&mut self, method: &GodotString, args: &[Variant]) -> Result<Variant, CallError> {
         unsafe {
             let api = get_api();
@@ -549,6 +561,35 @@ impl Variant {
         }
     }
 
+    /// Evaluates an operator on this variant and `rhs`, like the corresponding GDScript
+    /// expression would. Unary operators ignore `rhs`.
+    ///
+    /// Returns an error if the operator is not defined for the operand types, or for integer
+    /// division by zero.
+    pub fn evaluate(&self, op: VariantOperator, rhs: &Variant) -> Result<Variant, InvalidOp> {
+        unsafe {
+            let mut ret = Variant::new();
+            let mut valid = false;
+            (get_api().godot_variant_evaluate)(
+                op as sys::godot_variant_operator,
+                &self.0,
+                &rhs.0,
+                &mut ret.0,
+                &mut valid,
+            );
+
+            if valid {
+                Ok(ret)
+            } else {
+                Err(InvalidOp {
+                    op,
+                    lhs: self.get_type(),
+                    rhs: rhs.get_type(),
+                })
+            }
+        }
+    }
+
     pub(crate) fn cast_ref<'l>(ptr: *const sys::godot_variant) -> &'l Variant {
         unsafe { transmute(ptr) }
     }
@@ -593,10 +634,120 @@ impl_basic_traits!(
     for Variant as godot_variant {
         Drop => godot_variant_destroy;
         Clone => godot_variant_new_copy;
-        PartialEq => godot_variant_operator_equal;
     }
 );
 
+/// Error returned by `Variant::evaluate` when an operator is not defined for its operands.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidOp {
+    pub op: VariantOperator,
+    pub lhs: VariantType,
+    pub rhs: VariantType,
+}
+
+impl fmt::Display for InvalidOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid operands {:?} and {:?} for operator {:?}",
+            self.lhs, self.rhs, self.op
+        )
+    }
+}
+
+impl std::error::Error for InvalidOp {}
+
+/// Compares variants like `==` in GDScript. Numbers of different types are compared by
+/// value, so `1 == 1.0`. Variants that can't be compared with each other are not equal.
+impl PartialEq for Variant {
+    fn eq(&self, other: &Self) -> bool {
+        self.evaluate(VariantOperator::Equal, other)
+            .map(|result| result.to_bool())
+            .unwrap_or(false)
+    }
+}
+
+/// Orders variants like the comparison operators in GDScript. Returns `None` for variants
+/// that can't be compared with each other, and for NaN.
+impl PartialOrd for Variant {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let compare = |op| {
+            self.evaluate(op, other)
+                .map(|result| result.to_bool())
+                .unwrap_or(false)
+        };
+
+        if compare(VariantOperator::Equal) {
+            Some(Ordering::Equal)
+        } else if compare(VariantOperator::Less) {
+            Some(Ordering::Less)
+        } else if compare(VariantOperator::Greater) {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! impl_variant_binary_ops {
+    ($($Trait:ident, $method:ident => $op:ident;)*) => {
+        $(
+            /// Evaluates the operator like GDScript would.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the operator is not defined for the operands. Use `Variant::evaluate`
+            /// to handle this case.
+            impl ops::$Trait<&Variant> for &Variant {
+                type Output = Variant;
+
+                fn $method(self, rhs: &Variant) -> Variant {
+                    self.evaluate(VariantOperator::$op, rhs)
+                        .unwrap_or_else(|e| panic!("{}", e))
+                }
+            }
+
+            impl ops::$Trait for Variant {
+                type Output = Variant;
+
+                fn $method(self, rhs: Variant) -> Variant {
+                    ops::$Trait::$method(&self, &rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_binary_ops! {
+    Add, add => Add;
+    Sub, sub => Subtact;
+    Mul, mul => Multiply;
+    Div, div => Divide;
+    Rem, rem => Module;
+}
+
+/// Negates the variant like GDScript would.
+///
+/// # Panics
+///
+/// Panics if the variant can't be negated. Use `Variant::evaluate` to handle this case.
+impl ops::Neg for &Variant {
+    type Output = Variant;
+
+    fn neg(self) -> Variant {
+        self.evaluate(VariantOperator::Negate, &Variant::new())
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl ops::Neg for Variant {
+    type Output = Variant;
+
+    fn neg(self) -> Variant {
+        (&self).neg()
+    }
+}
+
 impl Default for Variant {
     fn default() -> Self {
         Variant::new()
@@ -660,7 +811,7 @@ variant_from_ref!(
     impl From<&VariantArray> : from_array;
     impl From<&ByteArray> : from_byte_array;
     impl From<&Int32Array> : from_int32_array;
-    impl From<&Float32Array> : from_float32_array;
+    impl From<&RealArray> : from_float32_array;
     impl From<&Vector2Array> : from_vector2_array;
     impl From<&Vector3Array> : from_vector3_array;
     impl From<&ColorArray> : from_color_array;
@@ -737,6 +888,44 @@ godot_test!(
         assert!(v_false.try_to_array().is_none());
 
     }
+
+    test_variant_operators {
+        let int = |i| Variant::from_i64(i);
+        let real = |r| Variant::from_f64(r);
+
+        assert_eq!(Some(7), (int(3) + int(4)).try_to_i64());
+        assert_eq!(Some(3), (int(7) / int(2)).try_to_i64());
+        assert_eq!(Some(-1), (int(-7) % int(2)).try_to_i64());
+        assert_eq!(Some(3.5), (int(7) / real(2.0)).try_to_f64());
+        assert_eq!(Some(-2.5), (-real(2.5)).try_to_f64());
+        assert_eq!(Some(2), (&int(5) - &int(3)).try_to_i64());
+
+        let v = Variant::from_vector2(&Vector2::new(1.0, 2.0));
+        assert_eq!(Some(Vector2::new(2.0, 4.0)), (&v * &int(2)).try_to_vector2());
+        assert_eq!(Some(Vector2::new(2.0, 4.0)), (&v + &v).try_to_vector2());
+
+        let s = Variant::from_str("foo") + Variant::from_str("bar");
+        assert_eq!("foobar", s.to_string());
+
+        let err = int(1).evaluate(VariantOperator::Divide, &int(0)).unwrap_err();
+        assert_eq!(VariantOperator::Divide, err.op);
+        assert!(int(1).evaluate(VariantOperator::Add, &Variant::from_str("a")).is_err());
+
+        assert_eq!(
+            Ok(true),
+            Variant::from_str("oo")
+                .evaluate(VariantOperator::In, &Variant::from_str("foo"))
+                .map(|v| v.to_bool())
+        );
+
+        assert_eq!(int(1), real(1.0));
+        assert_ne!(int(1), Variant::from_str("1"));
+        assert_eq!(Variant::new(), Variant::new());
+        assert!(int(1) < real(1.5));
+        assert!(Variant::from_str("a") < Variant::from_str("b"));
+        assert_eq!(None, int(1).partial_cmp(&Variant::from_str("a")));
+        assert_eq!(None, real(f64::NAN).partial_cmp(&real(f64::NAN)));
+    }
 );
 
 /// Types that can be converted to a `Variant`.
@@ -808,6 +997,9 @@ pub trait FromVariant: Sized {
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 /// Error type returned by `FromVariant::from_variant`.
+///
+/// New kinds of errors may be added in minor versions, so matches on it need a wildcard arm.
+#[non_exhaustive]
 pub enum FromVariantError {
     /// An unspecified error.
     Unspecified,
@@ -872,6 +1064,16 @@ pub enum FromVariantError {
         index: usize,
         error: Box<FromVariantError>,
     },
+    /// Map contains an invalid key.
+    InvalidMapKey {
+        key: String,
+        error: Box<FromVariantError>,
+    },
+    /// Map contains an invalid value.
+    InvalidMapValue {
+        key: String,
+        error: Box<FromVariantError>,
+    },
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -962,6 +1164,10 @@ impl fmt::Display for FromVariantError {
                             write!(f, "[{}]", index)?;
                             next_error = error.as_ref();
                         }
+                        E::InvalidMapValue { key, error } => {
+                            write!(f, "[{:?}]", key)?;
+                            next_error = error.as_ref();
+                        }
                         _ => {
                             write!(f, ": {}", next_error)?;
                             return Ok(());
@@ -972,6 +1178,10 @@ impl fmt::Display for FromVariantError {
             E::InvalidItem { index, error } => {
                 write!(f, "invalid value for item at index {}: {}", index, error)
             }
+            E::InvalidMapKey { key, error } => write!(f, "invalid key {:?}: {}", key, error),
+            E::InvalidMapValue { key, error } => {
+                write!(f, "invalid value for key {:?}: {}", key, error)
+            }
         }
     }
 }
@@ -1138,7 +1348,7 @@ to_variant_as_sys! {
     impl ToVariant for VariantArray : godot_variant_new_array;
     impl ToVariant for ByteArray : godot_variant_new_pool_byte_array;
     impl ToVariant for Int32Array : godot_variant_new_pool_int_array;
-    impl ToVariant for Float32Array : godot_variant_new_pool_real_array;
+    impl ToVariant for RealArray : godot_variant_new_pool_real_array;
     impl ToVariant for StringArray : godot_variant_new_pool_string_array;
     impl ToVariant for Vector2Array : godot_variant_new_pool_vector2_array;
     impl ToVariant for Vector3Array : godot_variant_new_pool_vector3_array;
@@ -1206,7 +1416,6 @@ from_variant_from_sys!(
     impl FromVariant for VariantArray : godot_variant_as_array;
     impl FromVariant for ByteArray : godot_variant_as_pool_byte_array;
     impl FromVariant for Int32Array : godot_variant_as_pool_int_array;
-    impl FromVariant for Float32Array : godot_variant_as_pool_real_array;
     impl FromVariant for StringArray : godot_variant_as_pool_string_array;
     impl FromVariant for Vector2Array : godot_variant_as_pool_vector2_array;
     impl FromVariant for Vector3Array : godot_variant_as_pool_vector3_array;
@@ -1214,6 +1423,17 @@ from_variant_from_sys!(
     impl FromVariant for Dictionary : godot_variant_as_dictionary;
 );
 
+impl FromVariant for RealArray {
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        unsafe {
+            variant
+                .try_as_sys_of_type(VariantType::Float32Array)
+                .map(|v| (get_api().godot_variant_as_pool_real_array)(v))
+                .map(RealArray::from_sys)
+        }
+    }
+}
+
 impl ToVariant for str {
     fn to_variant(&self) -> Variant {
         Variant::from_str(self)
@@ -1427,6 +1647,65 @@ impl<T: FromVariant> FromVariant for Vec<T> {
     }
 }
 
+/// Converts a key-value pair of a `Dictionary`, reporting errors with the offending key.
+pub(crate) fn from_variant_entry<K: FromVariant, V: FromVariant>(
+    key: &Variant,
+    value: &Variant,
+) -> Result<(K, V), FromVariantError> {
+    let k = K::from_variant(key).map_err(|e| FromVariantError::InvalidMapKey {
+        key: key.to_string(),
+        error: Box::new(e),
+    })?;
+    let v = V::from_variant(value).map_err(|e| FromVariantError::InvalidMapValue {
+        key: key.to_string(),
+        error: Box::new(e),
+    })?;
+    Ok((k, v))
+}
+
+impl<K: ToVariant, V: ToVariant, S> ToVariant for HashMap<K, V, S> {
+    fn to_variant(&self) -> Variant {
+        let dict: Dictionary = self
+            .iter()
+            .map(|(k, v)| (k.to_variant(), v.to_variant()))
+            .collect();
+        dict.to_variant()
+    }
+}
+
+impl<K, V, S> FromVariant for HashMap<K, V, S>
+where
+    K: FromVariant + Eq + Hash,
+    V: FromVariant,
+    S: BuildHasher + Default,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Dictionary::from_variant(variant)?
+            .iter()
+            .map(|(k, v)| from_variant_entry(&k, &v))
+            .collect()
+    }
+}
+
+impl<K: ToVariant, V: ToVariant> ToVariant for BTreeMap<K, V> {
+    fn to_variant(&self) -> Variant {
+        let dict: Dictionary = self
+            .iter()
+            .map(|(k, v)| (k.to_variant(), v.to_variant()))
+            .collect();
+        dict.to_variant()
+    }
+}
+
+impl<K: FromVariant + Ord, V: FromVariant> FromVariant for BTreeMap<K, V> {
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Dictionary::from_variant(variant)?
+            .iter()
+            .map(|(k, v)| from_variant_entry(&k, &v))
+            .collect()
+    }
+}
+
 macro_rules! tuple_length {
     () => { 0usize };
     ($_x:ident, $($xs:ident,)*) => {
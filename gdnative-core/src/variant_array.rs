@@ -1,8 +1,14 @@
 use crate::get_api;
 use crate::sys;
 
+use crate::GodotObject;
+use crate::GodotString;
+use crate::ToVariant;
 use crate::Variant;
 
+use std::cmp::Ordering;
+use std::iter::{Extend, FromIterator};
+
 /// A reference-counted `Variant` vector. Godot's generic array data type.
 /// Negative indices can be used to count from the right.
 pub struct VariantArray(pub(crate) sys::godot_array);
@@ -134,20 +140,115 @@ impl VariantArray {
         unsafe { (get_api().godot_array_sort)(&mut self.0) }
     }
 
-    // TODO
-    // pub fn sort_custom(&mut self, obj: ?, s: ?) {
-    //     unimplemented!()
-    // }
+    /// Sorts the array using a custom method of `obj`, which receives two elements and
+    /// returns `true` if the first one is less than the second one.
+    pub fn sort_custom<T: GodotObject>(&mut self, obj: &T, func: &GodotString) {
+        unsafe { (get_api().godot_array_sort_custom)(&mut self.0, obj.to_sys(), &func.0) }
+    }
+
+    /// Sorts the array with a comparator function, like `slice::sort_by`.
+    ///
+    /// The sort is stable. The elements are copied out of the array while sorting, which is
+    /// cheap for all types since arrays, dictionaries and objects are reference-counted.
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&Variant, &Variant) -> Ordering,
+    {
+        let mut elements = self.iter().cloned().collect::<Vec<_>>();
+        elements.sort_by(compare);
+        for (idx, val) in elements.iter().enumerate() {
+            self.set(idx as i32, val);
+        }
+    }
+
+    /// Finds the index of `val` in a sorted array using binary search. If `val` is not
+    /// present, returns the index at which it should be inserted to keep the array sorted.
+    /// If `before` is `true`, the returned index comes before all existing entries equal to
+    /// `val`, otherwise after them.
+    pub fn bsearch(&mut self, val: &Variant, before: bool) -> i32 {
+        unsafe { (get_api().godot_array_bsearch)(&mut self.0, &val.0, before) }
+    }
+
+    /// Finds the index of `val` in an array sorted by a custom method of `obj`, like
+    /// `sort_custom`. See `bsearch` for the meaning of `before`.
+    pub fn bsearch_custom<T: GodotObject>(
+        &mut self,
+        val: &Variant,
+        obj: &T,
+        func: &GodotString,
+        before: bool,
+    ) -> i32 {
+        unsafe {
+            (get_api().godot_array_bsearch_custom)(
+                &mut self.0,
+                &val.0,
+                obj.to_sys(),
+                &func.0,
+                before,
+            )
+        }
+    }
+
+    /// Returns a copy of the array. If `deep` is `true`, nested arrays and dictionaries are
+    /// copied as well, otherwise they are shared with the original.
+    pub fn duplicate(&self, deep: bool) -> VariantArray {
+        unsafe { VariantArray((get_api().godot_array_duplicate)(&self.0, deep)) }
+    }
+
+    /// Returns the elements from `begin` to `end`, both inclusive, every `step` elements.
+    ///
+    /// Negative indices count from the right, and out-of-range indices are clamped. If
+    /// `step` is negative, the elements are returned in reverse order, and `begin` should be
+    /// greater than `end`. If `deep` is `true`, nested arrays and dictionaries are copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn slice(&self, begin: i32, end: i32, step: i32, deep: bool) -> VariantArray {
+        assert!(step != 0, "slice step cannot be zero");
+
+        let mut slice = VariantArray::new();
+        let len = self.len();
+        if len == 0 {
+            return slice;
+        }
+
+        let clamp = |idx: i32| {
+            let idx = idx.max(-len).min(len - 1);
+            if idx < 0 {
+                len + idx
+            } else {
+                idx
+            }
+        };
+
+        let (mut idx, end) = (clamp(begin), clamp(end));
+        while (step > 0 && idx <= end) || (step < 0 && idx >= end) {
+            slice.push(self.get_ref(idx));
+            idx = match idx.checked_add(step) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        if deep {
+            slice.duplicate(true)
+        } else {
+            slice
+        }
+    }
 
-    // pub fn bsearch(&mut self, val: (), before: bool) -> i32 {
-    //     unsafe {
-    //         (get_api().godot_array_bsearch)(&mut self.0, val, before)
-    //     }
-    // }
+    /// Returns the maximum value in the array, or nil if the array is empty or contains
+    /// elements that can't be compared with each other.
+    pub fn max(&self) -> Variant {
+        unsafe { Variant((get_api().godot_array_max)(&self.0)) }
+    }
 
-    // pub fn bsearch_custom(&mut self, val: ?, obj: ?, s: ?, before: bool) -> i32 {
-    //     unimplemented!();
-    // }
+    /// Returns the minimum value in the array, or nil if the array is empty or contains
+    /// elements that can't be compared with each other.
+    pub fn min(&self) -> Variant {
+        unsafe { Variant((get_api().godot_array_min)(&self.0)) }
+    }
 
     pub fn iter(&self) -> Iter {
         Iter {
@@ -186,6 +287,53 @@ impl_basic_traits!(
     }
 );
 
+impl<T: ToVariant> FromIterator<T> for VariantArray {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut array = VariantArray::new();
+        array.extend(iter);
+        array
+    }
+}
+
+impl<T: ToVariant> Extend<T> for VariantArray {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(&val.to_variant());
+        }
+    }
+}
+
+impl IntoIterator for VariantArray {
+    type Item = Variant;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        IntoIter {
+            arr: self,
+            range: 0..len,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a VariantArray {
+    type Item = &'a Variant;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut VariantArray {
+    type Item = &'a mut Variant;
+    type IntoIter = IterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 pub struct Iter<'a> {
     arr: &'a VariantArray,
     range: std::ops::Range<i32>,
@@ -221,6 +369,23 @@ impl<'a> Iterator for IterMut<'a> {
     }
 }
 
+/// Iterator over copies of the elements of an owned `VariantArray`.
+pub struct IntoIter {
+    arr: VariantArray,
+    range: std::ops::Range<i32>,
+}
+
+impl Iterator for IntoIter {
+    type Item = Variant;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|idx| self.arr.get_ref(idx).clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
 godot_test!(test_array {
     let foo = Variant::from_str("foo");
     let bar = Variant::from_str("bar");
@@ -303,6 +468,43 @@ godot_test!(test_array {
     );
 });
 
+godot_test!(test_array_functions {
+    let mut array: VariantArray = vec![3, 1, 2].into_iter().collect();
+    array.extend(vec!["a".to_string()]);
+    assert_eq!(4, array.len());
+
+    array.pop();
+    array.sort_by(|a, b| b.to_i64().cmp(&a.to_i64()));
+    assert_eq!(
+        vec![3, 2, 1],
+        (&array).into_iter().map(|v| v.to_i64()).collect::<Vec<_>>()
+    );
+
+    array.sort();
+    assert_eq!(1, array.bsearch(&Variant::from_i64(2), true));
+    assert_eq!(2, array.bsearch(&Variant::from_i64(2), false));
+    assert_eq!(Some(3), array.max().try_to_i64());
+    assert_eq!(Some(1), array.min().try_to_i64());
+    assert!(VariantArray::new().max().is_nil());
+
+    let mut nested = VariantArray::new();
+    nested.push(&Variant::from_array(&array));
+    let shallow = nested.duplicate(false);
+    let deep = nested.duplicate(true);
+    array.push(&Variant::from_i64(4));
+    assert_eq!(4, shallow.get_ref(0).to_array().len());
+    assert_eq!(3, deep.get_ref(0).to_array().len());
+
+    let to_vec = |arr: VariantArray| arr.into_iter().map(|v| v.to_i64()).collect::<Vec<_>>();
+    assert_eq!(vec![2, 3], to_vec(array.slice(1, 2, 1, false)));
+    assert_eq!(vec![1, 3], to_vec(array.slice(0, -1, 2, false)));
+    assert_eq!(vec![4, 3, 2, 1], to_vec(array.slice(-1, 0, -1, false)));
+    assert_eq!(vec![1, 2, 3, 4], to_vec(array.slice(-10, 10, 1, false)));
+    assert_eq!(vec![2], to_vec(array.slice(1, 2, i32::MAX, false)));
+    assert_eq!(vec![3], to_vec(array.slice(2, 1, i32::MIN, false)));
+    assert!(VariantArray::new().slice(0, 1, 1, false).is_empty());
+});
+
 // TODO: clear arrays without affecting clones
 //godot_test!(test_array_clone_clear {
 //    let foo = Variant::from_str("foo");
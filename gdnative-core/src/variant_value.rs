@@ -0,0 +1,442 @@
+use crate::*;
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// An owned, engine-independent copy of the contents of a `Variant`.
+///
+/// There is one enum variant for each `VariantType`. Unlike `Variant` and the container types,
+/// values of this type don't need the API to be bound, and are `Send` and `Sync`, so they can
+/// be moved to other threads or used in tests.
+///
+/// Values are converted from a `Variant` with `From`, and back with `try_to_variant`, which
+/// fails for values containing objects.
+///
+/// Floats are compared by their total order, so that any value can be used as a key in
+/// a `Dictionary`: `NaN` is equal to itself, and `-0.0` is not equal to `0.0`.
+#[derive(Clone, Debug)]
+pub enum VariantValue {
+    Nil,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    GodotString(String),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    NodePath(String),
+    /// The ID of a `Rid`, as returned by `Rid::get_id`. Only the invalid `Rid`, with the ID
+    /// `0`, can be converted back to a `Variant`.
+    Rid(u64),
+    Object(ObjectValue),
+    Dictionary(BTreeMap<VariantValue, VariantValue>),
+    VariantArray(Vec<VariantValue>),
+    ByteArray(Vec<u8>),
+    Int32Array(Vec<i32>),
+    Float32Array(Vec<real>),
+    StringArray(Vec<String>),
+    Vector2Array(Vec<Vector2>),
+    Vector3Array(Vec<Vector3>),
+    ColorArray(Vec<Color>),
+}
+
+/// An object stored in a `VariantValue`.
+///
+/// Objects are owned by the engine, so they are only referred to by their instance ID. Since
+/// objects can't be looked up by their ID from GDNative, only null objects can be converted
+/// back to a `Variant`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ObjectValue {
+    /// A reference to an object by its instance ID. Null objects have the ID `0`.
+    Id(u64),
+    /// A complete object with its class name and stored properties, as found in data encoded
+    /// with `full_objects`.
+    Full {
+        class: String,
+        properties: Vec<(String, VariantValue)>,
+    },
+}
+
+impl VariantValue {
+    /// Returns the `VariantType` of this value.
+    pub fn get_type(&self) -> VariantType {
+        match self {
+            VariantValue::Nil => VariantType::Nil,
+            VariantValue::Bool(_) => VariantType::Bool,
+            VariantValue::I64(_) => VariantType::I64,
+            VariantValue::F64(_) => VariantType::F64,
+            VariantValue::GodotString(_) => VariantType::GodotString,
+            VariantValue::Vector2(_) => VariantType::Vector2,
+            VariantValue::Rect2(_) => VariantType::Rect2,
+            VariantValue::Vector3(_) => VariantType::Vector3,
+            VariantValue::Transform2D(_) => VariantType::Transform2D,
+            VariantValue::Plane(_) => VariantType::Plane,
+            VariantValue::Quat(_) => VariantType::Quat,
+            VariantValue::Aabb(_) => VariantType::Aabb,
+            VariantValue::Basis(_) => VariantType::Basis,
+            VariantValue::Transform(_) => VariantType::Transform,
+            VariantValue::Color(_) => VariantType::Color,
+            VariantValue::NodePath(_) => VariantType::NodePath,
+            VariantValue::Rid(_) => VariantType::Rid,
+            VariantValue::Object(_) => VariantType::Object,
+            VariantValue::Dictionary(_) => VariantType::Dictionary,
+            VariantValue::VariantArray(_) => VariantType::VariantArray,
+            VariantValue::ByteArray(_) => VariantType::ByteArray,
+            VariantValue::Int32Array(_) => VariantType::Int32Array,
+            VariantValue::Float32Array(_) => VariantType::Float32Array,
+            VariantValue::StringArray(_) => VariantType::StringArray,
+            VariantValue::Vector2Array(_) => VariantType::Vector2Array,
+            VariantValue::Vector3Array(_) => VariantType::Vector3Array,
+            VariantValue::ColorArray(_) => VariantType::ColorArray,
+        }
+    }
+
+    /// Returns true if this is `Nil`.
+    pub fn is_nil(&self) -> bool {
+        matches!(self, VariantValue::Nil)
+    }
+
+    /// Converts the value back to a `Variant`.
+    ///
+    /// Returns `None` if the value is or contains a non-null object, which can't be looked up
+    /// by its instance ID from GDNative. Full objects aren't instanced either, like in
+    /// `bytes2var` without `allow_objects`. Valid `Rid`s can't be looked up by their ID either,
+    /// so `None` is also returned for any `Rid` other than `Rid(0)`.
+    pub fn try_to_variant(&self) -> Option<Variant> {
+        let variant = match self {
+            VariantValue::Nil => Variant::new(),
+            VariantValue::Bool(v) => Variant::from_bool(*v),
+            VariantValue::I64(v) => Variant::from_i64(*v),
+            VariantValue::F64(v) => Variant::from_f64(*v),
+            VariantValue::GodotString(v) => Variant::from_str(v),
+            VariantValue::Vector2(v) => Variant::from_vector2(v),
+            VariantValue::Rect2(v) => Variant::from_rect2(v),
+            VariantValue::Vector3(v) => Variant::from_vector3(v),
+            VariantValue::Transform2D(v) => Variant::from_transform2d(v),
+            VariantValue::Plane(v) => Variant::from_plane(v),
+            VariantValue::Quat(v) => Variant::from_quat(v),
+            VariantValue::Aabb(v) => Variant::from_aabb(v),
+            VariantValue::Basis(v) => Variant::from_basis(v),
+            VariantValue::Transform(v) => Variant::from_transform(v),
+            VariantValue::Color(v) => Variant::from_color(v),
+            VariantValue::NodePath(v) => Variant::from_node_path(&NodePath::from_str(v)),
+            VariantValue::Rid(0) => Variant::from_rid(&Rid::new()),
+            VariantValue::Rid(_) => return None,
+            VariantValue::Object(ObjectValue::Id(0)) => unsafe {
+                let mut dest = sys::godot_variant::default();
+                (get_api().godot_variant_new_object)(&mut dest, std::ptr::null());
+                Variant::from_sys(dest)
+            },
+            VariantValue::Object(_) => return None,
+            VariantValue::Dictionary(entries) => {
+                let mut dict = Dictionary::new();
+                for (key, value) in entries {
+                    dict.set(&key.try_to_variant()?, &value.try_to_variant()?);
+                }
+                Variant::from_dictionary(&dict)
+            }
+            VariantValue::VariantArray(items) => {
+                let mut array = VariantArray::new();
+                for item in items {
+                    array.push(&item.try_to_variant()?);
+                }
+                Variant::from_array(&array)
+            }
+            VariantValue::ByteArray(v) => {
+                let array = ByteArray::from_slice(v);
+                Variant::from_byte_array(&array)
+            }
+            VariantValue::Int32Array(v) => {
+                let array = Int32Array::from_slice(v);
+                Variant::from_int32_array(&array)
+            }
+            VariantValue::Float32Array(v) => {
+                let array = RealArray::from_slice(v);
+                Variant::from_float32_array(&array)
+            }
+            VariantValue::StringArray(v) => {
+                let array: StringArray = v.iter().map(GodotString::from_str).collect();
+                Variant::from_string_array(&array)
+            }
+            VariantValue::Vector2Array(v) => {
+                let array = Vector2Array::from_slice(v);
+                Variant::from_vector2_array(&array)
+            }
+            VariantValue::Vector3Array(v) => {
+                let array = Vector3Array::from_slice(v);
+                Variant::from_vector3_array(&array)
+            }
+            VariantValue::ColorArray(v) => {
+                let array = ColorArray::from_slice(v);
+                Variant::from_color_array(&array)
+            }
+        };
+        Some(variant)
+    }
+
+    /// Returns the real components of geometry values and float arrays, in the order they
+    /// are laid out in memory.
+    fn reals(&self) -> Option<Vec<real>> {
+        let reals = match self {
+            VariantValue::Vector2(v) => vec![v.x, v.y],
+            VariantValue::Rect2(r) => vec![r.origin.x, r.origin.y, r.size.width, r.size.height],
+            VariantValue::Vector3(v) => vec![v.x, v.y, v.z],
+            VariantValue::Transform2D(t) => vec![t.m11, t.m12, t.m21, t.m22, t.m31, t.m32],
+            VariantValue::Plane(p) => vec![p.normal.x, p.normal.y, p.normal.z, p.d],
+            VariantValue::Quat(q) => vec![q.i, q.j, q.k, q.r],
+            VariantValue::Aabb(a) => vec![
+                a.position.x,
+                a.position.y,
+                a.position.z,
+                a.size.x,
+                a.size.y,
+                a.size.z,
+            ],
+            VariantValue::Basis(b) => basis_reals(b).to_vec(),
+            VariantValue::Transform(t) => {
+                let mut reals = basis_reals(&t.basis).to_vec();
+                reals.extend_from_slice(&[t.origin.x, t.origin.y, t.origin.z]);
+                reals
+            }
+            VariantValue::Color(c) => color_reals(c).to_vec(),
+            VariantValue::Float32Array(v) => v.clone(),
+            VariantValue::Vector2Array(v) => v.iter().flat_map(|v| vec![v.x, v.y]).collect(),
+            VariantValue::Vector3Array(v) => v.iter().flat_map(|v| vec![v.x, v.y, v.z]).collect(),
+            VariantValue::ColorArray(v) => v.iter().flat_map(color_reals).collect(),
+            _ => return None,
+        };
+        Some(reals)
+    }
+}
+
+fn basis_reals(basis: &Basis) -> [real; 9] {
+    let [x, y, z] = basis.elements;
+    [x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z]
+}
+
+fn color_reals(color: &Color) -> [real; 4] {
+    [
+        color.r as real,
+        color.g as real,
+        color.b as real,
+        color.a as real,
+    ]
+}
+
+/// Maps the bits of a float to an integer with the same total order.
+fn total_order_key_f64(f: f64) -> i64 {
+    let bits = f.to_bits() as i64;
+    bits ^ (((bits >> 63) as u64) >> 1) as i64
+}
+
+impl Ord for VariantValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        use VariantValue as V;
+
+        let by_type = (self.get_type() as u32).cmp(&(other.get_type() as u32));
+        if by_type != Ordering::Equal {
+            return by_type;
+        }
+
+        match (self, other) {
+            (V::Bool(a), V::Bool(b)) => a.cmp(b),
+            (V::I64(a), V::I64(b)) => a.cmp(b),
+            (V::F64(a), V::F64(b)) => total_order_key_f64(*a).cmp(&total_order_key_f64(*b)),
+            (V::GodotString(a), V::GodotString(b)) | (V::NodePath(a), V::NodePath(b)) => a.cmp(b),
+            (V::Rid(a), V::Rid(b)) => a.cmp(b),
+            (V::Object(a), V::Object(b)) => a.cmp(b),
+            (V::Dictionary(a), V::Dictionary(b)) => a.cmp(b),
+            (V::VariantArray(a), V::VariantArray(b)) => a.cmp(b),
+            (V::ByteArray(a), V::ByteArray(b)) => a.cmp(b),
+            (V::Int32Array(a), V::Int32Array(b)) => a.cmp(b),
+            (V::StringArray(a), V::StringArray(b)) => a.cmp(b),
+            _ => match (self.reals(), other.reals()) {
+                (Some(a), Some(b)) => a
+                    .iter()
+                    .map(|&f| total_order_key_f64(f.into()))
+                    .cmp(b.iter().map(|&f| total_order_key_f64(f.into()))),
+                _ => Ordering::Equal,
+            },
+        }
+    }
+}
+
+impl PartialOrd for VariantValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for VariantValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for VariantValue {}
+
+impl<'a> From<&'a Variant> for VariantValue {
+    fn from(variant: &'a Variant) -> Self {
+        match variant.get_type() {
+            VariantType::Nil => VariantValue::Nil,
+            VariantType::Bool => VariantValue::Bool(variant.to_bool()),
+            VariantType::I64 => VariantValue::I64(variant.to_i64()),
+            VariantType::F64 => VariantValue::F64(variant.to_f64()),
+            VariantType::GodotString => VariantValue::GodotString(variant.to_string()),
+            VariantType::Vector2 => VariantValue::Vector2(variant.to_vector2()),
+            VariantType::Rect2 => VariantValue::Rect2(variant.to_rect2()),
+            VariantType::Vector3 => VariantValue::Vector3(variant.to_vector3()),
+            VariantType::Transform2D => VariantValue::Transform2D(variant.to_transform2d()),
+            VariantType::Plane => VariantValue::Plane(variant.to_plane()),
+            VariantType::Quat => VariantValue::Quat(variant.to_quat()),
+            VariantType::Aabb => VariantValue::Aabb(variant.to_aabb()),
+            VariantType::Basis => VariantValue::Basis(variant.to_basis()),
+            VariantType::Transform => VariantValue::Transform(variant.to_transform()),
+            VariantType::Color => VariantValue::Color(variant.to_color()),
+            VariantType::NodePath => VariantValue::NodePath(variant.to_node_path().to_string()),
+            VariantType::Rid => VariantValue::Rid(u64::from(variant.to_rid().get_id() as u32)),
+            VariantType::Object => VariantValue::Object(ObjectValue::Id(object_id(variant))),
+            VariantType::Dictionary => {
+                let dict = variant.to_dictionary();
+                let entries = dict
+                    .keys()
+                    .iter()
+                    .map(|key| (VariantValue::from(key), VariantValue::from(&dict.get(key))))
+                    .collect();
+                VariantValue::Dictionary(entries)
+            }
+            VariantType::VariantArray => {
+                let items = variant.to_array().iter().map(VariantValue::from).collect();
+                VariantValue::VariantArray(items)
+            }
+            VariantType::ByteArray => {
+                VariantValue::ByteArray(variant.to_byte_array().read().to_vec())
+            }
+            VariantType::Int32Array => {
+                VariantValue::Int32Array(variant.to_int32_array().read().to_vec())
+            }
+            VariantType::Float32Array => {
+                VariantValue::Float32Array(variant.to_float32_array().read().to_vec())
+            }
+            VariantType::StringArray => {
+                let array = variant.to_string_array();
+                let strings = array.read().iter().map(GodotString::to_string).collect();
+                VariantValue::StringArray(strings)
+            }
+            VariantType::Vector2Array => {
+                VariantValue::Vector2Array(variant.to_vector2_array().read().to_vec())
+            }
+            VariantType::Vector3Array => {
+                VariantValue::Vector3Array(variant.to_vector3_array().read().to_vec())
+            }
+            VariantType::ColorArray => {
+                VariantValue::ColorArray(variant.to_color_array().read().to_vec())
+            }
+        }
+    }
+}
+
+impl From<Variant> for VariantValue {
+    fn from(variant: Variant) -> Self {
+        VariantValue::from(&variant)
+    }
+}
+
+fn object_id(variant: &Variant) -> u64 {
+    unsafe {
+        let obj = (get_api().godot_variant_as_object)(&variant.0);
+        if obj.is_null() {
+            0
+        } else {
+            Object::from_sys(obj).get_instance_id() as u64
+        }
+    }
+}
+
+impl FromVariant for VariantValue {
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Ok(VariantValue::from(variant))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<VariantValue>();
+    }
+
+    #[test]
+    fn it_orders_floats_totally() {
+        assert_eq!(VariantValue::F64(f64::NAN), VariantValue::F64(f64::NAN));
+        assert_ne!(VariantValue::F64(0.0), VariantValue::F64(-0.0));
+        assert!(VariantValue::F64(-1.0) < VariantValue::F64(0.5));
+        assert!(
+            VariantValue::Vector2(Vector2::new(1.0, -2.0))
+                < VariantValue::Vector2(Vector2::new(1.0, 2.0))
+        );
+    }
+
+    #[test]
+    fn it_orders_by_type_first() {
+        assert!(VariantValue::Nil < VariantValue::Bool(false));
+        assert!(VariantValue::I64(100) < VariantValue::F64(-100.0));
+        assert!(VariantValue::GodotString("b".into()) > VariantValue::GodotString("a".into()));
+    }
+}
+
+godot_test!(test_variant_value_roundtrip {
+    let mut dict = Dictionary::new();
+    dict.set(&Variant::from_i64(1), &Variant::from_str("one"));
+    dict.set(&Variant::from_f64(0.5), &Variant::from_vector3(&Vector3::new(1.0, 2.0, 3.0)));
+
+    let mut array = VariantArray::new();
+    array.push(&Variant::from_dictionary(&dict));
+    array.push(&Variant::from_node_path(&NodePath::from_str("/root/a:b")));
+    array.push(&Variant::from_color(&Color::rgb(1.0, 0.0, 0.5)));
+    array.push(&Variant::new());
+
+    let value = VariantValue::from(&Variant::from_array(&array));
+    let items = match &value {
+        VariantValue::VariantArray(items) => items,
+        _ => panic!("expected VariantArray, got {:?}", value),
+    };
+    assert_eq!(4, items.len());
+    match &items[0] {
+        VariantValue::Dictionary(entries) => {
+            assert_eq!(
+                Some(&VariantValue::GodotString("one".into())),
+                entries.get(&VariantValue::I64(1))
+            );
+        }
+        other => panic!("expected Dictionary, got {:?}", other),
+    }
+    assert_eq!(VariantValue::NodePath("/root/a:b".into()), items[1]);
+    assert!(items[3].is_nil());
+
+    let back = value.try_to_variant().unwrap();
+    assert_eq!(value, VariantValue::from(&back));
+
+    let null = VariantValue::Object(ObjectValue::Id(0));
+    assert_eq!(VariantType::Object, null.try_to_variant().unwrap().get_type());
+    let object = VariantValue::Object(ObjectValue::Id(1234));
+    assert!(object.try_to_variant().is_none());
+    assert!(VariantValue::VariantArray(vec![VariantValue::Nil, object]).try_to_variant().is_none());
+
+    let invalid = VariantValue::Rid(0);
+    let variant = invalid.try_to_variant().unwrap();
+    assert_eq!(VariantType::Rid, variant.get_type());
+    assert!(!variant.to_rid().is_valid());
+    assert_eq!(invalid, VariantValue::from(&variant));
+    assert!(VariantValue::Rid(0xdead_beef).try_to_variant().is_none());
+});
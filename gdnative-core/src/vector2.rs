@@ -1,18 +1,65 @@
-use crate::{Angle, Rotation2D, Vector2};
+use crate::geom::{fposmod, sgn, CMP_EPSILON, UNIT_EPSILON};
+use crate::{real, Angle, Rotation2D, Vector2};
 
 /// Helper methods for `Vector2`.
 ///
 /// Trait used to provide additional methods that are equivalent to Godot's methods.
-/// See the official [`Godot documentation`](https://docs.godotengine.org/en/3.1/classes/class_vector2.html).
+/// See the official [`Godot documentation`](https://docs.godotengine.org/en/3.2/classes/class_vector2.html).
+///
+/// Methods that euclid already provides with the same semantics, like `abs`, `dot`, `cross`,
+/// `length`, `floor`, `ceil` and `round`, are not repeated here. The engine's `reflect` returns
+/// the opposite of euclid's `Vector2D::reflect`, which is `bounce` here, so it is provided as
+/// `reflect_godot` instead of shadowing it.
 pub trait Vector2Godot {
+    /// Returns the angle of the vector relative to the X axis, in the range -PI - PI.
+    fn angle(self) -> Angle;
+    /// Returns the signed angle to the given vector, in the range -PI - PI.
+    fn angle_to(self, to: Self) -> Angle;
+    /// Returns the angle of the line from `to` to this vector, relative to the X axis.
+    fn angle_to_point(self, to: Self) -> Angle;
     /// Returns the ratio of x to y.
-    fn aspect(self) -> f32;
+    fn aspect(self) -> real;
+    /// Returns the vector bounced off a plane defined by the given normal.
+    fn bounce(self, normal: Self) -> Self;
+    /// Returns the vector with its length limited to `length`.
+    fn clamped(self, length: real) -> Self;
     /// Cubicly interpolates between this vector and `b` using `pre_a` and `post_b` as handles,
     /// and returns the result at position `t`. `t` is in the range of 0.0 - 1.0, representing
     /// the amount of interpolation.
-    fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self;
+    fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: real) -> Self;
+    /// Returns the normalized vector pointing from this vector to `to`.
+    fn direction_to(self, to: Self) -> Self;
+    /// Returns the squared distance to `to`.
+    fn distance_squared_to(self, to: Self) -> real;
+    /// Returns the distance to `to`.
+    fn distance_to(self, to: Self) -> real;
+    /// Returns `true` if the length of the vector is approximately 1.
+    fn is_normalized(&self) -> bool;
+    /// Returns the squared length of the vector.
+    fn length_squared(self) -> real;
+    /// Linearly interpolates between this vector and `b` by `t`, in the range 0.0 - 1.0.
+    fn linear_interpolate(self, b: Self, t: real) -> Self;
+    /// Returns the vector moved towards `to` by at most `delta`.
+    fn move_toward(self, to: Self, delta: real) -> Self;
+    /// Returns the vector scaled to unit length. A zero vector stays zero.
+    fn normalized(self) -> Self;
+    /// Returns the vector with each component replaced by its positive remainder by `modulus`.
+    fn posmod(self, modulus: real) -> Self;
+    /// Returns the vector with each component replaced by its positive remainder by the
+    /// matching component of `modulus`.
+    fn posmodv(self, modulus: Self) -> Self;
+    /// Returns the vector projected onto `onto`.
+    fn project(self, onto: Self) -> Self;
+    /// Returns the vector reflected from a plane defined by the given normal.
+    fn reflect_godot(self, normal: Self) -> Self;
     /// Returns the vector rotated by `angle` radians.
     fn rotated(self, angle: Angle) -> Self;
+    /// Returns the vector with each component set to -1.0 if it is negative, and to 1.0
+    /// otherwise, like in the engine.
+    fn sign(self) -> Self;
+    /// Spherically interpolates between this vector and `b` by `t`, in the range 0.0 - 1.0.
+    /// Both vectors must be normalized.
+    fn slerp(self, b: Self, t: real) -> Self;
     /// Returns the component of the vector along a plane defined by the given normal.
     fn slide(self, normal: Self) -> Self;
     /// Returns the vector snapped to a grid with the given size.
@@ -23,12 +70,42 @@ pub trait Vector2Godot {
 
 impl Vector2Godot for Vector2 {
     #[inline]
-    fn aspect(self) -> f32 {
+    fn angle(self) -> Angle {
+        Angle::radians(self.y.atan2(self.x))
+    }
+
+    #[inline]
+    fn angle_to(self, to: Self) -> Angle {
+        Angle::radians(self.cross(to).atan2(self.dot(to)))
+    }
+
+    #[inline]
+    fn angle_to_point(self, to: Self) -> Angle {
+        Angle::radians((self.y - to.y).atan2(self.x - to.x))
+    }
+
+    #[inline]
+    fn aspect(self) -> real {
         self.x / self.y
     }
 
     #[inline]
-    fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self {
+    fn bounce(self, normal: Self) -> Self {
+        -self.reflect_godot(normal)
+    }
+
+    #[inline]
+    fn clamped(self, length: real) -> Self {
+        let current = self.length();
+        if current > 0.0 && length < current {
+            self / current * length
+        } else {
+            self
+        }
+    }
+
+    #[inline]
+    fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: real) -> Self {
         let v0 = pre_a;
         let v1 = self;
         let v2 = b;
@@ -44,12 +121,94 @@ impl Vector2Godot for Vector2 {
             * 0.5
     }
 
+    #[inline]
+    fn direction_to(self, to: Self) -> Self {
+        (to - self).normalized()
+    }
+
+    #[inline]
+    fn distance_squared_to(self, to: Self) -> real {
+        (to - self).length_squared()
+    }
+
+    #[inline]
+    fn distance_to(self, to: Self) -> real {
+        (to - self).length()
+    }
+
+    #[inline]
+    fn is_normalized(&self) -> bool {
+        let length_squared = self.length_squared();
+        length_squared == 1.0 || ((length_squared - 1.0).abs() as f64) < UNIT_EPSILON
+    }
+
+    #[inline]
+    fn length_squared(self) -> real {
+        self.x * self.x + self.y * self.y
+    }
+
+    #[inline]
+    fn linear_interpolate(self, b: Self, t: real) -> Self {
+        self + (b - self) * t
+    }
+
+    #[inline]
+    fn move_toward(self, to: Self, delta: real) -> Self {
+        let vd = to - self;
+        let length = vd.length();
+        if length <= delta || (length as f64) < CMP_EPSILON {
+            to
+        } else {
+            self + vd / length * delta
+        }
+    }
+
+    #[inline]
+    fn normalized(self) -> Self {
+        let length_squared = self.length_squared();
+        if length_squared != 0.0 {
+            self / length_squared.sqrt()
+        } else {
+            self
+        }
+    }
+
+    #[inline]
+    fn posmod(self, modulus: real) -> Self {
+        Vector2::new(fposmod(self.x, modulus), fposmod(self.y, modulus))
+    }
+
+    #[inline]
+    fn posmodv(self, modulus: Self) -> Self {
+        Vector2::new(fposmod(self.x, modulus.x), fposmod(self.y, modulus.y))
+    }
+
+    #[inline]
+    fn project(self, onto: Self) -> Self {
+        onto * (self.dot(onto) / onto.length_squared())
+    }
+
+    #[inline]
+    fn reflect_godot(self, normal: Self) -> Self {
+        normal * 2.0 * self.dot(normal) - self
+    }
+
     #[inline]
     fn rotated(self, angle: Angle) -> Self {
         let r = Rotation2D::new(angle);
         r.transform_vector(self)
     }
 
+    #[inline]
+    fn sign(self) -> Self {
+        Vector2::new(sgn(self.x), sgn(self.y))
+    }
+
+    #[inline]
+    fn slerp(self, b: Self, t: real) -> Self {
+        self.rotated(self.angle_to(b) * t)
+    }
+
     #[inline]
     fn slide(self, normal: Self) -> Self {
         self - normal * self.dot(normal)
@@ -199,4 +358,73 @@ mod tests {
             assert!(expected.approx_eq(&v.snapped(by)));
         }
     }
+
+    #[test]
+    fn angles_are_sane() {
+        use crate::real_consts::{FRAC_PI_2, FRAC_PI_4, PI};
+        use Vector2 as V;
+
+        assert!((V::new(0.0, 2.0).angle().radians - FRAC_PI_2).abs() < 0.0001);
+        assert!((V::new(1.0, 0.0).angle_to(V::new(1.0, 1.0)).radians - FRAC_PI_4).abs() < 0.0001);
+        assert!((V::new(1.0, 1.0).angle_to(V::new(1.0, 0.0)).radians + FRAC_PI_4).abs() < 0.0001);
+        assert!((V::new(0.0, 0.0).angle_to_point(V::new(1.0, 0.0)).radians - PI).abs() < 0.0001);
+    }
+
+    #[test]
+    fn reflect_and_bounce_are_sane() {
+        use Vector2 as V;
+
+        let normal = V::new(0.0, 1.0);
+        assert_eq!(V::new(-1.0, 1.0), V::new(1.0, 1.0).reflect_godot(normal));
+        assert_eq!(V::new(1.0, -1.0), V::new(1.0, 1.0).bounce(normal));
+        assert_eq!(V::new(1.0, -1.0), V::new(1.0, 1.0).reflect(normal));
+        assert_eq!(V::new(2.0, 0.0), V::new(2.0, 3.0).project(V::new(4.0, 0.0)));
+    }
+
+    #[test]
+    fn lengths_and_distances_are_sane() {
+        use euclid::approxeq::ApproxEq;
+        use Vector2 as V;
+
+        let v = V::new(3.0, 4.0);
+        assert_eq!(25.0, v.length_squared());
+        assert_eq!(V::new(0.6, 0.8), v.normalized());
+        assert_eq!(V::new(0.0, 0.0), V::new(0.0, 0.0).normalized());
+        assert!(v.normalized().is_normalized());
+        assert!(!v.is_normalized());
+        assert!(V::new(1.5, 2.0).approx_eq(&v.clamped(2.5)));
+        assert_eq!(v, v.clamped(10.0));
+
+        let to = V::new(0.0, 8.0);
+        assert_eq!(25.0, v.distance_squared_to(to));
+        assert_eq!(5.0, v.distance_to(to));
+        assert_eq!(V::new(-0.6, 0.8), v.direction_to(to));
+
+        assert!(V::new(2.4, 4.8).approx_eq(&v.move_toward(to, 1.0)));
+        assert_eq!(to, v.move_toward(to, 5.0));
+        assert_eq!(V::new(1.5, 6.0), v.linear_interpolate(to, 0.5));
+    }
+
+    #[test]
+    fn slerp_is_sane() {
+        use euclid::approxeq::ApproxEq;
+        use Vector2 as V;
+
+        let half = crate::real_consts::FRAC_1_SQRT_2;
+        assert!(V::new(half, half).approx_eq(&V::new(1.0, 0.0).slerp(V::new(0.0, 1.0), 0.5)));
+    }
+
+    #[test]
+    fn componentwise_functions_are_sane() {
+        use Vector2 as V;
+
+        let v = V::new(-1.5, 2.5);
+        assert_eq!(V::new(-1.0, 3.0), v.ceil());
+        assert_eq!(V::new(-2.0, 2.0), v.floor());
+        assert_eq!(V::new(-2.0, 3.0), v.round());
+        assert_eq!(V::new(-1.0, 1.0), v.sign());
+        assert_eq!(V::new(1.0, 1.0), V::new(0.0, 0.0).sign());
+        assert_eq!(V::new(0.5, 0.5), v.posmod(2.0));
+        assert_eq!(V::new(1.5, 0.5), v.posmodv(V::new(3.0, 1.0)));
+    }
 }
@@ -1,6 +1,182 @@
+use crate::geom::{fposmod, sgn, CMP_EPSILON};
+use crate::{real, Angle, Basis, Vector3};
+
+/// Helper methods for `Vector3`.
+///
+/// Trait used to provide additional methods that are equivalent to Godot's methods.
+/// See the official [`Godot documentation`](https://docs.godotengine.org/en/3.2/classes/class_vector3.html).
+///
+/// Methods that euclid already provides with the same semantics, like `abs`, `dot`, `cross`,
+/// `length`, `floor`, `ceil` and `round`, are not repeated here. The engine's `reflect` returns
+/// the opposite of euclid's `Vector3D::reflect`, which is `bounce` here, so it is provided as
+/// `reflect_godot` instead of shadowing it.
+pub trait Vector3Godot {
+    /// Returns the unsigned angle to the given vector, in the range 0 - PI.
+    fn angle_to(self, to: Self) -> Angle;
+    /// Returns the vector bounced off a plane defined by the given normal.
+    fn bounce(self, normal: Self) -> Self;
+    /// Cubicly interpolates between this vector and `b` using `pre_a` and `post_b` as handles,
+    /// and returns the result at position `t`. `t` is in the range of 0.0 - 1.0, representing
+    /// the amount of interpolation.
+    fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: real) -> Self;
+    /// Returns the normalized vector pointing from this vector to `to`.
+    fn direction_to(self, to: Self) -> Self;
+    /// Returns the squared distance to `to`.
+    fn distance_squared_to(self, to: Self) -> real;
+    /// Returns the distance to `to`.
+    fn distance_to(self, to: Self) -> real;
+    /// Returns the vector moved towards `to` by at most `delta`.
+    fn move_toward(self, to: Self, delta: real) -> Self;
+    /// Returns the outer product with `b`, whose rows are `b` scaled by each component of
+    /// this vector.
+    fn outer(self, b: Self) -> Basis;
+    /// Returns the vector with each component replaced by its positive remainder by `modulus`.
+    fn posmod(self, modulus: real) -> Self;
+    /// Returns the vector with each component replaced by its positive remainder by the
+    /// matching component of `modulus`.
+    fn posmodv(self, modulus: Self) -> Self;
+    /// Returns the vector projected onto `onto`.
+    fn project(self, onto: Self) -> Self;
+    /// Returns the vector reflected from a plane defined by the given normal.
+    fn reflect_godot(self, normal: Self) -> Self;
+    /// Returns the vector rotated around `axis` by `angle`. `axis` must be normalized.
+    fn rotated(self, axis: Self, angle: Angle) -> Self;
+    /// Returns the vector with each component set to -1.0 if it is negative, and to 1.0
+    /// otherwise, like in the engine.
+    fn sign(self) -> Self;
+    /// Returns the component of the vector along a plane defined by the given normal.
+    fn slide(self, normal: Self) -> Self;
+    /// Returns the vector snapped to a grid with the given size.
+    fn snapped(self, by: Self) -> Self;
+    /// Returns a diagonal matrix with the vector as its main diagonal.
+    fn to_diagonal_matrix(self) -> Basis;
+}
+
+impl Vector3Godot for Vector3 {
+    #[inline]
+    fn angle_to(self, to: Self) -> Angle {
+        Angle::radians(self.cross(to).length().atan2(self.dot(to)))
+    }
+
+    #[inline]
+    fn bounce(self, normal: Self) -> Self {
+        -self.reflect_godot(normal)
+    }
+
+    #[inline]
+    fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: real) -> Self {
+        let v0 = pre_a;
+        let v1 = self;
+        let v2 = b;
+        let v3 = post_b;
+
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        ((v1 * 2.0)
+            + (-v0 + v2) * t
+            + (v0 * 2.0 - v1 * 5.0 + v2 * 4.0 - v3) * t2
+            + (-v0 + v1 * 3.0 - v2 * 3.0 + v3) * t3)
+            * 0.5
+    }
+
+    #[inline]
+    fn direction_to(self, to: Self) -> Self {
+        crate::geom::normalized(to - self)
+    }
+
+    #[inline]
+    fn distance_squared_to(self, to: Self) -> real {
+        (to - self).square_length()
+    }
+
+    #[inline]
+    fn distance_to(self, to: Self) -> real {
+        (to - self).length()
+    }
+
+    #[inline]
+    fn move_toward(self, to: Self, delta: real) -> Self {
+        let vd = to - self;
+        let length = vd.length();
+        if length <= delta || (length as f64) < CMP_EPSILON {
+            to
+        } else {
+            self + vd / length * delta
+        }
+    }
+
+    #[inline]
+    fn outer(self, b: Self) -> Basis {
+        Basis {
+            elements: [b * self.x, b * self.y, b * self.z],
+        }
+    }
+
+    #[inline]
+    fn posmod(self, modulus: real) -> Self {
+        Vector3::new(
+            fposmod(self.x, modulus),
+            fposmod(self.y, modulus),
+            fposmod(self.z, modulus),
+        )
+    }
+
+    #[inline]
+    fn posmodv(self, modulus: Self) -> Self {
+        Vector3::new(
+            fposmod(self.x, modulus.x),
+            fposmod(self.y, modulus.y),
+            fposmod(self.z, modulus.z),
+        )
+    }
+
+    #[inline]
+    fn project(self, onto: Self) -> Self {
+        onto * (self.dot(onto) / onto.square_length())
+    }
+
+    #[inline]
+    fn reflect_godot(self, normal: Self) -> Self {
+        normal * 2.0 * self.dot(normal) - self
+    }
+
+    #[inline]
+    fn rotated(self, axis: Self, angle: Angle) -> Self {
+        Basis::from_axis_angle(axis, angle.radians).xform(self)
+    }
+
+    #[inline]
+    fn sign(self) -> Self {
+        Vector3::new(sgn(self.x), sgn(self.y), sgn(self.z))
+    }
+
+    #[inline]
+    fn slide(self, normal: Self) -> Self {
+        self - normal * self.dot(normal)
+    }
+
+    #[inline]
+    fn snapped(self, by: Self) -> Self {
+        let snap = |v: real, by: real| {
+            if by != 0.0 {
+                (v / by + 0.5).floor() * by
+            } else {
+                v
+            }
+        };
+        Vector3::new(snap(self.x, by.x), snap(self.y, by.y), snap(self.z, by.z))
+    }
+
+    #[inline]
+    fn to_diagonal_matrix(self) -> Basis {
+        Basis::from_diagonal(self)
+    }
+}
+
 godot_test!(
     test_vector3_variants {
-        use crate::{FromVariant, ToVariant, Vector3};
+        use crate::{FromVariant, ToVariant};
 
         fn test(vector: Vector3, set_to: Vector3) {
             let api = crate::get_api();
@@ -54,7 +230,7 @@ godot_test!(
 
 #[cfg(test)]
 mod tests {
-    use crate::Vector3;
+    use crate::{Vector3, Vector3Godot};
 
     #[test]
     fn it_is_copy() {
@@ -77,4 +253,110 @@ mod tests {
     fn it_supports_inequality() {
         assert_ne!(Vector3::new(1.0, 10.0, 100.0), Vector3::new(1.0, 2.0, 3.0));
     }
+
+    #[test]
+    fn angle_to_is_sane() {
+        use crate::real_consts::FRAC_PI_2;
+        use Vector3 as V;
+
+        let angle = V::new(1.0, 0.0, 0.0).angle_to(V::new(0.0, 0.0, -3.0));
+        assert!((angle.radians - FRAC_PI_2).abs() < 0.0001);
+        assert_eq!(
+            0.0,
+            V::new(1.0, 2.0, 3.0)
+                .angle_to(V::new(2.0, 4.0, 6.0))
+                .radians
+        );
+    }
+
+    #[test]
+    fn reflect_bounce_slide_project_are_sane() {
+        use Vector3 as V;
+
+        let normal = V::new(0.0, 1.0, 0.0);
+        let v = V::new(1.0, 1.0, 2.0);
+        assert_eq!(V::new(-1.0, 1.0, -2.0), v.reflect_godot(normal));
+        assert_eq!(V::new(1.0, -1.0, 2.0), v.bounce(normal));
+        assert_eq!(V::new(1.0, -1.0, 2.0), v.reflect(normal));
+        assert_eq!(V::new(1.0, 0.0, 2.0), v.slide(normal));
+        assert_eq!(V::new(0.0, 1.0, 0.0), v.project(V::new(0.0, 5.0, 0.0)));
+    }
+
+    #[test]
+    fn rotated_is_sane() {
+        use euclid::approxeq::ApproxEq;
+        use Vector3 as V;
+
+        let rotated = V::new(1.0, 0.0, 0.0).rotated(
+            V::new(0.0, 0.0, 1.0),
+            crate::Angle::radians(crate::real_consts::FRAC_PI_2),
+        );
+        assert!(V::new(0.0, 1.0, 0.0).approx_eq(&rotated));
+    }
+
+    #[test]
+    fn cubic_interpolate_is_sane() {
+        use euclid::approxeq::ApproxEq;
+        use Vector3 as V;
+
+        let (pre_a, a, b, post_b) = (
+            V::new(-1.0, 0.0, 1.0),
+            V::new(0.0, 0.0, 0.0),
+            V::new(1.0, 2.0, 3.0),
+            V::new(2.0, 2.0, 2.0),
+        );
+        assert!(a.approx_eq(&a.cubic_interpolate(b, pre_a, post_b, 0.0)));
+        assert!(b.approx_eq(&a.cubic_interpolate(b, pre_a, post_b, 1.0)));
+        assert!(V::new(0.5, 1.0, 1.5).approx_eq(&a.cubic_interpolate(b, pre_a, post_b, 0.5)));
+    }
+
+    #[test]
+    fn distances_and_movement_are_sane() {
+        use euclid::approxeq::ApproxEq;
+        use Vector3 as V;
+
+        let (from, to) = (V::new(1.0, 1.0, 1.0), V::new(1.0, 4.0, 5.0));
+        assert_eq!(25.0, from.distance_squared_to(to));
+        assert_eq!(5.0, from.distance_to(to));
+        assert!(V::new(0.0, 0.6, 0.8).approx_eq(&from.direction_to(to)));
+        assert_eq!(V::new(0.0, 0.0, 0.0), from.direction_to(from));
+
+        assert!(V::new(1.0, 1.6, 1.8).approx_eq(&from.move_toward(to, 1.0)));
+        assert_eq!(to, from.move_toward(to, 6.0));
+    }
+
+    #[test]
+    fn matrices_are_sane() {
+        use crate::Basis;
+        use Vector3 as V;
+
+        let outer = V::new(1.0, 2.0, 3.0).outer(V::new(4.0, 5.0, 6.0));
+        assert_eq!(
+            Basis {
+                elements: [
+                    V::new(4.0, 5.0, 6.0),
+                    V::new(8.0, 10.0, 12.0),
+                    V::new(12.0, 15.0, 18.0),
+                ],
+            },
+            outer
+        );
+
+        let diagonal = V::new(1.0, 2.0, 3.0).to_diagonal_matrix();
+        assert_eq!(V::new(2.0, 4.0, 6.0), diagonal.xform(V::new(2.0, 2.0, 2.0)));
+    }
+
+    #[test]
+    fn componentwise_functions_are_sane() {
+        use Vector3 as V;
+
+        let v = V::new(-1.5, 2.5, 0.0);
+        assert_eq!(V::new(-1.0, 3.0, 0.0), v.ceil());
+        assert_eq!(V::new(-2.0, 2.0, 0.0), v.floor());
+        assert_eq!(V::new(-2.0, 3.0, 0.0), v.round());
+        assert_eq!(V::new(-1.0, 1.0, 1.0), v.sign());
+        assert_eq!(V::new(0.5, 0.5, 0.0), v.posmod(2.0));
+        assert_eq!(V::new(1.5, 0.5, 0.0), v.posmodv(V::new(3.0, 1.0, 1.0)));
+        assert_eq!(V::new(-2.0, 3.0, 0.0), v.snapped(V::new(2.0, 1.0, 0.0)));
+    }
 }
@@ -0,0 +1,30 @@
+use syn::{Attribute, Lit, Meta, MetaNameValue};
+
+/// Collects the `///` doc comments in `attrs` into documentation for the editor.
+///
+/// The leading space of each line is removed. Returns `None` if there are no doc comments.
+pub(crate) fn extract_doc(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(lit_str),
+                ..
+            })) => Some(lit_str.value()),
+            _ => None,
+        })
+        .map(|line| match line.strip_prefix(' ') {
+            Some(line) => line.to_string(),
+            None => line,
+        })
+        .collect::<Vec<_>>();
+
+    let doc = lines.join("\n");
+    let doc = doc.trim();
+    if doc.is_empty() {
+        None
+    } else {
+        Some(doc.to_string())
+    }
+}
@@ -7,6 +7,7 @@ extern crate quote;
 
 use proc_macro::TokenStream;
 
+mod doc;
 mod methods;
 mod native_script;
 mod variant;
@@ -18,7 +19,7 @@ pub fn methods(meta: TokenStream, input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(
     NativeClass,
-    attributes(inherit, export, opt, user_data, property, register_with)
+    attributes(inherit, export, opt, user_data, property, register_with, signal)
 )]
 pub fn derive_native_class(input: TokenStream) -> TokenStream {
     native_script::derive_native_class(input)
@@ -4,6 +4,9 @@ use proc_macro::TokenStream;
 use std::boxed::Box;
 use syn::export::Span;
 
+/// Prefix of the names given to wildcard arguments of exported methods.
+const UNUSED_ARG: &str = "___unused_arg_";
+
 pub(crate) struct ClassMethodExport {
     pub(crate) class_ty: Box<Type>,
     pub(crate) methods: Vec<ExportMethod>,
@@ -13,11 +16,15 @@ pub(crate) struct ClassMethodExport {
 pub(crate) struct ExportMethod {
     pub(crate) sig: Signature,
     pub(crate) args: ExportArgs,
+    /// Doc comments of the method, forwarded to the editor.
+    pub(crate) doc: Option<String>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
 pub(crate) struct ExportArgs {
     pub(crate) optional_args: Option<usize>,
+    /// Name of the `RpcMode` variant, if set with `#[export(rpc = "...")]`.
+    pub(crate) rpc_mode: Option<String>,
 }
 
 pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStream {
@@ -29,9 +36,10 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
         let methods = export
             .methods
             .into_iter()
-            .map(|ExportMethod { sig, args }| {
+            .map(|ExportMethod { sig, args, doc }| {
                 let name = sig.ident;
                 let name_string = name.to_string();
+                let rpc_mode = args.rpc_mode;
                 let ret_ty = match sig.output {
                     syn::ReturnType::Default => quote!(()),
                     syn::ReturnType::Type(_, ty) => quote!( #ty ),
@@ -57,6 +65,31 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
                     None => 0,
                 };
 
+                // names and types of the arguments after self and owner, for the editor
+                let arg_info = sig.inputs.iter().skip(2).enumerate().map(|(n, arg)| {
+                    let (pat, ty) = match arg {
+                        FnArg::Typed(cap) => (&cap.pat, &cap.ty),
+                        FnArg::Receiver(_) => panic!("only the first argument can be self"),
+                    };
+                    let arg_name = match &**pat {
+                        Pat::Ident(ident) if !ident.ident.to_string().starts_with(UNUSED_ARG) => {
+                            ident.ident.to_string()
+                        }
+                        _ => format!("arg{}", n),
+                    };
+
+                    quote!(gdnative::init::MethodArgument {
+                        name: #arg_name,
+                        export_info: (&gdnative::init::ArgumentType::<#ty>(
+                            ::std::marker::PhantomData
+                        ))
+                            .argument_export_info(),
+                    })
+                });
+
+                let set_doc =
+                    doc.map(|doc| quote!(builder.set_method_documentation(#name_string, #doc);));
+
                 let args = sig.inputs.iter().enumerate().map(|(n, arg)| {
                     if n < arg_count - optional_args {
                         quote!(#arg ,)
@@ -65,6 +98,18 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
                     }
                 });
 
+                let add_method = match rpc_mode {
+                    Some(rpc_mode) => {
+                        let rpc_mode = syn::Ident::new(&rpc_mode, Span::call_site());
+                        quote!(builder.add_method_with_rpc_mode(
+                            #name_string,
+                            method,
+                            gdnative::init::RpcMode::#rpc_mode,
+                        );)
+                    }
+                    None => quote!(builder.add_method(#name_string, method);),
+                };
+
                 quote!(
                     {
                         let method = gdnative::godot_wrap_method!(
@@ -72,7 +117,13 @@ pub(crate) fn derive_methods(meta: TokenStream, input: TokenStream) -> TokenStre
                             fn #name ( #( #args )* ) -> #ret_ty
                         );
 
-                        builder.add_method(#name_string, method);
+                        #add_method
+
+                        builder.set_method_argument_information(
+                            #name_string,
+                            &[#(#arg_info),*],
+                        );
+                        #set_doc
                     }
                 )
             })
@@ -154,7 +205,7 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
                             .map(|i| i.ident.to_string());
 
                         if let Some("export") = last_seg.as_ref().map(String::as_str) {
-                            let _export_args = export_args.get_or_insert_with(ExportArgs::default);
+                            let export_args = export_args.get_or_insert_with(ExportArgs::default);
                             if !attr.tokens.is_empty() {
                                 use quote::ToTokens;
                                 use syn::{Meta, MetaNameValue, NestedMeta};
@@ -181,10 +232,33 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
                                     ),
                                 };
 
-                                for MetaNameValue { path, .. } in pairs.into_iter() {
+                                for MetaNameValue { path, lit, .. } in pairs.into_iter() {
                                     let last =
                                         path.segments.last().expect("the path should not be empty");
                                     match last.ident.to_string().as_str() {
+                                        "rpc" => {
+                                            let value = match lit {
+                                                syn::Lit::Str(lit_str) => lit_str.value(),
+                                                _ => panic!("rpc mode is not a string literal"),
+                                            };
+                                            let rpc_mode = match value.as_str() {
+                                                "disabled" => "Disabled",
+                                                "remote" => "Remote",
+                                                "master" => "Master",
+                                                "puppet" => "Puppet",
+                                                "remotesync" => "RemoteSync",
+                                                "mastersync" => "MasterSync",
+                                                "puppetsync" => "PuppetSync",
+                                                unexpected => {
+                                                    panic!("unknown rpc mode: `{}`", unexpected)
+                                                }
+                                            };
+                                            if let Some(old) =
+                                                export_args.rpc_mode.replace(rpc_mode.to_string())
+                                            {
+                                                panic!("there is already an rpc mode set: {}", old);
+                                            }
+                                        }
                                         unexpected => {
                                             panic!("unknown option for export: `{}`", unexpected)
                                         }
@@ -237,6 +311,7 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
                     methods_to_export.push(ExportMethod {
                         sig: method.sig.clone(),
                         args: export_args,
+                        doc: crate::doc::extract_doc(&method.attrs),
                     });
                 }
 
@@ -277,7 +352,7 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
                 .for_each(|(i, arg)| match arg {
                     FnArg::Typed(cap) => match *cap.pat.clone() {
                         Pat::Wild(_) => {
-                            let name = format!("___unused_arg_{}", i);
+                            let name = format!("{}{}", UNUSED_ARG, i);
 
                             cap.pat = Box::new(Pat::Ident(PatIdent {
                                 attrs: vec![],
@@ -1,21 +1,67 @@
 use proc_macro::TokenStream;
-use std::collections::HashMap;
-use syn::{Data, DeriveInput, Fields, Ident, Meta, MetaList, NestedMeta, Path, Type};
+use std::collections::{HashMap, HashSet};
+use syn::punctuated::Punctuated;
+use syn::{Data, DeriveInput, Fields, Ident, Meta, MetaList, NestedMeta, Path, Type, Visibility};
 
 mod property_args;
+mod signal_args;
 use property_args::{PropertyAttrArgs, PropertyAttrArgsBuilder};
+use signal_args::SignalDecl;
 
 pub(crate) struct DeriveData {
     pub(crate) name: Ident,
+    pub(crate) vis: Visibility,
+    pub(crate) doc: Option<String>,
     pub(crate) base: Type,
     pub(crate) register_callback: Option<Path>,
     pub(crate) user_data: Type,
     pub(crate) properties: HashMap<Ident, PropertyAttrArgs>,
+    pub(crate) signals: Vec<SignalDecl>,
 }
 
 pub(crate) fn derive_native_class(input: TokenStream) -> TokenStream {
     let data = parse_derive_input(input);
 
+    // generate the typed emit helpers of the declared signals
+    let emit_helpers = {
+        let name = &data.name;
+        let base = &data.base;
+        let vis = &data.vis;
+        let helpers = data.signals.iter().map(|signal| {
+            let signal_name = signal.name.to_string();
+            let helper = format_ident!("emit_{}", signal.name);
+            let signal_doc = match &signal.doc {
+                Some(doc) => format!("{}\n\n", doc),
+                None => String::new(),
+            };
+            let doc = format!(
+                "Emits the `{}` signal on `owner`.\n\n{}\
+                 # Safety\n\n\
+                 `owner` must be a valid instance of the base class.",
+                signal_name, signal_doc
+            );
+            let arg_names = signal.args.iter().map(|arg| &arg.name).collect::<Vec<_>>();
+            let arg_types = signal.args.iter().map(|arg| &arg.ty);
+
+            quote!(
+                #[doc = #doc]
+                #[allow(dead_code)]
+                #vis unsafe fn #helper(&self, mut owner: #base, #(#arg_names: #arg_types),*) {
+                    owner.emit_signal(
+                        gdnative::CachedName::get(#signal_name).into(),
+                        &[#(gdnative::ToVariant::to_variant(&#arg_names)),*],
+                    );
+                }
+            )
+        });
+
+        quote!(
+            impl #name {
+                #(#helpers)*
+            }
+        )
+    };
+
     // generate NativeClass impl
     let trait_impl = {
         let name = data.name;
@@ -33,15 +79,58 @@ pub(crate) fn derive_native_class(input: TokenStream) -> TokenStream {
             };
 
             let label = config.path.unwrap_or_else(|| format!("{}", ident));
+            let set_doc = config
+                .doc
+                .map(|doc| quote!(builder.set_property_documentation(#label, #doc);));
             quote!({
                 builder.add_property(#label)
                     #with_default
                     .with_ref_getter(|this: &#name, _| &this.#ident)
                     .with_setter(|this: &mut #name, _, v| this.#ident = v)
                     .done();
+                #set_doc
+            })
+        });
+
+        let signals = data.signals.iter().map(|signal| {
+            let signal_name = signal.name.to_string();
+            let args = signal.args.iter().map(|arg| {
+                let arg_name = arg.name.to_string();
+                let ty = &arg.ty;
+                let default = match &arg.default {
+                    Some(default) => quote!({
+                        let default: #ty = #default;
+                        gdnative::ToVariant::to_variant(&default)
+                    }),
+                    None => quote!(gdnative::Variant::new()),
+                };
+
+                quote!(gdnative::init::SignalArgument {
+                    name: #arg_name,
+                    default: #default,
+                    export_info: <#ty as gdnative::init::Export>::export_info(None),
+                    usage: gdnative::init::PropertyUsage::DEFAULT,
+                })
+            });
+
+            let set_doc = signal
+                .doc
+                .as_ref()
+                .map(|doc| quote!(builder.set_signal_documentation(#signal_name, #doc);));
+
+            quote!({
+                builder.add_signal(gdnative::init::Signal {
+                    name: #signal_name,
+                    args: &[#(#args),*],
+                });
+                #set_doc
             })
         });
 
+        let set_doc = data
+            .doc
+            .map(|doc| quote!(builder.set_class_documentation(#doc);));
+
         // string variant needed for the `class_name` function.
         let name_str = quote!(#name).to_string();
 
@@ -59,7 +148,9 @@ pub(crate) fn derive_native_class(input: TokenStream) -> TokenStream {
                 }
 
                 fn register_properties(builder: &gdnative::init::ClassBuilder<Self>) {
+                    #set_doc
                     #(#properties)*;
+                    #(#signals)*;
                     #register_callback
                 }
             }
@@ -67,7 +158,11 @@ pub(crate) fn derive_native_class(input: TokenStream) -> TokenStream {
     };
 
     // create output token stream
-    trait_impl.into()
+    quote!(
+        #trait_impl
+        #emit_helpers
+    )
+    .into()
 }
 
 fn parse_derive_input(input: TokenStream) -> DeriveData {
@@ -79,6 +174,8 @@ fn parse_derive_input(input: TokenStream) -> DeriveData {
     };
 
     let ident = input.ident;
+    let vis = input.vis;
+    let doc = crate::doc::extract_doc(&input.attrs);
 
     let inherit_attr = input
         .attrs
@@ -113,6 +210,24 @@ fn parse_derive_input(input: TokenStream) -> DeriveData {
                 .expect("quoted tokens should be a valid type")
         });
 
+    // read declared signals, e.g. `#[signal(tick, tick_with_data(data: i64 = 100))]`
+    let signals = input
+        .attrs
+        .iter()
+        .filter(|a| a.path.is_ident("signal"))
+        .flat_map(|attr| {
+            attr.parse_args_with(Punctuated::<SignalDecl, Token![,]>::parse_terminated)
+                .unwrap_or_else(|err| panic!("`signal` attribute is malformed: {}", err))
+        })
+        .collect::<Vec<_>>();
+
+    let mut signal_names = HashSet::new();
+    for signal in signals.iter() {
+        if !signal_names.insert(signal.name.to_string()) {
+            panic!("signal `{}` is declared more than once", signal.name);
+        }
+    }
+
     // make sure it's a struct
     let struct_data = if let Data::Struct(data) = input.data {
         data
@@ -157,7 +272,7 @@ fn parse_derive_input(input: TokenStream) -> DeriveData {
 
                 property_args.map(|builder| {
                     let ident = field.ident.clone().expect("fields should be named");
-                    (ident, builder.done())
+                    (ident, builder.done(crate::doc::extract_doc(&field.attrs)))
                 })
             })
             .collect::<HashMap<_, _>>()
@@ -167,9 +282,12 @@ fn parse_derive_input(input: TokenStream) -> DeriveData {
 
     DeriveData {
         name: ident,
+        vis,
+        doc,
         base,
         register_callback,
         user_data,
         properties,
+        signals,
     }
 }
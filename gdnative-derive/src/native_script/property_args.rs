@@ -1,6 +1,8 @@
 pub struct PropertyAttrArgs {
     pub path: Option<String>,
     pub default: Option<syn::Lit>,
+    /// Doc comments of the field, forwarded to the editor.
+    pub doc: Option<String>,
 }
 
 #[derive(Default)]
@@ -44,10 +46,11 @@ impl<'a> Extend<&'a syn::MetaNameValue> for PropertyAttrArgsBuilder {
 }
 
 impl PropertyAttrArgsBuilder {
-    pub fn done(self) -> PropertyAttrArgs {
+    pub fn done(self, doc: Option<String>) -> PropertyAttrArgs {
         PropertyAttrArgs {
             path: self.path,
             default: self.default,
+            doc,
         }
     }
 }
@@ -0,0 +1,62 @@
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, Ident, Type};
+
+/// A signal declared with `#[signal(name(arg: Type = default, ...))]`. It can be preceded
+/// by doc comments.
+pub struct SignalDecl {
+    pub name: Ident,
+    pub args: Vec<SignalArgDecl>,
+    pub doc: Option<String>,
+}
+
+/// An argument of a declared signal. The default value is optional.
+pub struct SignalArgDecl {
+    pub name: Ident,
+    pub ty: Type,
+    pub default: Option<Expr>,
+}
+
+impl Parse for SignalDecl {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        if let Some(attr) = attrs.iter().find(|attr| !attr.path.is_ident("doc")) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "only doc comments are allowed on signals",
+            ));
+        }
+        let doc = crate::doc::extract_doc(&attrs);
+
+        let name = input.parse()?;
+
+        let args = if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Punctuated::<SignalArgDecl, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(SignalDecl { name, args, doc })
+    }
+}
+
+impl Parse for SignalArgDecl {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+
+        let default = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(SignalArgDecl { name, ty, default })
+    }
+}
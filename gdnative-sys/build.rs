@@ -55,6 +55,39 @@ mod header_binding {
         false
     }
 
+    /// Returns C definitions of the opaque types whose sizes depend on `real_t`, for engine
+    /// builds with `real_t=double`.
+    fn double_precision_types() -> String {
+        // `Variant` stores a type tag, padded to 8 bytes, followed by up to four `real_t`s.
+        // Larger values are stored behind a pointer, so its size doesn't depend on the target.
+        let types = [
+            ("vector2", 16),
+            ("vector3", 24),
+            ("basis", 72),
+            ("quat", 32),
+            ("plane", 32),
+            ("rect2", 32),
+            ("aabb", 48),
+            ("transform", 96),
+            ("transform2d", 48),
+            ("variant", 40),
+        ];
+
+        let mut header = String::from("#include <stdint.h>\n");
+        for (name, size) in types.iter() {
+            header.push_str(&format!(
+                "#define GODOT_CORE_API_GODOT_{upper}_TYPE_DEFINED\n\
+                 typedef struct godot_{name} {{\n\
+                 \tuint8_t _dont_touch_that[{size}];\n\
+                 }} godot_{name};\n",
+                upper = name.to_uppercase(),
+                name = name,
+                size = size,
+            ));
+        }
+        header
+    }
+
     pub(crate) fn generate(manifest_dir: &str, out_dir: &str) {
         // on mac/iOS this will be modified, so it is marked as mutable.
         // on all other targets, this `mut` will be unused and the complainer compiles.t s
@@ -70,6 +103,22 @@ mod header_binding {
             .ctypes_prefix("libc")
             .clang_arg(format!("-I{}/godot_headers", manifest_dir));
 
+        // Custom engine builds with `real_t=double` use 64-bit floats for `godot_real` and
+        // the geometry types, which changes their sizes. The headers only describe the
+        // default build, but skip their definitions of these types if they are already
+        // defined, so the double-precision ones are included before them.
+        if std::env::var("CARGO_FEATURE_DOUBLE_PRECISION").is_ok() {
+            let types_path = PathBuf::from(out_dir).join("double_precision_types.h");
+            std::fs::write(&types_path, double_precision_types())
+                .expect("Unable to write double-precision type definitions");
+
+            builder = builder
+                .clang_arg("-include")
+                .clang_arg(types_path.to_string_lossy())
+                .blacklist_type("godot_real")
+                .raw_line("pub type godot_real = f64;");
+        }
+
         let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap();
         let target_arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap();
 
@@ -301,6 +350,7 @@ mod api_wrapper {
             .expect(&"File ({:?}) does not contain expected JSON");
         let struct_fields = godot_api_functions(&api_root);
         let impl_constructor = api_constructor(&api_root);
+        let impl_unimplemented = api_unimplemented(&api_root);
         let wrapper = quote! {
             pub struct GodotApi{
                 #struct_fields
@@ -308,6 +358,7 @@ mod api_wrapper {
             impl GodotApi {
                 #impl_constructor
             }
+            #impl_unimplemented
         };
         let mut wrapper_file = File::create(to.join(file_name)).expect(&format!(
             "Couldn't create output file: {:?}",
@@ -370,6 +421,45 @@ mod api_wrapper {
         }
     }
 
+    fn api_unimplemented(api: &ApiRoot) -> TokenStream {
+        let mut stub_functions = TokenStream::new();
+        let mut constructed_struct_fields = TokenStream::new();
+        for api in api.all_apis() {
+            for function in &api.functions {
+                let function_name = function.rust_name();
+                let name = function.name.as_str();
+                let arg_types = function.arguments.iter().map(Argument::rust_type);
+                let return_type = function.rust_return_type();
+                stub_functions.extend(quote! {
+                    pub unsafe extern "C" fn #function_name(#(_: #arg_types),*) -> #return_type {
+                        unimplemented_api_function(#name)
+                    }
+                });
+                constructed_struct_fields.extend(quote! {
+                    #function_name: unimplemented_api::#function_name,
+                });
+            }
+        }
+        quote! {
+            #[cfg(feature = "mock")]
+            mod unimplemented_api {
+                use super::*;
+                #stub_functions
+            }
+            #[cfg(feature = "mock")]
+            impl GodotApi {
+                /// Returns an API table where every function aborts with a message naming it.
+                ///
+                /// Used as a starting point for API tables implemented outside of the engine.
+                pub fn unimplemented() -> Self {
+                    GodotApi {
+                        #constructed_struct_fields
+                    }
+                }
+            }
+        }
+    }
+
     fn parse_c_type(mut c_type: &str) -> (bool, i8, &str) {
         c_type = c_type.trim();
         let is_const = c_type.starts_with("const ");
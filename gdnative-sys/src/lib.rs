@@ -19,6 +19,14 @@ pub enum InitError {
     },
 }
 
+/// Called by the stubs of `GodotApi::unimplemented`. Panics can't unwind out of the
+/// `extern "C"` stubs, so this aborts the process instead.
+#[cfg(feature = "mock")]
+fn unimplemented_api_function(name: &str) -> ! {
+    eprintln!("API function not implemented: {}", name);
+    std::process::abort()
+}
+
 fn map_option_to_init_error<T>(t: Option<T>, message: &'static str) -> Result<T, InitError> {
     match t {
         Some(t) => Ok(t),
@@ -1,5 +1,7 @@
 use gdnative::*;
 
+mod test_async;
+mod test_closure;
 mod test_derive;
 mod test_free_ub;
 mod test_register;
@@ -13,25 +15,37 @@ pub extern "C" fn run_tests(
 ) -> gdnative::sys::godot_variant {
     let mut status = true;
     status &= gdnative::test_string();
+    status &= gdnative::test_string_functions();
+    status &= gdnative::test_name_cache();
+    status &= gdnative::test_node_path();
 
     status &= gdnative::test_dictionary();
+    status &= gdnative::test_dictionary_iter_entry();
+    status &= gdnative::test_typed_dictionary();
     // status &= gdnative::test_dictionary_clone_clear();
 
     status &= gdnative::test_array();
+    status &= gdnative::test_array_functions();
+    status &= gdnative::test_typed_array();
     // status &= gdnative::test_array_clone_clear();
 
     status &= gdnative::test_variant_nil();
     status &= gdnative::test_variant_i64();
     status &= gdnative::test_variant_bool();
+    status &= gdnative::test_variant_operators();
 
     status &= gdnative::test_vector2_variants();
 
     status &= gdnative::test_vector3_variants();
 
+    status &= gdnative::test_transform_variants();
+    status &= gdnative::test_transform2d_variants();
+
     status &= gdnative::test_variant_option();
     status &= gdnative::test_variant_result();
     status &= gdnative::test_to_variant_iter();
     status &= gdnative::test_variant_tuple();
+    status &= gdnative::test_variant_value_roundtrip();
 
     status &= gdnative::test_byte_array_access();
     status &= gdnative::test_int32_array_access();
@@ -40,11 +54,17 @@ pub extern "C" fn run_tests(
     status &= gdnative::test_string_array_access();
     status &= gdnative::test_vector2_array_access();
     status &= gdnative::test_vector3_array_access();
+    status &= gdnative::test_pool_array_api();
+    status &= gdnative::test_pool_array_slices();
+
+    status &= gdnative::marshal::test_marshal_variant();
 
     status &= test_constructor();
     status &= test_underscore_method_binding();
     status &= test_rust_class_construction();
 
+    status &= test_async::run_tests();
+    status &= test_closure::run_tests();
     status &= test_derive::run_tests();
     status &= test_free_ub::run_tests();
     status &= test_register::run_tests();
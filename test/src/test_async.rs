@@ -0,0 +1,225 @@
+use gdnative::r#async::{next_frame, spawn, spawn_for, tick_now, SignalFutureExt};
+use gdnative::*;
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_signal_future();
+    status &= test_executor();
+    status &= test_executor_owner();
+    status &= test_executor_panic();
+
+    status
+}
+
+struct Flag(AtomicBool);
+
+/// Sets the flag when the task holding it is dropped.
+struct DropFlag(Rc<Cell<bool>>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.set(true);
+    }
+}
+
+impl Wake for Flag {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+fn test_signal_future() -> bool {
+    println!(" -- test_signal_future");
+
+    let ok = std::panic::catch_unwind(|| unsafe {
+        let flag = Arc::new(Flag(AtomicBool::new(false)));
+        let waker = Waker::from(flag.clone());
+        let mut context = Context::from_waker(&waker);
+
+        let mut emitter = Reference::new();
+        emitter.add_user_signal("fired".into(), VariantArray::new());
+
+        let mut future = emitter
+            .signal_future("fired")
+            .expect("should be able to connect");
+        assert_eq!(Poll::Pending, Pin::new(&mut future).poll(&mut context));
+        assert!(!flag.0.load(Ordering::SeqCst));
+
+        emitter.emit_signal("fired".into(), &[Variant::from_i64(42)]);
+        assert!(flag.0.load(Ordering::SeqCst));
+        match Pin::new(&mut future).poll(&mut context) {
+            Poll::Ready(args) => {
+                assert_eq!(1, args.len());
+                assert_eq!(Some(42), args[0].try_to_i64());
+            }
+            Poll::Pending => panic!("the signal future should be ready"),
+        }
+
+        // Signals emitted before the first poll are not missed.
+        let mut future = emitter
+            .signal_future("fired")
+            .expect("should be able to connect");
+        emitter.emit_signal("fired".into(), &[Variant::from_i64(54)]);
+        assert!(Pin::new(&mut future).poll(&mut context).is_ready());
+
+        assert!(emitter.signal_future("missing").is_err());
+
+        // Without the executor ticking, the next frame never comes.
+        let mut frame = next_frame();
+        assert_eq!(Poll::Pending, Pin::new(&mut frame).poll(&mut context));
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_signal_future failed");
+    }
+
+    ok
+}
+
+fn test_executor() -> bool {
+    println!(" -- test_executor");
+
+    let ok = std::panic::catch_unwind(|| unsafe {
+        let progress = Rc::new(Cell::new(0));
+        let dropped = Rc::new(Cell::new(false));
+        {
+            let progress = progress.clone();
+            let guard = DropFlag(dropped.clone());
+            spawn(async move {
+                let _guard = guard;
+                progress.set(1);
+                next_frame().await;
+                progress.set(2);
+            });
+        }
+
+        // Tasks are first polled on the next frame, and resume one frame later.
+        assert_eq!(0, progress.get());
+        tick_now();
+        assert_eq!(1, progress.get());
+        assert!(!dropped.get());
+        tick_now();
+        assert_eq!(2, progress.get());
+        assert!(dropped.get());
+
+        let mut emitter = Reference::new();
+        emitter.add_user_signal("fired".into(), VariantArray::new());
+        let fired = emitter
+            .signal_future("fired")
+            .expect("should be able to connect");
+
+        let received = Rc::new(Cell::new(None));
+        {
+            let received = received.clone();
+            spawn(async move {
+                let args = fired.await;
+                received.set(args[0].try_to_i64());
+            });
+        }
+
+        tick_now();
+        tick_now();
+        assert_eq!(None, received.get());
+
+        emitter.emit_signal("fired".into(), &[Variant::from_i64(42)]);
+        assert_eq!(None, received.get());
+        tick_now();
+        assert_eq!(Some(42), received.get());
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_executor failed");
+    }
+
+    ok
+}
+
+fn test_executor_owner() -> bool {
+    println!(" -- test_executor_owner");
+
+    let ok = std::panic::catch_unwind(|| unsafe {
+        let owner = Node::new();
+        let polls = Rc::new(Cell::new(0));
+        let dropped = Rc::new(Cell::new(false));
+        {
+            let polls = polls.clone();
+            let guard = DropFlag(dropped.clone());
+            spawn_for(&owner, async move {
+                let _guard = guard;
+                loop {
+                    polls.set(polls.get() + 1);
+                    next_frame().await;
+                }
+            });
+        }
+
+        tick_now();
+        tick_now();
+        assert_eq!(2, polls.get());
+        assert!(!dropped.get());
+
+        // The task is dropped without being polled again once its owner is freed.
+        owner.free();
+        tick_now();
+        assert_eq!(2, polls.get());
+        assert!(dropped.get());
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_executor_owner failed");
+    }
+
+    ok
+}
+
+fn test_executor_panic() -> bool {
+    println!(" -- test_executor_panic");
+
+    let ok = std::panic::catch_unwind(|| {
+        let polls = Rc::new(Cell::new(0));
+        let dropped = Rc::new(Cell::new(false));
+        {
+            let polls = polls.clone();
+            let guard = DropFlag(dropped.clone());
+            spawn(async move {
+                let _guard = guard;
+                polls.set(polls.get() + 1);
+                next_frame().await;
+                panic!("this panic is expected");
+            });
+        }
+
+        tick_now();
+        assert_eq!(1, polls.get());
+
+        // The panic is caught by the executor, and the task is dropped.
+        tick_now();
+        assert!(dropped.get());
+
+        let survivor = Rc::new(Cell::new(false));
+        {
+            let survivor = survivor.clone();
+            spawn(async move { survivor.set(true) });
+        }
+        tick_now();
+        assert!(survivor.get());
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_executor_panic failed");
+    }
+
+    ok
+}
@@ -0,0 +1,60 @@
+use gdnative::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_connect_closure();
+
+    status
+}
+
+fn test_connect_closure() -> bool {
+    println!(" -- test_connect_closure");
+
+    let ok = std::panic::catch_unwind(|| unsafe {
+        let mut emitter = Reference::new();
+        emitter.add_user_signal("fired".into(), VariantArray::new());
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let connection = {
+            let received = received.clone();
+            connect_closure(&mut emitter, "fired", move |args| {
+                received.borrow_mut().push(args[0].try_to_i64().unwrap());
+            })
+            .expect("should be able to connect")
+        };
+
+        let oneshot_calls = Rc::new(Cell::new(0));
+        let oneshot = {
+            let oneshot_calls = oneshot_calls.clone();
+            connect_closure_oneshot(&mut emitter, "fired", move |_| {
+                oneshot_calls.set(oneshot_calls.get() + 1);
+            })
+            .expect("should be able to connect")
+        };
+
+        assert!(connection.is_connected());
+        assert!(oneshot.is_connected());
+
+        emitter.emit_signal("fired".into(), &[Variant::from_i64(1)]);
+        emitter.emit_signal("fired".into(), &[Variant::from_i64(2)]);
+        assert_eq!(vec![1, 2], *received.borrow());
+        assert_eq!(1, oneshot_calls.get());
+        assert!(!oneshot.is_connected());
+
+        drop(connection);
+        emitter.emit_signal("fired".into(), &[Variant::from_i64(3)]);
+        assert_eq!(vec![1, 2], *received.borrow());
+
+        assert!(connect_closure(&mut emitter, "missing", |_| {}).is_err());
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_connect_closure failed");
+    }
+
+    ok
+}
@@ -4,6 +4,9 @@ pub(crate) fn run_tests() -> bool {
     let mut status = true;
 
     status &= test_register_property();
+    status &= test_derive_signal();
+    status &= test_register_rpc();
+    status &= test_register_docs();
 
     status
 }
@@ -11,6 +14,10 @@ pub(crate) fn run_tests() -> bool {
 pub(crate) fn register(handle: &init::InitHandle) {
     handle.add_class::<RegisterSignal>();
     handle.add_class::<RegisterProperty>();
+    handle.add_class::<DeriveSignal>();
+    handle.add_class::<SignalReceiver>();
+    handle.add_class::<RegisterRpc>();
+    handle.add_class::<RegisterDocs>();
 }
 
 struct RegisterSignal;
@@ -104,3 +111,257 @@ fn test_register_property() -> bool {
 
     ok
 }
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+#[signal(started, progress(amount: i64 = 10, message: GodotString))]
+struct DeriveSignal;
+
+impl DeriveSignal {
+    fn _init(_owner: Reference) -> Self {
+        DeriveSignal
+    }
+}
+
+#[methods]
+impl DeriveSignal {}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct SignalReceiver {
+    started: i64,
+    progress: Option<(i64, String)>,
+}
+
+impl SignalReceiver {
+    fn _init(_owner: Reference) -> Self {
+        SignalReceiver {
+            started: 0,
+            progress: None,
+        }
+    }
+}
+
+#[methods]
+impl SignalReceiver {
+    #[export]
+    fn on_started(&mut self, _owner: Reference) {
+        self.started += 1;
+    }
+
+    #[export]
+    fn on_progress(&mut self, _owner: Reference, amount: i64, message: GodotString) {
+        self.progress = Some((amount, message.to_string()));
+    }
+}
+
+fn test_derive_signal() -> bool {
+    println!(" -- test_derive_signal");
+
+    let ok = std::panic::catch_unwind(|| {
+        let emitter = Instance::<DeriveSignal>::new();
+        let receiver = Instance::<SignalReceiver>::new();
+
+        unsafe {
+            let mut base = emitter.base().clone();
+            let target = receiver.base().to_object();
+            for (signal, method) in &[("started", "on_started"), ("progress", "on_progress")] {
+                base.connect(
+                    (*signal).into(),
+                    Some(target),
+                    (*method).into(),
+                    VariantArray::new(),
+                    0,
+                )
+                .expect("derived signals should be registered");
+            }
+
+            emitter
+                .map(|emitter, owner| {
+                    emitter.emit_started(owner.clone());
+                    emitter.emit_progress(owner, 42, "halfway".into());
+                })
+                .unwrap();
+        }
+
+        receiver
+            .map(|receiver, _| {
+                assert_eq!(1, receiver.started);
+                assert_eq!(Some((42, "halfway".to_string())), receiver.progress);
+            })
+            .unwrap();
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_derive_signal failed");
+    }
+
+    ok
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+#[register_with(Self::register_properties)]
+struct RegisterRpc {
+    health: i64,
+}
+
+impl RegisterRpc {
+    fn _init(_owner: Node) -> Self {
+        RegisterRpc { health: 100 }
+    }
+
+    fn register_properties(builder: &init::ClassBuilder<Self>) {
+        builder
+            .add_property("health")
+            .with_default(100)
+            .with_rset_mode(init::RpcMode::Puppet)
+            .with_getter(|this: &RegisterRpc, _| this.health)
+            .with_setter(|this: &mut RegisterRpc, _, value| this.health = value)
+            .done();
+    }
+}
+
+#[methods]
+impl RegisterRpc {
+    #[export(rpc = "remotesync")]
+    fn take_damage(&mut self, _owner: Node, amount: i64) -> i64 {
+        self.health -= amount;
+        self.health
+    }
+
+    #[export(rpc = "master")]
+    fn health(&self, _owner: Node) -> i64 {
+        self.health
+    }
+}
+
+fn test_register_rpc() -> bool {
+    println!(" -- test_register_rpc");
+
+    let ok = std::panic::catch_unwind(|| {
+        let obj = Instance::<RegisterRpc>::new();
+        let mut base = obj.into_base();
+
+        unsafe {
+            assert_eq!(
+                Some(90),
+                base.call("take_damage".into(), &[10.to_variant()])
+                    .try_to_i64()
+            );
+            assert_eq!(Some(90), base.call("health".into(), &[]).try_to_i64());
+
+            base.set("health".into(), 54.to_variant());
+            assert_eq!(Some(54), base.get("health".into()).try_to_i64());
+
+            base.free();
+        }
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_register_rpc failed");
+    }
+
+    ok
+}
+
+/// A counter with documentation.
+///
+/// Shown in the help of the editor.
+#[derive(NativeClass)]
+#[inherit(Reference)]
+#[signal(
+    /// Emitted when the value changes.
+    changed(value: i64)
+)]
+struct RegisterDocs {
+    /// The current value.
+    #[property]
+    value: i64,
+}
+
+#[methods]
+impl RegisterDocs {
+    fn _init(_owner: Reference) -> Self {
+        RegisterDocs { value: 0 }
+    }
+
+    /// Adds `amount` to the value.
+    #[export]
+    fn add(&mut self, _owner: Reference, amount: i64, _: Variant, _tag: Variant) -> i64 {
+        self.value += amount;
+        self.value
+    }
+}
+
+fn test_register_docs() -> bool {
+    println!(" -- test_register_docs");
+
+    let ok = std::panic::catch_unwind(|| {
+        let obj = Instance::<RegisterDocs>::new();
+        let base = obj.into_base();
+
+        unsafe {
+            let script = base
+                .get_script()
+                .and_then(|script| script.cast::<NativeScript>())
+                .expect("the instance should have a NativeScript");
+
+            assert_eq!(
+                GodotString::from_str(
+                    "A counter with documentation.\n\nShown in the help of the editor."
+                ),
+                script.get_class_documentation()
+            );
+            assert_eq!(
+                GodotString::from_str("Adds `amount` to the value."),
+                script.get_method_documentation("add".into())
+            );
+            assert_eq!(
+                GodotString::from_str("The current value."),
+                script.get_property_documentation("value".into())
+            );
+            assert_eq!(
+                GodotString::from_str("Emitted when the value changes."),
+                script.get_signal_documentation("changed".into())
+            );
+
+            let add = base
+                .get_method_list()
+                .iter()
+                .filter_map(|method| method.try_to_dictionary())
+                .find(|method| method.get(&"name".to_variant()) == "add".to_variant())
+                .expect("`add` should be in the method list");
+
+            let args = add.get(&"args".to_variant()).to_array();
+            let args = args
+                .iter()
+                .map(|arg| {
+                    let arg = arg.to_dictionary();
+                    (
+                        arg.get(&"name".to_variant()).to_string(),
+                        arg.get(&"type".to_variant()).to_i64(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                vec![
+                    ("amount".to_string(), VariantType::I64 as i64),
+                    ("arg1".to_string(), VariantType::Nil as i64),
+                    ("_tag".to_string(), VariantType::Nil as i64),
+                ],
+                args
+            );
+        }
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_register_docs failed");
+    }
+
+    ok
+}